@@ -233,7 +233,7 @@ pub fn character_profile_editor(
         name,
         color,
         stats,
-        traits,
+        traits: _,
     }: &mut CharacterProfile,
     species_colors: &SpeciesColors,
     species_data: &IndexMap<SpeciesId, CharacterSpeciesData>,
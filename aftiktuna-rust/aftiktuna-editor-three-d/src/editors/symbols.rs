@@ -129,6 +129,7 @@ pub fn local_symbols_editor(
                         aggressive: None,
                         wandering: None,
                         tag: None,
+                        held_item: None,
                         direction: None,
                     }),
                 })
@@ -223,6 +224,7 @@ pub enum SymbolEditAction {
 }
 
 /// Displays settings to edit data for one specific symbol.
+#[allow(clippy::too_many_arguments)]
 pub fn symbol_editor_ui(
     ui: &mut egui::Ui,
     symbol_edit_data: &mut SymbolEditData,
@@ -355,7 +357,13 @@ pub fn symbol_editor_ui(
             });
         }
         SymbolData::Creature(creature_spawn_data) => {
-            creature_spawn_data_editor(ui, creature_spawn_data, fauna_data, area_tags);
+            creature_spawn_data_editor(
+                ui,
+                creature_spawn_data,
+                fauna_data,
+                area_tags,
+                item_type_list,
+            );
         }
         SymbolData::Character(npc_spawn_data) => {
             npc_spawn_data_editor(
@@ -432,11 +440,13 @@ fn creature_spawn_data_editor(
         attribute,
         aggressive,
         wandering,
-        tag,
+        tag: _,
+        held_item,
         direction,
     }: &mut CreatureSpawnData,
     fauna_data: &IndexMap<SpeciesId, FaunaData>,
     area_tags: &HashSet<Tag>,
+    item_type_list: &[ItemTypeId],
 ) {
     super::species_editor(ui, creature, "fauna", fauna_data.keys());
 
@@ -522,6 +532,14 @@ fn creature_spawn_data_editor(
         },
     );
 
+    super::option_with_checkbox(
+        ui,
+        held_item,
+        "Holding item",
+        || item_type_list[0].clone(),
+        |ui, held_item| super::item_type_editor(ui, held_item, "creature_held", item_type_list),
+    );
+
     super::option_direction_editor(ui, direction, "creature_direction");
 }
 
@@ -531,10 +549,10 @@ fn npc_spawn_data_editor(
         profile,
         health,
         morale,
-        tag,
-        background,
-        interaction,
-        background_dialogue,
+        tag: _,
+        background: _,
+        interaction: _,
+        background_dialogue: _,
         wielded_item,
         direction,
     }: &mut NpcSpawnData,
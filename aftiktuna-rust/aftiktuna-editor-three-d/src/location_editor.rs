@@ -1,6 +1,7 @@
 mod ui {
     use aftiktuna::asset::location::{AreaData, SymbolMap};
     use aftiktuna::core::area::BackgroundId;
+    use aftiktuna::core::hazard::{Hazard, HazardKind};
     use aftiktuna::core::{BlockType, Tag};
     use aftiktuna_editor_three_d::editors::symbols::*;
     use aftiktuna_editor_three_d::editors::*;
@@ -157,6 +158,54 @@ mod ui {
         }
     }
 
+    fn hazard_list_editor(ui: &mut egui::Ui, hazards: &mut Vec<Hazard>, area_size: usize) {
+        ui.label("Hazards:");
+        let mut removed_index = None;
+        for (index, hazard) in hazards.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                egui::ComboBox::from_id_salt(("hazard_kind", index))
+                    .selected_text(format!("{:?}", hazard.kind))
+                    .show_ui(ui, |ui| {
+                        for &selectable_kind in HazardKind::variants() {
+                            ui.selectable_value(
+                                &mut hazard.kind,
+                                selectable_kind,
+                                format!("{selectable_kind:?}"),
+                            );
+                        }
+                    });
+                if ui.button("Remove").clicked() {
+                    removed_index = Some(index);
+                }
+            });
+            option_with_checkbox(
+                ui,
+                &mut hazard.coord,
+                "Centered on coord",
+                || 0,
+                |ui, coord| {
+                    ui.add(egui::Slider::new(
+                        coord,
+                        0..=area_size.saturating_sub(1) as u32,
+                    ));
+                },
+            );
+            if hazard.coord.is_some() {
+                ui.add(egui::Slider::new(&mut hazard.radius, 0..=5).text("Radius"));
+            }
+        }
+        if let Some(index) = removed_index {
+            hazards.remove(index);
+        }
+        if ui.button("Add Hazard").clicked() {
+            hazards.push(Hazard {
+                kind: HazardKind::ToxicGas,
+                coord: None,
+                radius: 0,
+            });
+        }
+    }
+
     fn overview_ui(editor_data: &mut super::EditorData, ui: &mut egui::Ui) {
         for (door_pair, pair_data) in &mut editor_data.location_data.door_pairs {
             let hovered_label = ui.label(door_pair).interact(egui::Sense::hover()).hovered();
@@ -182,6 +231,13 @@ mod ui {
                 })
                 .response
                 .hovered();
+            if pair_data.block_type == Some(BlockType::Locked) {
+                let key = pair_data.key.get_or_insert_with(String::new);
+                ui.horizontal(|ui| {
+                    ui.label("Key:");
+                    ui.text_edit_singleline(key);
+                });
+            }
             ui.checkbox(&mut pair_data.is_cut, "Is Cut");
 
             let is_connecting = matches!(&editor_data.connecting_pair, Some((connecting_pair, _)) if connecting_pair == door_pair);
@@ -235,6 +291,7 @@ mod ui {
                     background_offset: None,
                     extra_background_layers: Vec::default(),
                     darkness: 0.,
+                    hazards: Vec::new(),
                     objects: vec![String::default()],
                     variant_objects: Default::default(),
                     symbols: SymbolMap::new(),
@@ -323,6 +380,8 @@ mod ui {
 
             ui.add(egui::Slider::new(&mut area.darkness, 0.0..=1.0));
 
+            hazard_list_editor(ui, &mut area.hazards, area.objects.len());
+
             background_layer_list_editor(
                 ui,
                 selected_extra_background_layer,
@@ -598,69 +657,62 @@ fn handle_overview_input(
                 position,
                 handled,
                 ..
-            } => {
-                if !*handled && *button == three_d::MouseButton::Left {
-                    let (mouse_x, mouse_y) = mouse_to_overview_pos(*position, scale_factor);
-                    let clicked_area = editor_data
-                        .location_data
-                        .areas
-                        .iter()
-                        .enumerate()
-                        .find(|(_, area)| {
-                            let offset = area_offset(area);
-                            area.pos_in_overview.0 + offset <= mouse_x
-                                && mouse_x
-                                    < area.pos_in_overview.0 + offset + area.objects.len() as i32
-                                && mouse_y == area.pos_in_overview.1
-                        })
-                        .map(|(index, _)| index);
-
-                    if let Some((door_pair_id, current_selected)) = &mut editor_data.connecting_pair
-                        && let Some(clicked_area) = clicked_area
-                    {
-                        let area = &editor_data.location_data.areas[clicked_area];
+            } if !*handled && *button == three_d::MouseButton::Left => {
+                let (mouse_x, mouse_y) = mouse_to_overview_pos(*position, scale_factor);
+                let clicked_area = editor_data
+                    .location_data
+                    .areas
+                    .iter()
+                    .enumerate()
+                    .find(|(_, area)| {
                         let offset = area_offset(area);
-                        let clicked_coord = mouse_x - area.pos_in_overview.0 - offset;
-
-                        if let Some(door_char) = area.objects[clicked_coord as usize]
-                            .chars()
-                            .chain(
-                                area.variant_objects
-                                    .get(&editor_data.selected_variant)
-                                    .into_iter()
-                                    .flat_map(|objects| objects[clicked_coord as usize].chars()),
-                            )
-                            .find(|char| {
-                                matches!(area.symbols.get(char), Some(SymbolData::Door(_)))
-                            })
-                        {
-                            current_selected.push(AreaSymbolId {
-                                area_index: clicked_area,
-                                symbol: door_char,
-                            });
-                            if let [door_id_1, door_id_2] = current_selected[..] {
-                                connect_paths(
-                                    &mut editor_data.location_data,
-                                    door_pair_id.clone(),
-                                    door_id_1,
-                                    door_id_2,
-                                );
-                                editor_data.connecting_pair = None;
-                            }
+                        area.pos_in_overview.0 + offset <= mouse_x
+                            && mouse_x < area.pos_in_overview.0 + offset + area.objects.len() as i32
+                            && mouse_y == area.pos_in_overview.1
+                    })
+                    .map(|(index, _)| index);
+
+                if let Some((door_pair_id, current_selected)) = &mut editor_data.connecting_pair
+                    && let Some(clicked_area) = clicked_area
+                {
+                    let area = &editor_data.location_data.areas[clicked_area];
+                    let offset = area_offset(area);
+                    let clicked_coord = mouse_x - area.pos_in_overview.0 - offset;
+
+                    if let Some(door_char) = area.objects[clicked_coord as usize]
+                        .chars()
+                        .chain(
+                            area.variant_objects
+                                .get(&editor_data.selected_variant)
+                                .into_iter()
+                                .flat_map(|objects| objects[clicked_coord as usize].chars()),
+                        )
+                        .find(|char| matches!(area.symbols.get(char), Some(SymbolData::Door(_))))
+                    {
+                        current_selected.push(AreaSymbolId {
+                            area_index: clicked_area,
+                            symbol: door_char,
+                        });
+                        if let [door_id_1, door_id_2] = current_selected[..] {
+                            connect_paths(
+                                &mut editor_data.location_data,
+                                door_pair_id.clone(),
+                                door_id_1,
+                                door_id_2,
+                            );
+                            editor_data.connecting_pair = None;
                         }
-                    } else {
-                        editor_data.dragged_area = clicked_area;
                     }
-                    *handled = true;
+                } else {
+                    editor_data.dragged_area = clicked_area;
                 }
+                *handled = true;
             }
             three_d::Event::MouseRelease {
                 button, handled, ..
-            } => {
-                if editor_data.dragged_area.is_some() && *button == three_d::MouseButton::Left {
-                    editor_data.dragged_area = None;
-                    *handled = true;
-                }
+            } if editor_data.dragged_area.is_some() && *button == three_d::MouseButton::Left => {
+                editor_data.dragged_area = None;
+                *handled = true;
             }
             three_d::Event::MouseMotion {
                 position, handled, ..
@@ -309,13 +309,13 @@ fn model_editor_ui(
         if ui.button("Use Texture Size").clicked() {
             layer.positioning.size = None;
         }
-    } else if let TexturesOrChildren::Texture(colored_textures) = &layer.textures_or_children {
-        if ui.button("Use Custom Size").clicked() {
-            let texture = textures
-                .load_texture(model::texture_path(colored_textures.primary_texture()))
-                .unwrap();
-            layer.positioning.size = Some((texture.width() as i16, texture.height() as i16));
-        }
+    } else if let TexturesOrChildren::Texture(colored_textures) = &layer.textures_or_children
+        && ui.button("Use Custom Size").clicked()
+    {
+        let texture = textures
+            .load_texture(model::texture_path(colored_textures.primary_texture()))
+            .unwrap();
+        layer.positioning.size = Some((texture.width() as i16, texture.height() as i16));
     }
 
     ui.label("Offset:");
@@ -1,10 +1,11 @@
-use aftiktuna::serialization::LoadError;
+use aftiktuna::core::{CREW_SIZE_LIMIT, SpeciesId};
+use aftiktuna::run_config::{self, RunConfig};
+use aftiktuna::serialization::{LoadError, SaveSlot};
 use aftiktuna::{game_interface, serialization};
 use aftiktuna_three_d::asset::{Assets, BuiltinFonts};
 use aftiktuna_three_d::game::{self, GameAction};
 use aftiktuna_three_d::{dimensions, render};
 use std::env;
-use std::path::Path;
 use std::rc::Rc;
 use three_d::egui;
 use winit::event::{Event as WinitEvent, WindowEvent};
@@ -162,24 +163,47 @@ struct LoadedApp {
     assets: Assets,
     state: AppState,
     autosave: bool,
+    enable_undo: bool,
+    enable_combat_log: bool,
+    seed: Option<u64>,
     close_after_ending: bool,
 }
 
 impl LoadedApp {
     fn load(context: &three_d::Context, builtin_fonts: Rc<BuiltinFonts>) -> Result<Self, String> {
+        if let Some(language) =
+            env::args().find_map(|arg| arg.strip_prefix("--language=").map(str::to_owned))
+        {
+            aftiktuna::asset::select_language(&language).map_err(|error| error.to_string())?;
+        }
         let mut assets =
             Assets::load(context.clone(), builtin_fonts).map_err(|error| error.to_string())?;
 
         let disable_autosave = env::args().any(|arg| arg.eq("--disable-autosave"));
         let new_game = env::args().any(|arg| arg.eq("--new-game"));
+        let enable_undo = env::args().any(|arg| arg.eq("--enable-undo"));
+        let enable_combat_log = env::args().any(|arg| arg.eq("--combat-log"));
         if disable_autosave {
             println!("Running without autosave");
         }
         let autosave = !disable_autosave;
+        let seed = env::args()
+            .find_map(|arg| arg.strip_prefix("--seed=").map(str::to_owned))
+            .map(|seed| {
+                seed.parse::<u64>()
+                    .map_err(|error| format!("Invalid seed \"{seed}\": {error}"))
+            })
+            .transpose()?;
 
         let state = if new_game {
-            let game = game_interface::setup_new()?;
-            AppState::game(game, autosave, &mut assets)
+            let game = setup_new_game(RunConfig::default(), seed)?;
+            AppState::game(
+                game,
+                autosave.then(serialization::new_slot_name),
+                enable_undo,
+                enable_combat_log,
+                &mut assets,
+            )
         } else {
             AppState::main_menu()
         };
@@ -191,6 +215,9 @@ impl LoadedApp {
             assets,
             state,
             autosave,
+            enable_undo,
+            enable_combat_log,
+            seed,
             close_after_ending: new_game,
         })
     }
@@ -209,24 +236,75 @@ impl LoadedApp {
                     }
                 }
             }
-            AppState::MainMenu { has_save_file } => {
-                let menu_action = handle_menu_frame(*has_save_file, frame_input, &mut self.gui);
+            AppState::MainMenu { slots } => {
+                let menu_action = handle_menu_frame(slots, frame_input, &mut self.gui);
 
                 match menu_action {
-                    Some(MenuAction::NewGame) => match game_interface::setup_new() {
-                        Ok(game) => {
-                            self.state = AppState::game(game, self.autosave, &mut self.assets)
+                    Some(MenuAction::NewGame) => {
+                        let species_options =
+                            run_config::selectable_crew_species().unwrap_or_else(|error| {
+                                eprintln!("Unable to load species: {error}");
+                                Vec::new()
+                            });
+                        self.state = AppState::NewGameSetup {
+                            run_config: RunConfig::default(),
+                            species_options,
                         }
-                        Err(error) => {
-                            return (
-                                AppAction::Continue,
-                                vec![format!("Unable to load assets:"), error],
-                            );
+                    }
+                    Some(MenuAction::PickSlot) => {
+                        self.state = AppState::SlotPicker {
+                            slots: std::mem::take(slots),
                         }
-                    },
-                    Some(MenuAction::LoadGame) => match game_interface::load() {
+                    }
+                    _ => {}
+                }
+            }
+            AppState::NewGameSetup {
+                run_config,
+                species_options,
+            } => {
+                let menu_action =
+                    handle_new_game_frame(run_config, species_options, frame_input, &mut self.gui);
+
+                match menu_action {
+                    Some(MenuAction::StartGame) => {
+                        match setup_new_game(run_config.clone(), self.seed) {
+                            Ok(game) => {
+                                let save_slot = self.autosave.then(serialization::new_slot_name);
+                                self.state = AppState::game(
+                                    game,
+                                    save_slot,
+                                    self.enable_undo,
+                                    self.enable_combat_log,
+                                    &mut self.assets,
+                                )
+                            }
+                            Err(error) => {
+                                return (
+                                    AppAction::Continue,
+                                    vec!["Unable to start a new game:".to_string(), error],
+                                );
+                            }
+                        }
+                    }
+                    Some(MenuAction::Back) => self.state = AppState::main_menu(),
+                    _ => {}
+                }
+            }
+            AppState::SlotPicker { slots } => {
+                let menu_action = handle_slot_picker_frame(slots, frame_input, &mut self.gui);
+
+                match menu_action {
+                    Some(MenuAction::LoadGame(slot)) => match game_interface::load(&slot) {
                         Ok(game) => {
-                            self.state = AppState::game(game, self.autosave, &mut self.assets)
+                            let save_slot = self.autosave.then_some(slot);
+                            self.state = AppState::game(
+                                game,
+                                save_slot,
+                                self.enable_undo,
+                                self.enable_combat_log,
+                                &mut self.assets,
+                            )
                         }
                         Err(error) => {
                             let recommendation = if matches!(
@@ -246,7 +324,8 @@ impl LoadedApp {
                             );
                         }
                     },
-                    None => {}
+                    Some(MenuAction::Back) => self.state = AppState::main_menu(),
+                    _ => {}
                 }
             }
         }
@@ -261,28 +340,86 @@ impl LoadedApp {
     }
 }
 
+fn setup_new_game(
+    run_config: RunConfig,
+    seed: Option<u64>,
+) -> Result<game_interface::Game, String> {
+    let game = game_interface::setup_new(run_config, seed)?;
+    if let Some(seed) = game.seed() {
+        println!("Starting new game with seed {seed}");
+    }
+    Ok(game)
+}
+
 enum AppState {
-    MainMenu { has_save_file: bool },
+    MainMenu {
+        slots: Vec<SaveSlot>,
+    },
+    NewGameSetup {
+        run_config: RunConfig,
+        species_options: Vec<SpeciesId>,
+    },
+    SlotPicker {
+        slots: Vec<SaveSlot>,
+    },
     Game(Box<game::State>),
 }
 
 impl AppState {
     fn main_menu() -> Self {
-        let has_save_file = Path::new(serialization::SAVE_FILE_NAME).exists();
-        Self::MainMenu { has_save_file }
+        let slots = serialization::list_save_slots().unwrap_or_else(|error| {
+            eprintln!("Unable to list saved games: {error}");
+            Vec::new()
+        });
+        Self::MainMenu { slots }
     }
-    fn game(game: game_interface::Game, is_save_enabled: bool, assets: &mut Assets) -> Self {
-        Self::Game(Box::new(game::State::init(game, is_save_enabled, assets)))
+    fn game(
+        mut game: game_interface::Game,
+        save_slot: Option<String>,
+        enable_undo: bool,
+        enable_combat_log: bool,
+        assets: &mut Assets,
+    ) -> Self {
+        if enable_undo {
+            game.enable_undo();
+        }
+        if enable_combat_log {
+            game.enable_combat_log();
+        }
+        Self::Game(Box::new(game::State::init(game, save_slot, assets)))
     }
 }
 
 enum MenuAction {
     NewGame,
-    LoadGame,
+    StartGame,
+    PickSlot,
+    LoadGame(String),
+    Back,
+}
+
+const BUTTON_FONT: egui::FontId = egui::FontId::proportional(22.);
+const BUTTON_COLOR: egui::Color32 = egui::Color32::from_rgba_premultiplied(
+    (0.2 * 0.6 * 255.) as u8,
+    (0.1 * 0.6 * 255.) as u8,
+    (0.4 * 0.6 * 255.) as u8,
+    (0.6 * 255.) as u8,
+);
+
+fn menu_button(ui: &mut egui::Ui, text: &str) -> bool {
+    ui.add(
+        egui::Button::new(
+            egui::RichText::new(text)
+                .font(BUTTON_FONT)
+                .color(egui::Color32::WHITE),
+        )
+        .fill(BUTTON_COLOR),
+    )
+    .clicked()
 }
 
 fn handle_menu_frame(
-    has_save_file: bool,
+    slots: &[SaveSlot],
     mut frame_input: three_d::FrameInput,
     gui: &mut three_d::GUI,
 ) -> Option<MenuAction> {
@@ -306,42 +443,15 @@ fn handle_menu_frame(
                         );
 
                         ui.style_mut().spacing.button_padding = egui::vec2(46., 18.);
-                        const BUTTON_FONT: egui::FontId = egui::FontId::proportional(22.);
-                        const BUTTON_COLOR: egui::Color32 = egui::Color32::from_rgba_premultiplied(
-                            (0.2 * 0.6 * 255.) as u8,
-                            (0.1 * 0.6 * 255.) as u8,
-                            (0.4 * 0.6 * 255.) as u8,
-                            (0.6 * 255.) as u8,
-                        );
 
                         ui.add_space(124.);
-                        let pressed_new_game = ui
-                            .add(
-                                egui::Button::new(
-                                    egui::RichText::new("New Game")
-                                        .font(BUTTON_FONT)
-                                        .color(egui::Color32::WHITE),
-                                )
-                                .fill(BUTTON_COLOR),
-                            )
-                            .clicked();
-                        if pressed_new_game {
+                        if menu_button(ui, "New Game") {
                             menu_action = Some(MenuAction::NewGame);
                         }
-                        if has_save_file {
+                        if !slots.is_empty() {
                             ui.add_space(38.);
-                            let pressed_load_game = ui
-                                .add(
-                                    egui::Button::new(
-                                        egui::RichText::new("Load Game")
-                                            .font(BUTTON_FONT)
-                                            .color(egui::Color32::WHITE),
-                                    )
-                                    .fill(BUTTON_COLOR),
-                                )
-                                .clicked();
-                            if pressed_load_game {
-                                menu_action = Some(MenuAction::LoadGame);
+                            if menu_button(ui, "Load Game") {
+                                menu_action = Some(MenuAction::PickSlot);
                             }
                         }
                     });
@@ -355,3 +465,192 @@ fn handle_menu_frame(
 
     menu_action
 }
+
+const SETTING_FONT: egui::FontId = egui::FontId::proportional(18.);
+
+fn setting_text(text: &str) -> egui::RichText {
+    egui::RichText::new(text)
+        .font(SETTING_FONT)
+        .color(egui::Color32::WHITE)
+}
+
+fn handle_new_game_frame(
+    run_config: &mut RunConfig,
+    species_options: &[SpeciesId],
+    mut frame_input: three_d::FrameInput,
+    gui: &mut three_d::GUI,
+) -> Option<MenuAction> {
+    let mut menu_action = None;
+    gui.update(
+        &mut frame_input.events,
+        frame_input.accumulated_time,
+        frame_input.viewport,
+        frame_input.device_pixel_ratio,
+        |ui| {
+            egui::CentralPanel::default()
+                .frame(egui::Frame::NONE)
+                .show_inside(ui, |ui| {
+                    ui.with_layout(egui::Layout::top_down(egui::Align::Center), |ui| {
+                        ui.add_space(40.);
+                        ui.label(
+                            egui::RichText::new("New Game")
+                                .font(egui::FontId::proportional(32.))
+                                .color(egui::Color32::WHITE),
+                        );
+                        ui.add_space(40.);
+
+                        egui::Grid::new("run_config")
+                            .num_columns(2)
+                            .spacing(egui::vec2(24., 16.))
+                            .show(ui, |ui| run_config_editor(ui, run_config, species_options));
+
+                        ui.add_space(40.);
+                        ui.style_mut().spacing.button_padding = egui::vec2(46., 18.);
+                        if menu_button(ui, "Start") {
+                            menu_action = Some(MenuAction::StartGame);
+                        }
+                        ui.add_space(20.);
+                        if menu_button(ui, "Back") {
+                            menu_action = Some(MenuAction::Back);
+                        }
+                    });
+                });
+        },
+    );
+
+    let screen = frame_input.screen();
+    screen.clear(three_d::ClearState::color_and_depth(0., 0., 0., 1., 1.));
+    screen.write(|| gui.render()).unwrap();
+
+    menu_action
+}
+
+const DEFAULT_CUSTOM_POINTS: i32 = 10000;
+
+fn run_config_editor(ui: &mut egui::Ui, run_config: &mut RunConfig, species_options: &[SpeciesId]) {
+    ui.label(setting_text("Locations before Fortuna"));
+    ui.add(egui::DragValue::new(&mut run_config.locations_before_fortuna).range(1..=10));
+    ui.end_row();
+
+    ui.label(setting_text("Difficulty"));
+    ui.add(
+        egui::Slider::new(
+            &mut run_config.difficulty,
+            RunConfig::MIN_DIFFICULTY..=RunConfig::MAX_DIFFICULTY,
+        )
+        .step_by(0.25),
+    );
+    ui.end_row();
+
+    let mut custom_points = run_config.starting_points.is_some();
+    if ui
+        .checkbox(&mut custom_points, setting_text("Custom starting points"))
+        .changed()
+    {
+        run_config.starting_points = custom_points.then_some(DEFAULT_CUSTOM_POINTS);
+    }
+    if let Some(points) = &mut run_config.starting_points {
+        ui.add(egui::DragValue::new(points).range(0..=100000).speed(100));
+    } else {
+        ui.label("");
+    }
+    ui.end_row();
+
+    let Some(default_species) = species_options.first() else {
+        return;
+    };
+    let mut custom_crew = run_config.crew_species.is_some();
+    if ui
+        .checkbox(&mut custom_crew, setting_text("Custom crew"))
+        .changed()
+    {
+        run_config.crew_species = custom_crew.then(|| vec![default_species.clone()]);
+    }
+    if let Some(crew_species) = &mut run_config.crew_species {
+        ui.horizontal(|ui| {
+            for (index, species) in crew_species.iter_mut().enumerate() {
+                egui::ComboBox::from_id_salt(("crew_species", index))
+                    .selected_text(species.to_string())
+                    .show_ui(ui, |ui| {
+                        for option in species_options {
+                            ui.selectable_value(species, option.clone(), option.to_string());
+                        }
+                    });
+            }
+            if crew_species.len() < CREW_SIZE_LIMIT && ui.button("+").clicked() {
+                crew_species.push(default_species.clone());
+            }
+            if crew_species.len() > 1 && ui.button("-").clicked() {
+                crew_species.pop();
+            }
+        });
+    } else {
+        ui.label("");
+    }
+    ui.end_row();
+}
+
+fn handle_slot_picker_frame(
+    slots: &[SaveSlot],
+    mut frame_input: three_d::FrameInput,
+    gui: &mut three_d::GUI,
+) -> Option<MenuAction> {
+    let mut menu_action = None;
+    gui.update(
+        &mut frame_input.events,
+        frame_input.accumulated_time,
+        frame_input.viewport,
+        frame_input.device_pixel_ratio,
+        |ui| {
+            egui::CentralPanel::default()
+                .frame(egui::Frame::NONE)
+                .show_inside(ui, |ui| {
+                    ui.with_layout(egui::Layout::top_down(egui::Align::Center), |ui| {
+                        ui.add_space(40.);
+                        ui.label(
+                            egui::RichText::new("Load Game")
+                                .font(egui::FontId::proportional(32.))
+                                .color(egui::Color32::WHITE),
+                        );
+                        ui.add_space(20.);
+
+                        egui::ScrollArea::vertical()
+                            .max_height(440.)
+                            .show(ui, |ui| {
+                                ui.style_mut().spacing.button_padding = egui::vec2(16., 8.);
+                                for slot in slots {
+                                    let summary = slot
+                                        .metadata
+                                        .as_ref()
+                                        .map(serialization::SaveMetadata::summary)
+                                        .unwrap_or_default();
+                                    let text =
+                                        egui::RichText::new(format!("{}\n{summary}", slot.name))
+                                            .size(16.)
+                                            .color(egui::Color32::WHITE);
+                                    let pressed = ui
+                                        .add(egui::Button::new(text).fill(BUTTON_COLOR))
+                                        .clicked();
+                                    if pressed {
+                                        menu_action = Some(MenuAction::LoadGame(slot.name.clone()));
+                                    }
+                                    ui.add_space(8.);
+                                }
+                            });
+
+                        ui.add_space(20.);
+                        ui.style_mut().spacing.button_padding = egui::vec2(46., 18.);
+                        if menu_button(ui, "Back") {
+                            menu_action = Some(MenuAction::Back);
+                        }
+                    });
+                });
+        },
+    );
+
+    let screen = frame_input.screen();
+    screen.clear(three_d::ClearState::color_and_depth(0., 0., 0., 1., 1.));
+    screen.write(|| gui.render()).unwrap();
+
+    menu_action
+}
@@ -1,5 +1,6 @@
 use aftiktuna::game_interface;
 use aftiktuna::location::GenerationState;
+use aftiktuna::run_config::RunConfig;
 use aftiktuna_three_d::asset::{Assets, BuiltinFonts};
 use aftiktuna_three_d::dimensions;
 use aftiktuna_three_d::game::{GameAction, State};
@@ -15,8 +16,13 @@ const SIZE: (u32, u32) = (
 fn main() {
     let args: Vec<String> = env::args().collect();
     let location = &args[1];
+    let seed = args
+        .get(2)
+        .map(|seed| seed.parse::<u64>().expect("Expected seed to be a number"));
     let game = game_interface::setup_new_with(
-        GenerationState::single(location.to_owned()).expect("Unable to initialize game"),
+        GenerationState::single(location.to_owned(), RunConfig::default())
+            .expect("Unable to initialize game"),
+        seed,
     )
     .expect("Unable to initialize game");
 
@@ -31,7 +37,7 @@ fn main() {
     let mut assets = Assets::load(window.gl(), Rc::new(BuiltinFonts::init())).unwrap();
     let mut gui = three_d::GUI::new(&window.gl());
 
-    let mut state = State::init(game, false, &mut assets);
+    let mut state = State::init(game, None, &mut assets);
 
     window.render_loop(move |frame_input| {
         let action = state.handle_game_frame(frame_input, &mut gui, &mut assets);
@@ -9,14 +9,13 @@ use aftiktuna::game_interface::{Game, GameResult};
 use aftiktuna::serialization;
 use aftiktuna::view::area::ObjectRenderData;
 use aftiktuna::view::{Frame, FullStatus};
-use std::fs;
 
 pub enum GameAction {
     ExitGame,
 }
 
 pub struct State {
-    is_save_enabled: bool,
+    save_slot: Option<String>,
     game: Game,
     frame: Frame,
     cached_objects: Vec<(three_d::Vec2, ObjectRenderData)>,
@@ -30,9 +29,14 @@ pub struct State {
 }
 
 impl State {
-    pub fn init(game: Game, is_save_enabled: bool, assets: &mut Assets) -> Self {
+    /// Sets up the game state. If a save slot is given, the game is saved to it when exiting,
+    /// and autosaved each time the ship lands.
+    pub fn init(mut game: Game, save_slot: Option<String>, assets: &mut Assets) -> Self {
+        if save_slot.is_some() {
+            game.enable_autosave();
+        }
         let mut state = Self {
-            is_save_enabled,
+            save_slot,
             game,
             frame: Frame::Introduction,
             cached_objects: Vec::new(),
@@ -100,6 +104,19 @@ impl State {
             if self.input_text.eq_ignore_ascii_case("exit game") {
                 self.save_game_if_enabled();
                 action = Some(GameAction::ExitGame);
+            } else if self.input_text.eq_ignore_ascii_case("undo") {
+                if self.game.undo() {
+                    self.try_get_next_frame(&mut assets.models);
+                } else {
+                    self.text_box_text = vec!["There is no turn to undo.".to_owned()];
+                    self.request_input_focus = true;
+                }
+            } else if self.input_text.eq_ignore_ascii_case("export combat log") {
+                self.text_box_text = vec![match self.game.export_combat_log() {
+                    Ok(path) => format!("Exported the combat log to \"{path}\"."),
+                    Err(error) => error,
+                }];
+                self.request_input_focus = true;
             } else {
                 let result = self.game.handle_input(&self.input_text);
 
@@ -168,11 +185,14 @@ impl State {
     }
 
     pub fn save_game_if_enabled(&self) {
-        if !matches!(self.frame, Frame::Ending { .. }) && self.is_save_enabled {
-            if let Err(error) = serialization::write_game_to_save_file(&self.game) {
+        if matches!(self.frame, Frame::Ending { .. }) {
+            return;
+        }
+        if let Some(slot) = &self.save_slot {
+            if let Err(error) = serialization::write_game_to_save_slot(&self.game, slot) {
                 eprintln!("Failed to save game: {error}");
             } else {
-                println!("Saved the game successfully.")
+                println!("Saved the game to slot \"{slot}\".")
             }
         }
     }
@@ -190,8 +210,8 @@ impl State {
             } else {
                 self.cached_objects = Vec::new();
             }
-            if matches!(self.frame, Frame::Ending { .. }) && self.is_save_enabled {
-                let _ = fs::remove_file(serialization::SAVE_FILE_NAME);
+            if let (Frame::Ending { .. }, Some(slot)) = (&self.frame, &self.save_slot) {
+                serialization::delete_saves_of_finished_run(slot);
             }
             self.text_box_text = self.frame.get_messages();
             self.request_input_focus = self.game.ready_to_take_input();
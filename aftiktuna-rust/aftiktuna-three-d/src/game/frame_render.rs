@@ -3,6 +3,7 @@ use crate::{dimensions, render, Camera};
 use aftiktuna::asset::model::ModelAccess;
 use aftiktuna::asset::placement;
 use aftiktuna::core::area::BackgroundId;
+use aftiktuna::core::hazard::HazardKind;
 use aftiktuna::core::position::Direction;
 use aftiktuna::view::area::{ObjectProperties, ObjectRenderData, RenderData};
 use aftiktuna::view::{self, Frame, StoreStockView};
@@ -18,7 +19,7 @@ pub fn render_frame(
     assets: &mut Assets,
 ) {
     match frame {
-        Frame::Introduction | Frame::LocationChoice(_) | Frame::Error(_) => {
+        Frame::Introduction | Frame::LocationChoice(_) | Frame::Map(_) | Frame::Error(_) => {
             let background_objects = render::render_objects_for_primary_background(
                 assets
                     .backgrounds
@@ -97,7 +98,7 @@ pub fn render_frame(
         Frame::StoreView { view, .. } => {
             draw_store_view(view, screen, frame_input, assets);
         }
-        Frame::Ending { stop_type } => {
+        Frame::Ending { stop_type, .. } => {
             let (r, g, b) = match stop_type {
                 aftiktuna::StopType::Win => (0.78, 0.78, 0.78),
                 aftiktuna::StopType::Lose => (0., 0., 0.),
@@ -176,6 +177,8 @@ fn draw_area_view(
         })
         .unwrap();
 
+    draw_hazards(render_data, &render_camera, screen, frame_input);
+
     if render_data.darkness > 0. {
         let light_pos = three_d::vec2(
             placement::coord_to_center_x(render_data.character_coord) - camera.camera_x,
@@ -200,6 +203,64 @@ fn draw_area_view(
     );
 }
 
+/// Draws a pulsing overlay over the coordinates covered by each hazard.
+fn draw_hazards(
+    render_data: &RenderData,
+    render_camera: &three_d::Camera,
+    screen: &three_d::RenderTarget<'_>,
+    frame_input: &three_d::FrameInput,
+) {
+    if render_data.hazards.is_empty() {
+        return;
+    }
+
+    let alpha_factor = ((frame_input.accumulated_time / 1000. * 2.).sin() + 3.) / 4.;
+    let hazard_objects = render_data
+        .hazards
+        .iter()
+        .flat_map(|hazard| {
+            let ((r, g, b, a), center_y, height) = match hazard.kind {
+                HazardKind::ToxicGas => ((110, 200, 60, 90.), 260., 220.),
+                HazardKind::Fire => ((255, 110, 20, 110.), 230., 160.),
+                HazardKind::Radiation => ((200, 255, 80, 70.), 300., 300.),
+                HazardKind::UnstableFloor => ((90, 60, 30, 150.), 170., 40.),
+            };
+            let material = three_d::ColorMaterial {
+                color: three_d::Srgba::new(r, g, b, (a * alpha_factor).round() as u8),
+                render_states: three_d::RenderStates {
+                    write_mask: three_d::WriteMask::COLOR,
+                    blend: crate::TRANSPARENCY_BLEND,
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            (0..render_data.area_size)
+                .filter(|&coord| hazard.covers(coord))
+                .map(move |coord| {
+                    three_d::Gm::new(
+                        three_d::Rectangle::new(
+                            &frame_input.context,
+                            three_d::vec2(placement::coord_to_center_x(coord), center_y),
+                            three_d::degrees(0.),
+                            120.,
+                            height,
+                        ),
+                        material.clone(),
+                    )
+                })
+        })
+        .collect::<Vec<_>>();
+
+    screen
+        .write::<three_d::RendererError>(|| {
+            for object in hazard_objects {
+                object.render(render_camera, &[]);
+            }
+            Ok(())
+        })
+        .unwrap();
+}
+
 fn draw_camera_arrows(
     [left_drag, right_drag]: [bool; 2],
     arrow_texture: &three_d::Texture2DRef,
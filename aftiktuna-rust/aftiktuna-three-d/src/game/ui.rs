@@ -1,7 +1,8 @@
 use crate::asset::{Assets, LazilyLoadedModels};
 use crate::{dimensions, render};
 use aftiktuna::command_suggestion::Suggestion;
-use aftiktuna::view::FullStatus;
+use aftiktuna::view::map::MapView;
+use aftiktuna::view::{Frame, FullStatus};
 use three_d::egui;
 
 #[derive(Default)]
@@ -57,6 +58,10 @@ pub fn update_ui(
                 ui_result.clicked_text_box = text_box_panel(&state.text_box_text, ui);
             }
 
+            if let Frame::Map(map_view) = &state.frame {
+                paint_map(map_view, ui);
+            }
+
             if let Some(status) = &state.displayed_status {
                 ui_result.closed_status_window = !show_status_screen(status, ui.ctx());
             } else {
@@ -121,6 +126,102 @@ fn show_status_screen(status: &FullStatus, egui_context: &egui::Context) -> bool
     is_open
 }
 
+const MAP_OUTER_MARGIN: f32 = 40.;
+const MAP_MAX_CELL_SIZE: f32 = 24.;
+const MAP_AREA_COLOR: egui::Color32 = egui::Color32::from_rgb(70, 60, 90);
+const MAP_CONTROLLED_AREA_COLOR: egui::Color32 = egui::Color32::from_rgb(110, 90, 150);
+const MAP_SHIP_COLOR: egui::Color32 = egui::Color32::from_rgb(220, 200, 80);
+const MAP_CREW_COLOR: egui::Color32 = egui::Color32::from_rgb(120, 220, 120);
+
+fn paint_map(map_view: &MapView, ui: &egui::Ui) {
+    let map_rect = egui::Rect::from_min_max(
+        egui::Pos2::ZERO,
+        egui::pos2(
+            dimensions::WINDOW_WIDTH_F,
+            dimensions::WINDOW_HEIGHT_F - INPUT_PANEL_HEIGHT - TEXT_PANEL_HEIGHT,
+        ),
+    )
+    .shrink(MAP_OUTER_MARGIN);
+    let painter = ui.painter_at(map_rect);
+
+    if map_view.areas.is_empty() {
+        return;
+    }
+    let (mut min_x, mut max_x, mut min_y, mut max_y) = (i32::MAX, i32::MIN, i32::MAX, i32::MIN);
+    for area in &map_view.areas {
+        min_x = min_x.min(area.start.0);
+        max_x = max_x.max(area.start.0 + area.size as i32);
+        min_y = min_y.min(area.start.1);
+        max_y = max_y.max(area.start.1);
+    }
+
+    let cell_size = (map_rect.width() / (max_x - min_x) as f32)
+        .min(map_rect.height() / (max_y - min_y + 1) as f32)
+        .min(MAP_MAX_CELL_SIZE);
+    let map_size = egui::vec2(
+        (max_x - min_x) as f32 * cell_size,
+        (max_y - min_y + 1) as f32 * cell_size,
+    );
+    let origin = map_rect.center() - map_size / 2.;
+    // The y-axis of the overview points upwards, while it points downwards on screen.
+    let to_screen = |(x, y): (i32, i32)| {
+        origin + egui::vec2((x - min_x) as f32, (max_y - y) as f32) * cell_size
+    };
+    let cell_center = |pos: (i32, i32)| to_screen(pos) + egui::Vec2::splat(cell_size / 2.);
+
+    for &(from, to) in &map_view.connections {
+        painter.line_segment(
+            [cell_center(from), cell_center(to)],
+            egui::Stroke::new(2., egui::Color32::WHITE),
+        );
+    }
+
+    for area in &map_view.areas {
+        let area_rect = egui::Rect::from_min_size(
+            to_screen(area.start),
+            egui::vec2(area.size as f32 * cell_size, cell_size),
+        )
+        .shrink(cell_size / 6.);
+        let fill = if area.has_controlled {
+            MAP_CONTROLLED_AREA_COLOR
+        } else {
+            MAP_AREA_COLOR
+        };
+        painter.rect_filled(area_rect, 2., fill);
+        painter.rect_stroke(
+            area_rect,
+            2.,
+            egui::Stroke::new(1., egui::Color32::WHITE),
+            egui::StrokeKind::Inside,
+        );
+        painter.text(
+            area_rect.center_top(),
+            egui::Align2::CENTER_BOTTOM,
+            &area.label,
+            TEXT_BOX_FONT,
+            egui::Color32::WHITE,
+        );
+        if area.has_ship {
+            painter.text(
+                area_rect.left_center() + egui::vec2(4., 0.),
+                egui::Align2::LEFT_CENTER,
+                "Ship",
+                TEXT_BOX_FONT,
+                MAP_SHIP_COLOR,
+            );
+        }
+        if !area.crew.is_empty() {
+            painter.text(
+                area_rect.center_bottom(),
+                egui::Align2::CENTER_TOP,
+                area.crew.join(", "),
+                TEXT_BOX_FONT,
+                MAP_CREW_COLOR,
+            );
+        }
+    }
+}
+
 fn show_tooltip_and_menu(
     state: &super::State,
     models: &mut LazilyLoadedModels,
@@ -5,8 +5,9 @@ pub mod item;
 mod ship;
 mod trade;
 
-use crate::action::item::UseAction;
-use crate::core::behavior::{Hostile, Recruitable, RepeatingAction};
+use crate::action::item::{TreatAction, UseAction};
+use crate::core::area::ShipUpgrade;
+use crate::core::behavior::{Hostile, Order, Recruitable, RepeatingAction};
 use crate::core::combat::AttackKind;
 use crate::core::item::ItemTypeId;
 use crate::core::name::{Name, NameData, NameIdData, NameQuery};
@@ -14,8 +15,10 @@ use crate::core::position::{self, Placement, PlacementQuery, Pos};
 use crate::core::{self, CrewMember, Door, FortunaChest, OpenedChest, inventory, status};
 use crate::game_loop::GameState;
 use crate::view;
+use crate::view::event::GameEvent;
 use crate::view::text::{CombinableMsgType, IntoMessage};
 use hecs::{Entity, World};
+use rand::seq::SliceRandom;
 use std::collections::HashMap;
 use std::result;
 
@@ -29,7 +32,10 @@ pub enum Action {
     Search(item::SearchAction),
     GiveItem(Entity, Entity),
     Wield(Entity, NameData),
+    Wear(Entity, NameData),
+    TakeOff(Entity),
     Use(item::UseAction),
+    Treat(item::TreatAction),
     EnterDoor(Entity),
     ForceDoor(ForceDoorAction),
     GoToShip,
@@ -40,12 +46,16 @@ pub enum Action {
     Rest(bool),
     Refuel,
     Launch,
+    StoreInShip(Entity),
+    RetrieveFromShip(Entity),
+    InstallUpgrade(ShipUpgrade),
     TalkTo(TalkAction),
     Recruit(Entity),
     AnswerYesNoDecision(AnswerYesNoDecisionAction),
     TellToWait(Entity),
     TellToWaitAtShip(Entity),
     TellToFollow(Entity),
+    GiveOrder(Entity, Order),
     Trade(Entity),
     Buy(ItemTypeId, u16),
     Sell(Vec<Entity>),
@@ -64,6 +74,16 @@ impl From<RepeatingAction> for Action {
             RepeatingAction::GoToShip => Action::GoToShip,
             RepeatingAction::UseItem { item, use_time } => UseAction { item, use_time }.into(),
             RepeatingAction::ChargedAttack(target) => Action::ChargedAttack(target),
+            RepeatingAction::Treat {
+                item,
+                patient,
+                use_time,
+            } => TreatAction {
+                item,
+                patient,
+                use_time,
+            }
+            .into(),
         }
     }
 }
@@ -73,7 +93,13 @@ pub fn tick(
     state: &mut GameState,
     view_buffer: &mut view::Buffer,
 ) {
-    let mut entities = action_map
+    let mut actions = action_map.into_iter().collect::<Vec<_>>();
+    // Sort before shuffling so that the order of entities with equal agility
+    // only depends on the rng, and not on the iteration order of the map.
+    actions.sort_by_key(|&(entity, _)| entity);
+    actions.shuffle(&mut state.rng);
+
+    let mut entities = actions
         .into_iter()
         .map(|(entity, action)| {
             (
@@ -130,7 +156,10 @@ fn perform(
         TakeAll => item::take_all(&mut context, performer),
         GiveItem(item, receiver) => item::give_item(context, performer, item, receiver),
         Wield(item, name) => item::wield(&mut context, performer, item, name),
+        Wear(item, name) => item::wear(&mut context, performer, item, name),
+        TakeOff(item) => item::take_off(&mut context, performer, item),
         Use(use_action) => use_action.run(performer, context),
+        Treat(treat_action) => treat_action.run(performer, context),
         EnterDoor(door) => door::enter_door(&mut context, performer, door),
         ForceDoor(force_door_action) => force_door_action.run(context, performer),
         GoToShip => door::go_to_ship(context, performer),
@@ -160,12 +189,16 @@ fn perform(
         Rest(first) => rest(&mut context, performer, first),
         Refuel => ship::refuel(&mut context, performer),
         Launch => ship::launch(&mut context, performer),
+        StoreInShip(item) => ship::store_item(&mut context, performer, item),
+        RetrieveFromShip(item) => ship::retrieve_item(&mut context, performer, item),
+        InstallUpgrade(upgrade) => ship::install_upgrade(&mut context, performer, upgrade),
         TalkTo(talk_action) => talk_action.run(context, performer),
         Recruit(target) => dialogue::recruit(context, performer, target),
         AnswerYesNoDecision(action) => action.run(context, performer),
         TellToWait(target) => dialogue::tell_to_wait(context, performer, target),
         TellToWaitAtShip(target) => dialogue::tell_to_wait_at_ship(context, performer, target),
         TellToFollow(target) => dialogue::tell_to_follow(context, performer, target),
+        GiveOrder(target, order) => dialogue::give_order(context, performer, target, order),
         Trade(shopkeeper) => trade::trade(context, performer, shopkeeper),
         Buy(item_type, amount) => trade::buy(&mut context, performer, item_type, amount),
         Sell(items) => trade::sell(&mut context, performer, items),
@@ -218,6 +251,10 @@ impl ViewContext<'_, '_> {
         }
     }
 
+    fn add_event(&mut self, event: GameEvent) {
+        self.view_buffer.add_event(event);
+    }
+
     fn make_noise_at(&mut self, noise_source_areas: &[Entity], state: &mut GameState) {
         if noise_source_areas.contains(&self.player_area) {
             return;
@@ -268,9 +305,10 @@ fn rest(context: &mut Context, performer: Entity, first_turn_resting: bool) -> R
     }
 
     if first_turn_resting {
+        let assets = context.view_context.view_buffer.assets;
         context.view_context.add_message_at(
             area,
-            "The crew takes some time to rest up.",
+            assets.messages.text("rest.start", &[]),
             context.state,
         );
     }
@@ -285,19 +323,27 @@ fn open_chest(context: &mut Context, performer: Entity, chest: Entity) -> Result
     position::move_adjacent_placement(world, performer, chest_placement, assets)?;
 
     if world.get::<&FortunaChest>(chest).is_err() {
-        return Err(Error::visible(format!(
-            "{} tried to open {}, but that is not the fortuna chest!",
-            NameData::find(world, performer, assets).definite(),
-            NameData::find(world, chest, assets).definite(),
+        return Err(Error::visible(assets.messages.text(
+            "fortuna_chest.not_the_chest",
+            &[
+                (
+                    "performer",
+                    &NameData::find(world, performer, assets).definite(),
+                ),
+                ("target", &NameData::find(world, chest, assets).definite()),
+            ],
         )));
     }
 
     world.insert_one(performer, OpenedChest).unwrap();
     context.view_context.add_message_at(
         chest_placement.area(),
-        format!(
-            "{} opened the fortuna chest and found the item that they desired the most.",
-            NameData::find(world, performer, assets).definite(),
+        assets.messages.text(
+            "fortuna_chest.open",
+            &[(
+                "performer",
+                &NameData::find(world, performer, assets).definite(),
+            )],
         ),
         context.state,
     );
@@ -308,28 +354,33 @@ fn tame(context: &mut Context, performer: Entity, target: Entity) -> Result {
     let assets = context.view_context.view_buffer.assets;
     let world = &mut context.state.world;
     let crew = world.get::<&CrewMember>(performer).unwrap().0;
-    core::check_crew_size(world)?;
+    core::check_crew_size(world, assets)?;
 
     let performer_name = NameData::find(world, performer, assets).definite();
     let target_name = NameData::find(world, target, assets).definite();
     let target_placement = Placement::from(world.query_one_mut::<PlacementQuery>(target).unwrap());
 
     if !status::is_alive(target, world) {
-        return Err(Error::private(format!(
-            "{target_name} is not a tameable creature."
-        )));
+        return Err(Error::private(
+            assets
+                .messages
+                .text("tame.not_tameable", &[("target", &target_name)]),
+        ));
     }
 
     {
         let mut query = world.query_one::<&Hostile>(target).with::<&Recruitable>();
         let Ok(hostile) = query.get() else {
-            return Err(Error::private(format!(
-                "{target_name} is not a tameable creature."
-            )));
+            return Err(Error::private(
+                assets
+                    .messages
+                    .text("tame.not_tameable", &[("target", &target_name)]),
+            ));
         };
         if hostile.aggressive {
-            return Err(Error::private(format!(
-                "{target_name} is on the attack and does not let {performer_name} approach it."
+            return Err(Error::private(assets.messages.text(
+                "tame.aggressive",
+                &[("performer", &performer_name), ("target", &target_name)],
             )));
         }
     }
@@ -341,15 +392,20 @@ fn tame(context: &mut Context, performer: Entity, target: Entity) -> Result {
         .filter(|pos| pos.is_in(target_placement.area()))
         .count();
     if creature_count > 1 {
-        return Err(Error::private(format!(
-            "{performer_name} is unable to approach {target_name} as the latter is not alone."
+        return Err(Error::private(assets.messages.text(
+            "tame.not_alone",
+            &[("performer", &performer_name), ("target", &target_name)],
         )));
     }
 
     position::move_adjacent_placement(world, performer, target_placement, assets)?;
 
     inventory::consume_one(ItemTypeId::is_food_ration, world, performer).ok_or_else(|| {
-        Error::private(format!("{performer_name} needs a food ration for taming."))
+        Error::private(
+            assets
+                .messages
+                .text("tame.needs_food_ration", &[("performer", &performer_name)]),
+        )
     })?;
 
     world
@@ -358,7 +414,10 @@ fn tame(context: &mut Context, performer: Entity, target: Entity) -> Result {
 
     context.view_context.add_message_at(
         target_placement.area(),
-        format!("{performer_name} offered a food ration to {target_name} and tamed it."),
+        assets.messages.text(
+            "tame.success",
+            &[("performer", &performer_name), ("target", &target_name)],
+        ),
         context.state,
     );
 
@@ -375,13 +434,18 @@ fn give_name(context: &mut Context, performer: Entity, target: Entity, name: Str
     {
         let target = world.entity(target).unwrap();
         if !target.has::<CrewMember>() {
-            return Err(Error::private(format!(
-                "{performer_name} cannot name {target_name}."
+            return Err(Error::private(assets.messages.text(
+                "name.cannot_name",
+                &[("performer", &performer_name), ("target", &target_name)],
             )));
         }
 
         if target.has::<Name>() {
-            return Err(Error::private(format!("{target_name} already has a name.")));
+            return Err(Error::private(
+                assets
+                    .messages
+                    .text("name.already_named", &[("target", &target_name)]),
+            ));
         }
     }
 
@@ -391,7 +455,14 @@ fn give_name(context: &mut Context, performer: Entity, target: Entity, name: Str
 
     context.view_context.add_message_at(
         target_placement.area(),
-        format!("{performer_name} dubbed {target_name} to be named {name}."),
+        assets.messages.text(
+            "name.give",
+            &[
+                ("performer", &performer_name),
+                ("target", &target_name),
+                ("name", &name),
+            ],
+        ),
         context.state,
     );
     Ok(Success)
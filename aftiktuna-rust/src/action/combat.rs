@@ -1,16 +1,23 @@
 use crate::action::{self, Error};
 use crate::asset::GameAssets;
-use crate::core::behavior::{self, Hostile, RepeatingAction};
-use crate::core::combat::{self, AttackKind};
+use crate::core::area;
+use crate::core::behavior::{self, RepeatingAction};
+use crate::core::combat::{self, ArmorProperties, AttackKind, HitType};
+use crate::core::effect::{self, StatusEffectKind};
 use crate::core::item::ItemTypeId;
 use crate::core::name::{NameData, NameWithAttribute};
-use crate::core::position::{self, OccupiesSpace, Placement, PlacementQuery, Pos};
+use crate::core::position::{self, Placement, PlacementQuery, Pos};
 use crate::core::status::{self, Health, Morale, Stamina, Stats};
 use crate::core::{SpeciesId, inventory};
+use crate::view::combat_log::{AttackRecord, DamageBreakdown, HitRoll, StunRoll};
+use crate::view::event::GameEvent;
 use hecs::{Entity, EntityRef, World};
 use rand::Rng;
 use std::cmp::Ordering;
 
+/// Added to the hit difficulty of attacks made in a dark area without a light source.
+const DARKNESS_HIT_PENALTY: i16 = 3;
+
 pub(super) fn attack(
     context: &mut action::Context,
     attacker: Entity,
@@ -78,18 +85,34 @@ fn attack_single(
         .query_one_mut::<PlacementQuery>(target)
         .map(Placement::from)
         .map_err(|_| {
-            format!(
-                "{target_name} disappeared before {attacker_name} could attack.",
-                attacker_name = NameWithAttribute::lookup(attacker, world, assets).definite(),
-                target_name = NameWithAttribute::lookup(target, world, assets).definite()
+            assets.messages.text(
+                "attack.target_disappeared",
+                &[
+                    (
+                        "attacker",
+                        &NameWithAttribute::lookup(attacker, world, assets).definite(),
+                    ),
+                    (
+                        "target",
+                        &NameWithAttribute::lookup(target, world, assets).definite(),
+                    ),
+                ],
             )
         })?;
 
     if !attacker_pos.is_in(target_placement.area()) {
-        return Err(Error::private(format!(
-            "{target_name} left before {attacker_name} could attack.",
-            attacker_name = NameWithAttribute::lookup(attacker, world, assets).definite(),
-            target_name = NameWithAttribute::lookup(target, world, assets).definite(),
+        return Err(Error::private(assets.messages.text(
+            "attack.target_left",
+            &[
+                (
+                    "attacker",
+                    &NameWithAttribute::lookup(attacker, world, assets).definite(),
+                ),
+                (
+                    "target",
+                    &NameWithAttribute::lookup(target, world, assets).definite(),
+                ),
+            ],
         )));
     }
 
@@ -100,7 +123,9 @@ fn attack_single(
     let world = &mut context.state.world;
     behavior::trigger_aggression_in_area(world, attacker_pos.get_area());
 
-    position::move_adjacent_placement(world, attacker, target_placement, assets)?;
+    if combat::get_usable_ranged_properties(world, attacker, assets).is_none() {
+        position::move_adjacent_placement(world, attacker, target_placement, assets)?;
+    }
 
     if attack_kind == AttackKind::Charged {
         world
@@ -108,9 +133,12 @@ fn attack_single(
             .unwrap();
         context.view_context.add_message_at(
             attacker_pos.get_area(),
-            format!(
-                "{attacker_name} readies a powerful attack.",
-                attacker_name = NameWithAttribute::lookup(attacker, world, assets).definite()
+            assets.messages.text(
+                "attack.charge",
+                &[(
+                    "attacker",
+                    &NameWithAttribute::lookup(attacker, world, assets).definite(),
+                )],
             ),
             context.state,
         );
@@ -138,18 +166,34 @@ pub(super) fn charged_attack(
         .query_one_mut::<PlacementQuery>(target)
         .map(Placement::from)
         .map_err(|_| {
-            format!(
-                "{target_name} disappeared before {attacker_name} could attack.",
-                attacker_name = NameWithAttribute::lookup(attacker, world, assets).definite(),
-                target_name = NameWithAttribute::lookup(target, world, assets).definite(),
+            assets.messages.text(
+                "attack.target_disappeared",
+                &[
+                    (
+                        "attacker",
+                        &NameWithAttribute::lookup(attacker, world, assets).definite(),
+                    ),
+                    (
+                        "target",
+                        &NameWithAttribute::lookup(target, world, assets).definite(),
+                    ),
+                ],
             )
         })?;
 
     if !attacker_pos.is_in(target_placement.area()) {
-        return Err(Error::private(format!(
-            "{target_name} left before {attacker_name} could attack.",
-            attacker_name = NameWithAttribute::lookup(attacker, world, assets).definite(),
-            target_name = NameWithAttribute::lookup(target, world, assets).definite(),
+        return Err(Error::private(assets.messages.text(
+            "attack.target_left",
+            &[
+                (
+                    "attacker",
+                    &NameWithAttribute::lookup(attacker, world, assets).definite(),
+                ),
+                (
+                    "target",
+                    &NameWithAttribute::lookup(target, world, assets).definite(),
+                ),
+            ],
         )));
     }
 
@@ -160,7 +204,9 @@ pub(super) fn charged_attack(
     let world = &mut context.state.world;
     behavior::trigger_aggression_in_area(world, attacker_pos.get_area());
 
-    position::move_adjacent_placement(world, attacker, target_placement, assets)?;
+    if combat::get_usable_ranged_properties(world, attacker, assets).is_none() {
+        position::move_adjacent_placement(world, attacker, target_placement, assets)?;
+    }
 
     perform_attack(context, attacker, target, AttackKind::Charged)
 }
@@ -176,19 +222,25 @@ fn perform_attack(
     let attacker_area = world.get::<&Pos>(attacker).unwrap().get_area();
     let attacker_name = NameWithAttribute::lookup(attacker, world, assets).definite();
     let target_name = NameWithAttribute::lookup(target, world, assets).definite();
+    let ranged = combat::get_usable_ranged_properties(world, attacker, assets);
 
-    let attack_kind_text = match attack_kind {
-        AttackKind::Light => "",
-        AttackKind::Rash => "With uncontrolled force, ",
-        AttackKind::Charged => "With power, ",
-    };
     let (attack_text, hit_verb) = if let Some(weapon) = inventory::get_wielded(world, attacker) {
         let weapon_name = NameData::find(world, weapon, assets).base();
+        let message_id = if ranged.is_some() {
+            "attack.weapon.ranged"
+        } else {
+            "attack.weapon.melee"
+        };
         (
-            format!(
-                "{attack_kind_text}{attacker_name} swings their {weapon_name} at {target_name}"
+            assets.messages.text(
+                message_id,
+                &[
+                    ("attacker", &attacker_name),
+                    ("weapon", &weapon_name),
+                    ("target", &target_name),
+                ],
             ),
-            "hits",
+            assets.messages.text("attack.hit_verb", &[]),
         )
     } else if let Some(unarmed_type) = world
         .get::<&SpeciesId>(attacker)
@@ -196,33 +248,80 @@ fn perform_attack(
         .and_then(|species| assets.species_data_map.get(&species))
         .map(|species_data| species_data.unarmed)
     {
-        let attack_verb = unarmed_type.attack_verb();
         (
-            format!("{attack_kind_text}{attacker_name} {attack_verb} {target_name}"),
-            unarmed_type.hit_verb(),
+            assets.messages.text(
+                "attack.unarmed",
+                &[
+                    ("attacker", &attacker_name),
+                    ("verb", &unarmed_type.attack_verb(assets)),
+                    ("target", &target_name),
+                ],
+            ),
+            unarmed_type.hit_verb(assets),
         )
     } else {
         (
-            format!("{attack_kind_text}{attacker_name} attacks {target_name}"),
-            "hits",
+            assets.messages.text(
+                "attack.generic",
+                &[("attacker", &attacker_name), ("target", &target_name)],
+            ),
+            assets.messages.text("attack.hit_verb", &[]),
         )
     };
+    let attack_text = match attack_kind {
+        AttackKind::Light => attack_text,
+        AttackKind::Rash => assets
+            .messages
+            .text("attack.kind.rash", &[("attack", &attack_text)]),
+        AttackKind::Charged => assets
+            .messages
+            .text("attack.kind.charged", &[("attack", &attack_text)]),
+    };
 
-    let hit_type = roll_hit(world, attacker, target, attack_kind, &mut context.state.rng);
+    let mut hit_penalty = 0;
+    if area::is_dark(attacker_area, world, assets) {
+        hit_penalty += DARKNESS_HIT_PENALTY;
+    }
+    if let Some(ranged) = &ranged {
+        let attacker_pos = *world.get::<&Pos>(attacker).unwrap();
+        let distance = world
+            .query_one::<PlacementQuery>(target)
+            .get()
+            .map(|query| Placement::from(query).distance_to(attacker_pos))
+            .unwrap_or_default();
+        hit_penalty += ranged.distance_penalty(distance);
+        inventory::consume_one(|item_type| *item_type == ranged.ammo, world, attacker);
+    }
+    let (hit_type, hit_roll) = roll_hit(
+        world,
+        attacker,
+        target,
+        attack_kind,
+        hit_penalty,
+        &mut context.state.rng,
+        assets,
+    );
 
     if attack_kind == AttackKind::Rash {
         world.insert_one(attacker, status::IsStunned).unwrap();
     }
 
-    match hit_type {
-        HitType::Dodge => context.view_context.add_message_at(
-            attacker_area,
-            format!("{attack_text}, but {target_name} dodges the attack."),
-            context.state,
-        ),
+    let outcome = match hit_type {
+        HitType::Dodge => {
+            context.view_context.add_message_at(
+                attacker_area,
+                assets.messages.text(
+                    "attack.dodge",
+                    &[("attack", &attack_text), ("target", &target_name)],
+                ),
+                context.state,
+            );
+            None
+        }
         HitType::GrazingHit => {
-            let effect = perform_attack_hit(
+            let outcome = perform_attack_hit(
                 false,
+                ranged.is_some(),
                 attacker,
                 target,
                 attack_kind,
@@ -230,15 +329,23 @@ fn perform_attack(
                 &mut context.state.rng,
                 context.view_context.view_buffer.assets,
             );
-            let effect_text = effect
-                .map(AttackEffect::verb)
-                .map_or("".to_string(), |effect| format!(", {effect} {target_name}"));
+            let effect_text = outcome
+                .effect
+                .map_or(String::new(), |effect| effect.message(&target_name, assets));
 
             context.view_context.add_message_at(
                 attacker_area,
-                format!("{attack_text} and narrowly {hit_verb} them{effect_text}."),
+                assets.messages.text(
+                    "attack.grazing_hit",
+                    &[
+                        ("attack", &attack_text),
+                        ("hit_verb", &hit_verb),
+                        ("effect", &effect_text),
+                    ],
+                ),
                 context.state,
             );
+            Some(outcome)
         }
         HitType::DirectHit => {
             let cursed_nail = inventory::get_wielded(world, attacker).filter(|item| {
@@ -253,8 +360,9 @@ fn perform_attack(
                 target_health.apply_cursed_nail_effect();
             }
 
-            let effect = perform_attack_hit(
+            let outcome = perform_attack_hit(
                 true,
+                ranged.is_some(),
                 attacker,
                 target,
                 attack_kind,
@@ -262,13 +370,20 @@ fn perform_attack(
                 &mut context.state.rng,
                 context.view_context.view_buffer.assets,
             );
-            let effect_text = effect
-                .map(AttackEffect::verb)
-                .map_or("".to_string(), |effect| format!(", {effect} {target_name}"));
+            let effect_text = outcome
+                .effect
+                .map_or(String::new(), |effect| effect.message(&target_name, assets));
 
             context.view_context.add_message_at(
                 attacker_area,
-                format!("{attack_text} and directly {hit_verb} them{effect_text}."),
+                assets.messages.text(
+                    "attack.direct_hit",
+                    &[
+                        ("attack", &attack_text),
+                        ("hit_verb", &hit_verb),
+                        ("effect", &effect_text),
+                    ],
+                ),
                 context.state,
             );
 
@@ -276,11 +391,86 @@ fn perform_attack(
                 context.state.world.despawn(cursed_nail).unwrap();
                 context.view_context.add_message_at(
                     attacker_area,
-                    "The used nail breaks in two.",
+                    assets.messages.text("attack.cursed_nail_breaks", &[]),
                     context.state,
                 );
             }
+            Some(outcome)
         }
+    };
+
+    let world = &context.state.world;
+    if let Some(ranged) = &ranged
+        && !inventory::is_holding(|item_type| *item_type == ranged.ammo, world, attacker)
+    {
+        context.view_context.add_message_at(
+            attacker_area,
+            assets.messages.text(
+                "attack.out_of_ammo",
+                &[
+                    ("attacker", &attacker_name),
+                    (
+                        "ammo",
+                        assets.noun_data_map.lookup(&ranged.ammo.noun_id()).plural(),
+                    ),
+                ],
+            ),
+            context.state,
+        );
+    }
+
+    if let Some(combat_log) = &mut context.state.combat_log {
+        combat_log.add(AttackRecord {
+            attacker: attacker_name.clone(),
+            target: target_name.clone(),
+            kind: attack_kind,
+            hit_roll,
+            hit: hit_type,
+            damage: outcome.as_ref().map(|outcome| outcome.damage.clone()),
+            stun_roll: outcome
+                .as_ref()
+                .and_then(|outcome| outcome.stun_roll.clone()),
+            killed: outcome
+                .as_ref()
+                .is_some_and(|outcome| matches!(outcome.effect, Some(AttackEffect::Killed))),
+        });
+    }
+
+    let (damage, effect, applied_effects) = outcome.map_or((0., None, Vec::new()), |outcome| {
+        (
+            outcome.damage.dealt,
+            outcome.effect,
+            outcome.applied_effects,
+        )
+    });
+    context.view_context.add_event(GameEvent::Attack {
+        attacker,
+        target,
+        kind: attack_kind,
+        hit: hit_type,
+        damage,
+    });
+    for kind in applied_effects {
+        context
+            .view_context
+            .add_event(GameEvent::StatusEffectApplied {
+                entity: target,
+                effect: kind,
+            });
+        context.view_context.add_message_at(
+            attacker_area,
+            effect::applied_message(kind, target, &context.state.world, assets),
+            context.state,
+        );
+    }
+    match effect {
+        Some(AttackEffect::Stunned) => context
+            .view_context
+            .add_event(GameEvent::Stunned { entity: target }),
+        Some(AttackEffect::Killed) => context
+            .view_context
+            .add_event(GameEvent::Died { entity: target }),
+        None => {}
     }
 
     context
@@ -297,51 +487,81 @@ enum AttackEffect {
 }
 
 impl AttackEffect {
-    fn verb(self) -> &'static str {
-        match self {
-            Self::Stunned => "stunning",
-            Self::Killed => "killing",
-        }
+    fn message(self, target_name: &str, assets: &GameAssets) -> String {
+        let message_id = match self {
+            Self::Stunned => "attack.effect.stunned",
+            Self::Killed => "attack.effect.killed",
+        };
+        assets.messages.text(message_id, &[("target", target_name)])
     }
 }
 
+struct HitOutcome {
+    damage: DamageBreakdown,
+    effect: Option<AttackEffect>,
+    applied_effects: Vec<StatusEffectKind>,
+    stun_roll: Option<StunRoll>,
+}
+
 fn perform_attack_hit(
     is_direct_hit: bool,
+    is_ranged: bool,
     attacker: Entity,
     target: Entity,
     attack_kind: AttackKind,
     world: &mut World,
     rng: &mut impl Rng,
     assets: &GameAssets,
-) -> Option<AttackEffect> {
-    let damage_factor = if is_direct_hit { 1.0 } else { 0.5 } * attack_kind.damage_modifier();
+) -> HitOutcome {
+    let target_armor = combat::get_worn_armor_properties(world, target, assets);
+    let mut damage = get_attack_damage(world, attacker, is_ranged, assets);
+    damage.hit_factor = if is_direct_hit { 1.0 } else { 0.5 } * attack_kind.damage_modifier();
+    damage.dealt = target_armor.reduce_damage(damage.before_armor());
 
-    let damage_result = deal_damage(
-        world.entity(target).unwrap(),
-        damage_factor * get_attack_damage(world, attacker, assets),
-    );
+    let damage_result = deal_damage(world.entity(target).unwrap(), damage.dealt);
 
     if matches!(damage_result, Some(Killed)) {
-        let _ = world.remove_one::<OccupiesSpace>(target);
-        let _ = world.remove_one::<Hostile>(target);
-        return Some(AttackEffect::Killed);
+        status::clear_dead_creature(world, target);
+        return HitOutcome {
+            damage,
+            effect: Some(AttackEffect::Killed),
+            applied_effects: Vec::new(),
+            stun_roll: None,
+        };
     }
+    let weapon_properties = combat::get_active_weapon_properties(world, attacker, assets);
+    let applied_effects =
+        effect::apply_hit_effects(world, target, &weapon_properties.effects, rng, assets);
     if is_direct_hit
         && !world.satisfies::<&status::IsStunned>(target)
-        && combat::get_active_weapon_properties(world, attacker, assets).stun_attack
+        && weapon_properties.stun_attack
     {
-        let successful_stun = roll_stun(
+        let stun_roll = roll_stun(
             world.entity(attacker).unwrap(),
             world.entity(target).unwrap(),
             attack_kind,
+            &target_armor,
             rng,
         );
-        if successful_stun {
+        let effect = if stun_roll.is_success() {
             world.insert_one(target, status::IsStunned).unwrap();
-            return Some(AttackEffect::Stunned);
-        }
+            Some(AttackEffect::Stunned)
+        } else {
+            None
+        };
+        return HitOutcome {
+            damage,
+            effect,
+            applied_effects,
+            stun_roll: Some(stun_roll),
+        };
+    }
+    HitOutcome {
+        damage,
+        effect: None,
+        applied_effects,
+        stun_roll: None,
     }
-    None
 }
 
 pub struct Killed;
@@ -354,7 +574,12 @@ fn deal_damage(target_ref: EntityRef, damage: f32) -> Option<Killed> {
     if health.is_dead() { Some(Killed) } else { None }
 }
 
-fn get_attack_damage(world: &World, attacker: Entity, assets: &GameAssets) -> f32 {
+fn get_attack_damage(
+    world: &World,
+    attacker: Entity,
+    is_ranged: bool,
+    assets: &GameAssets,
+) -> DamageBreakdown {
     let strength = world
         .get::<&Stats>(attacker)
         .expect("Expected attacker to have stats attached")
@@ -364,10 +589,19 @@ fn get_attack_damage(world: &World, attacker: Entity, assets: &GameAssets) -> f3
         .as_deref()
         .copied()
         .unwrap_or_default();
-    let strength_mod = f32::from(strength + 2) / 6.0;
-    combat::get_active_weapon_properties(world, attacker, assets).damage_mod
-        * strength_mod
-        * morale.damage_factor()
+    // The force of a shot from a ranged weapon does not depend on the strength of the attacker.
+    let strength_factor = if is_ranged {
+        1.0
+    } else {
+        f32::from(strength + 2) / 6.0
+    };
+    DamageBreakdown {
+        weapon_damage: combat::get_active_weapon_properties(world, attacker, assets).damage_mod,
+        strength_factor,
+        morale_factor: morale.damage_factor(),
+        hit_factor: 1.0,
+        dealt: 0.,
+    }
 }
 
 fn roll_hit(
@@ -375,49 +609,59 @@ fn roll_hit(
     attacker: Entity,
     target: Entity,
     attack_kind: AttackKind,
+    hit_penalty: i16,
     rng: &mut impl Rng,
-) -> HitType {
+    assets: &GameAssets,
+) -> (HitType, HitRoll) {
+    let target_armor = combat::get_worn_armor_properties(world, target, assets);
     let attacker_stats = world.get::<&Stats>(attacker).unwrap();
     let target_ref = world.entity(target).unwrap();
     let target_stats = target_ref.get::<&Stats>().unwrap();
     let mut stamina = target_ref.get::<&mut Stamina>().unwrap();
     let stamina_factor = stamina.as_fraction();
 
-    let mut hit_difficulty = f32::from(target_stats.luck);
+    let mut target_dodge = 0.;
     if stamina_factor > 0.0 {
         stamina.on_dodge_attempt();
-        hit_difficulty +=
-            2. * stamina_factor * f32::from(target_stats.agility_for_dodging(target_ref));
+        target_dodge = 2.
+            * stamina_factor
+            * f32::from(target_stats.agility_for_dodging(target_ref, &target_armor));
     }
-    hit_difficulty -= f32::from(attacker_stats.agility) + 0.5 * f32::from(attacker_stats.luck);
-    let hit_difficulty = hit_difficulty.ceil() as i16 - attack_kind.hit_modifier();
+    let hit_difficulty = f32::from(target_stats.luck) + target_dodge
+        - (f32::from(attacker_stats.agility) + 0.5 * f32::from(attacker_stats.luck));
+    let hit_difficulty = hit_difficulty.ceil() as i16 - attack_kind.hit_modifier() + hit_penalty;
 
     // Yes, this looks slightly odd. This is meant to act as a d20 integer roll,
     // which is converted to a float only to be compared against the float factor.
     let hit_roll = rng.random_range::<i16, _>(1..=20);
 
-    if hit_roll < hit_difficulty - 5 {
+    let hit_type = if hit_roll < hit_difficulty - 5 {
         HitType::Dodge
     } else if hit_roll < hit_difficulty {
         HitType::GrazingHit
     } else {
         HitType::DirectHit
-    }
-}
-
-#[derive(Debug, Eq, PartialEq)]
-enum HitType {
-    DirectHit,
-    GrazingHit,
-    Dodge,
+    };
+    let roll = HitRoll {
+        roll: hit_roll,
+        difficulty: hit_difficulty,
+        target_luck: target_stats.luck,
+        target_dodge,
+        attacker_agility: attacker_stats.agility,
+        attacker_luck: attacker_stats.luck,
+        attack_kind_modifier: attack_kind.hit_modifier(),
+        penalty: hit_penalty,
+    };
+    (hit_type, roll)
 }
 
 fn roll_stun(
     attacker: EntityRef,
     target: EntityRef,
     attack_kind: AttackKind,
+    target_armor: &ArmorProperties,
     rng: &mut impl Rng,
-) -> bool {
+) -> StunRoll {
     let attacker_strength = attacker
         .get::<&Stats>()
         .expect("Expected attacker to have stats attached")
@@ -427,8 +671,11 @@ fn roll_stun(
         .expect("Expected target to have stats attached")
         .endurance;
 
-    let stun_difficulty =
-        15 + 2 * (target_endurance - attacker_strength) - attack_kind.stun_modifier();
-    let stun_roll = rng.random_range::<i16, _>(1..=20);
-    stun_roll >= stun_difficulty
+    let stun_difficulty = 15 + 2 * (target_endurance - attacker_strength)
+        - attack_kind.stun_modifier()
+        + target_armor.stun_resistance;
+    StunRoll {
+        roll: rng.random_range::<i16, _>(1..=20),
+        difficulty: stun_difficulty,
+    }
 }
@@ -1,6 +1,6 @@
 use crate::action::{self, Context, Error};
 use crate::core::behavior::{
-    Decision, Hostile, Passenger, PassengerPhase, Recruitable, Reward, Waiting,
+    Decision, Hostile, Order, Passenger, PassengerPhase, Recruitable, Reward, Waiting,
 };
 use crate::core::name::NameData;
 use crate::core::position::{Placement, PlacementQuery, Pos};
@@ -9,6 +9,7 @@ use crate::core::{self, CrewMember, position, status};
 use crate::dialogue::{self, TalkTopic};
 use crate::game_loop::GameState;
 use crate::view;
+use crate::view::event::GameEvent;
 use hecs::Entity;
 
 #[derive(Clone, Debug)]
@@ -30,14 +31,13 @@ impl TalkAction {
             return Ok(action::Success);
         }
         if context.state.world.satisfies::<&Hostile>(target) {
-            return Err(Error::private(format!(
-                "{the_target} is not interested in talking.",
-                the_target = NameData::find(
-                    &context.state.world,
-                    target,
-                    context.view_context.view_buffer.assets,
-                )
-                .definite(),
+            let assets = context.view_context.view_buffer.assets;
+            return Err(Error::private(assets.messages.text(
+                "talk.not_interested",
+                &[(
+                    "target",
+                    &NameData::find(&context.state.world, target, assets).definite(),
+                )],
             )));
         }
 
@@ -74,13 +74,23 @@ fn perform_recruitment_success(
     let _ = state.world.remove_one::<Passenger>(target);
     let _ = state.world.remove_one::<Reward>(target);
     state.world.insert_one(target, CrewMember(crew)).unwrap();
-
-    let name = NameData::find(&state.world, target, view_buffer.assets).definite();
-    view_buffer.add_change_message(format!("{name} joined the crew!"), state);
+    view_buffer.add_event(GameEvent::JoinedCrew { character: target });
+
+    let assets = view_buffer.assets;
+    let name = NameData::find(&state.world, target, assets).definite();
+    view_buffer.add_change_message(
+        assets
+            .messages
+            .text("recruit.joined_crew", &[("character", &name)]),
+        state,
+    );
 }
 
 pub(super) fn recruit(context: Context, performer: Entity, target: Entity) -> action::Result {
-    core::check_crew_size(&context.state.world)?;
+    core::check_crew_size(
+        &context.state.world,
+        context.view_context.view_buffer.assets,
+    )?;
 
     full_dialogue_action(
         context,
@@ -140,14 +150,13 @@ impl AnswerYesNoDecisionAction {
     pub(super) fn run(self, context: Context, performer: Entity) -> action::Result {
         let Self { decision_outcome } = self;
         let Ok(decision) = context.state.world.remove_one::<Decision>(performer) else {
-            return Err(Error::private(format!(
-                "{} lost track of who they were talking to.",
-                NameData::find(
-                    &context.state.world,
-                    performer,
-                    context.view_context.view_buffer.assets
-                )
-                .definite()
+            let assets = context.view_context.view_buffer.assets;
+            return Err(Error::private(assets.messages.text(
+                "talk.lost_track",
+                &[(
+                    "performer",
+                    &NameData::find(&context.state.world, performer, assets).definite(),
+                )],
             )));
         };
 
@@ -169,7 +178,10 @@ fn handle_recruit_decision(
     context: Context,
 ) -> action::Result {
     if decision_outcome {
-        core::check_crew_size(&context.state.world)?;
+        core::check_crew_size(
+            &context.state.world,
+            context.view_context.view_buffer.assets,
+        )?;
 
         full_dialogue_action(
             context,
@@ -192,11 +204,13 @@ fn handle_recruit_decision(
                     perform_recruitment_success(target, state, view_context.view_buffer);
                     Some(Ok(action::Success))
                 } else {
-                    Some(Err(Error::private(format!(
-                        "{the_target} is no longer receptive to being recruited.",
-                        the_target =
-                            NameData::find(&state.world, target, view_context.view_buffer.assets)
-                                .definite(),
+                    let assets = view_context.view_buffer.assets;
+                    Some(Err(Error::private(assets.messages.text(
+                        "recruit.no_longer_receptive",
+                        &[(
+                            "target",
+                            &NameData::find(&state.world, target, assets).definite(),
+                        )],
                     ))))
                 }
             },
@@ -304,6 +318,7 @@ pub(super) fn tell_to_wait(context: Context, performer: Entity, target: Entity)
                 view_context.view_buffer,
             );
 
+            let _ = state.world.remove_one::<Order>(target);
             state
                 .world
                 .insert_one(target, Waiting { at_ship: false })
@@ -340,6 +355,7 @@ pub(super) fn tell_to_wait_at_ship(
                 view_context.view_buffer,
             );
 
+            let _ = state.world.remove_one::<Order>(target);
             state
                 .world
                 .insert_one(target, Waiting { at_ship: true })
@@ -356,7 +372,10 @@ pub(super) fn tell_to_follow(
     target: Entity,
 ) -> action::Result {
     if !status::is_alive(target, &context.state.world)
-        || !context.state.world.satisfies::<&Waiting>(target)
+        || !context
+            .state
+            .world
+            .satisfies::<hecs::Or<&Waiting, &Order>>(target)
     {
         return Ok(action::Success);
     }
@@ -378,7 +397,49 @@ pub(super) fn tell_to_follow(
                 view_context.view_buffer,
             );
 
-            state.world.remove_one::<Waiting>(target).unwrap();
+            let _ = state.world.remove_one::<Waiting>(target);
+            let _ = state.world.remove_one::<Order>(target);
+
+            None
+        },
+    )
+}
+
+pub(super) fn give_order(
+    context: Context,
+    performer: Entity,
+    target: Entity,
+    order: Order,
+) -> action::Result {
+    if !status::is_alive(target, &context.state.world) {
+        return Ok(action::Success);
+    }
+
+    let dialogue_name = match order {
+        Order::Attack(_) => "core/tell_to/attack",
+        Order::Guard(_) => "core/tell_to/guard",
+        Order::RetreatToShip => "core/tell_to/retreat_to_ship",
+        Order::UseMedkitOn(_) => "core/tell_to/use_medkit",
+    };
+    full_dialogue_action(
+        context,
+        performer,
+        target,
+        false,
+        |Context {
+             state,
+             view_context,
+         }| {
+            dialogue::trigger_dialogue_by_name(
+                dialogue_name,
+                performer,
+                target,
+                state,
+                view_context.view_buffer,
+            );
+
+            let _ = state.world.remove_one::<Waiting>(target);
+            state.world.insert_one(target, order).unwrap();
 
             None
         },
@@ -405,8 +466,9 @@ pub(super) fn full_dialogue_action(
     if !performer_pos.is_in(target_placement.area()) {
         let performer_name = NameData::find(&context.state.world, performer, assets).definite();
         let target_name = NameData::find(&context.state.world, target, assets).definite();
-        return Err(Error::private(format!(
-            "{target_name} left before {performer_name} could talk to them.",
+        return Err(Error::private(assets.messages.text(
+            "talk.target_left",
+            &[("performer", &performer_name), ("target", &target_name)],
         )));
     }
 
@@ -442,7 +504,10 @@ pub(super) fn full_dialogue_action(
         let target_name = NameData::find(&context.state.world, target, assets).definite();
         context.view_context.add_message_at(
             performer_pos.get_area(),
-            format!("{performer_name} finishes talking with {target_name}."),
+            assets.messages.text(
+                "talk.finished",
+                &[("performer", &performer_name), ("target", &target_name)],
+            ),
             context.state,
         );
         Ok(action::Success)
@@ -1,11 +1,13 @@
 use crate::action::{self, Context, Error};
+use crate::asset::GameAssets;
 use crate::core::behavior::{self, Character, Intention, RepeatingAction};
 use crate::core::item::Tool;
 use crate::core::name::{NameData, NameIdData};
 use crate::core::position::{self, Direction, Placement, PlacementQuery, Pos};
 use crate::core::status::Stamina;
-use crate::core::{BlockType, CrewMember, Door, DoorKind, IsCut, area, inventory};
+use crate::core::{BlockType, CrewMember, Door, DoorKind, IsCut, LockKey, area, inventory};
 use crate::game_loop::GameState;
+use crate::view::event::GameEvent;
 use crate::view::text::CombinableMsgType;
 use crate::{ai, dialogue};
 use hecs::{Entity, World};
@@ -16,20 +18,73 @@ fn check_tool_for_forcing(
     world: &World,
     performer: Entity,
     performer_name: &str,
+    assets: &GameAssets,
 ) -> Result<Tool, String> {
     for tool in block_type.usable_tools() {
         if inventory::is_holding_tool(world, performer, tool) {
             return Ok(tool);
         }
     }
-    match block_type {
-        BlockType::Stuck => Err(format!(
-            "{performer_name} needs some sort of tool to force the door open.",
-        )),
-        BlockType::Sealed => Err(format!(
-            "{performer_name} needs some sort of tool to break the door open.",
-        )),
+    let message_id = match block_type {
+        BlockType::Stuck => "door.force.stuck_needs_tool",
+        BlockType::Sealed => "door.force.sealed_needs_tool",
+        BlockType::Locked => "door.force.locked_needs_key",
+    };
+    Err(assets
+        .messages
+        .text(message_id, &[("performer", performer_name)]))
+}
+
+/// Finds the performer or a crew member next to them that is carrying the key to the door pair.
+fn find_key_holder(
+    world: &World,
+    performer: Entity,
+    door_pair: Entity,
+    assets: &GameAssets,
+) -> Option<Entity> {
+    let key = world.get::<&LockKey>(door_pair).ok()?;
+    if inventory::is_holding_key(world, performer, &key, assets) {
+        return Some(performer);
+    }
+    if !world.satisfies::<&CrewMember>(performer) {
+        return None;
     }
+    let area = world.get::<&Pos>(performer).ok()?.get_area();
+    world
+        .query::<(Entity, &Pos)>()
+        .with::<&CrewMember>()
+        .iter()
+        .find(|&(crew_member, pos)| {
+            pos.is_in(area) && inventory::is_holding_key(world, crew_member, &key, assets)
+        })
+        .map(|(crew_member, _)| crew_member)
+}
+
+fn unlock_door(
+    state: &mut GameState,
+    view_context: &mut super::ViewContext,
+    key_holder: Entity,
+    door: Entity,
+    door_pair: Entity,
+    door_area: Entity,
+) {
+    let assets = view_context.view_buffer.assets;
+    state
+        .world
+        .remove::<(BlockType, LockKey)>(door_pair)
+        .unwrap();
+    view_context.add_event(GameEvent::DoorUnlocked {
+        character: key_holder,
+        door,
+    });
+    let message = assets.messages.text(
+        "door.unlock",
+        &[(
+            "performer",
+            &NameData::find(&state.world, key_holder, assets).definite(),
+        )],
+    );
+    view_context.add_message_at(door_area, message, state);
 }
 
 pub(super) fn enter_door(context: &mut Context, performer: Entity, door: Entity) -> action::Result {
@@ -42,15 +97,18 @@ pub(super) fn enter_door(context: &mut Context, performer: Entity, door: Entity)
     let performer_name = NameIdData::find(world, performer);
 
     let door_pos = *world.get::<&Pos>(door).ok().ok_or_else(|| {
-        format!(
-            "{the_performer} lost track of the door.",
-            the_performer = performer_name.clone().lookup(assets).definite()
+        assets.messages.text(
+            "door.lost_track",
+            &[(
+                "performer",
+                &performer_name.clone().lookup(assets).definite(),
+            )],
         )
     })?;
     if Ok(door_pos.get_area()) != world.get::<&Pos>(performer).map(|pos| pos.get_area()) {
-        return Err(Error::private(format!(
-            "{the_performer} cannot reach the door from here.",
-            the_performer = performer_name.lookup(assets).definite()
+        return Err(Error::private(assets.messages.text(
+            "door.cannot_reach",
+            &[("performer", &performer_name.lookup(assets).definite())],
         )));
     }
 
@@ -58,23 +116,34 @@ pub(super) fn enter_door(context: &mut Context, performer: Entity, door: Entity)
 
     let door_data = world
         .get::<&Door>(door)
-        .map_err(|_| "The door ceased being a door.".to_string())
+        .map_err(|_| assets.messages.text("door.not_a_door", &[]))
         .map(|door| door.deref().clone())?;
 
     if let Ok(block_type) = world
         .get::<&BlockType>(door_data.door_pair)
         .map(|block_type| *block_type)
     {
-        context
-            .view_context
-            .make_noise_at(&[door_pos.get_area()], state);
+        if let Some(key_holder) = find_key_holder(world, performer, door_data.door_pair, assets) {
+            unlock_door(
+                state,
+                view_context,
+                key_holder,
+                door,
+                door_data.door_pair,
+                door_pos.get_area(),
+            );
+        } else {
+            view_context.make_noise_at(&[door_pos.get_area()], state);
 
-        on_door_failure(state, performer, door, block_type);
-        return Err(Error::visible(format!(
-            "{performer} is unable to enter the door as it is {blocked}.",
-            performer = performer_name.lookup(assets).definite(),
-            blocked = block_type.description(),
-        )));
+            on_door_failure(state, performer, door, block_type);
+            return Err(Error::visible(assets.messages.text(
+                "door.enter.blocked",
+                &[
+                    ("performer", &performer_name.lookup(assets).definite()),
+                    ("blocked", block_type.description()),
+                ],
+            )));
+        }
     }
 
     view_context.capture_unseen_view(door_pos.get_area(), state);
@@ -105,6 +174,11 @@ pub(super) fn enter_door(context: &mut Context, performer: Entity, door: Entity)
     if performer == state.controlled {
         view_context.view_buffer.mark_unseen_view();
     }
+    view_context.add_event(GameEvent::DoorEntered {
+        character: performer,
+        door,
+        destination_area: destination_pos.get_area(),
+    });
 
     let door_name = NameIdData::find(world, door);
     let message = match door_data.kind {
@@ -150,19 +224,22 @@ impl ForceDoorAction {
         let assets = view_context.view_buffer.assets;
         let world = &state.world;
         let performer_name = NameData::find(world, performer, assets).definite();
-        let door_pos = *world
-            .get::<&Pos>(door)
-            .ok()
-            .ok_or_else(|| format!("{performer_name} lost track of the door."))?;
+        let door_pos = *world.get::<&Pos>(door).ok().ok_or_else(|| {
+            assets
+                .messages
+                .text("door.lost_track", &[("performer", &performer_name)])
+        })?;
         if Ok(door_pos.get_area()) != world.get::<&Pos>(performer).map(|pos| pos.get_area()) {
-            return Err(Error::private(format!(
-                "{performer_name} cannot reach the door from here."
-            )));
+            return Err(Error::private(
+                assets
+                    .messages
+                    .text("door.cannot_reach", &[("performer", &performer_name)]),
+            ));
         }
 
         let door_pair = world
             .get::<&Door>(door)
-            .map_err(|_| "The door ceased being a door.")?
+            .map_err(|_| assets.messages.text("door.not_a_door", &[]))?
             .door_pair;
 
         let movement = position::prepare_move(world, performer, door_pos)
@@ -181,12 +258,26 @@ impl ForceDoorAction {
 
         let world = &mut state.world;
         let block_type = *world.get::<&BlockType>(door_pair).map_err(|_| {
-            Error::visible(format!(
-                "{performer_name} inspects the door, but it does not appear to be stuck."
-            ))
+            Error::visible(
+                assets
+                    .messages
+                    .text("door.force.not_stuck", &[("performer", &performer_name)]),
+            )
         })?;
 
-        match check_tool_for_forcing(block_type, world, performer, &performer_name) {
+        if let Some(key_holder) = find_key_holder(world, performer, door_pair, assets) {
+            unlock_door(
+                state,
+                &mut view_context,
+                key_holder,
+                door,
+                door_pair,
+                door_pos.get_area(),
+            );
+            return Ok(action::Success);
+        }
+
+        match check_tool_for_forcing(block_type, world, performer, &performer_name, assets) {
             Err(message) => {
                 on_door_failure(state, performer, door, block_type);
                 Err(Error::visible(message))
@@ -205,9 +296,13 @@ impl ForceDoorAction {
                     }
                 }
 
+                view_context.add_event(GameEvent::DoorForced {
+                    character: performer,
+                    door,
+                });
                 view_context.add_message_at(
                     door_pos.get_area(),
-                    tool.into_message(&performer_name),
+                    tool.into_message(&performer_name, assets),
                     state,
                 );
                 Ok(action::Success)
@@ -250,6 +345,7 @@ fn on_door_failure(state: &mut GameState, performer: Entity, door: Entity, block
 }
 
 pub(super) fn go_to_ship(mut context: Context, performer: Entity) -> action::Result {
+    let assets = context.view_context.view_buffer.assets;
     let world = context.mut_world();
     let area = world.get::<&Pos>(performer).unwrap().get_area();
     if area::is_ship(area, world) {
@@ -257,7 +353,7 @@ pub(super) fn go_to_ship(mut context: Context, performer: Entity) -> action::Res
     }
 
     let path = ai::pathing::find_path_towards(world, area, |area| area::is_ship(area, world))
-        .ok_or_else(|| "Could not find a path to the ship.".to_string())?;
+        .ok_or_else(|| assets.messages.text("door.no_path_to_ship", &[]))?;
 
     let result = enter_door(&mut context, performer, path);
 
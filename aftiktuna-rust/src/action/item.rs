@@ -1,16 +1,20 @@
 use crate::action::{self, Context, Error};
 use crate::asset::ItemUseType;
+use crate::core::CrewMember;
 use crate::core::behavior::{self, RepeatingAction};
+use crate::core::effect;
 use crate::core::inventory::{self, Held};
 use crate::core::item::ItemTypeId;
 use crate::core::name::{self, ArticleKind, CountFormat, NameData, NameIdData, NameQuery};
 use crate::core::position::{self, Placement, PlacementQuery, Pos};
 use crate::core::status::{self, Health, StatChanges, Stats};
-use crate::dialogue;
+use crate::view::event::GameEvent;
 use crate::view::text::{self, CombinableMsgType};
+use crate::{deref_clone, dialogue};
 use hecs::Entity;
 
 pub(super) fn take_all(context: &mut Context, aftik: Entity) -> action::Result {
+    let assets = context.view_context.view_buffer.assets;
     let world = &mut context.state.world;
     let aftik_pos = *world.get::<&Pos>(aftik).unwrap();
     let (item, name) = world
@@ -19,13 +23,8 @@ pub(super) fn take_all(context: &mut Context, aftik: Entity) -> action::Result {
         .iter()
         .filter(|(_, pos, _)| pos.is_in(aftik_pos.get_area()))
         .min_by_key(|(_, pos, _)| pos.distance_to(aftik_pos))
-        .map(|(item, _, query)| {
-            (
-                item,
-                NameData::from_query(query, context.view_context.view_buffer.assets),
-            )
-        })
-        .ok_or("There are no items to take here.")?;
+        .map(|(item, _, query)| (item, NameData::from_query(query, assets)))
+        .ok_or_else(|| assets.messages.text("item.take.nothing_here", &[]))?;
 
     let result = take_item(context, aftik, item, name)?;
 
@@ -56,10 +55,15 @@ pub(super) fn take_item(
     let assets = context.view_context.view_buffer.assets;
     let performer_name = NameIdData::find(&context.state.world, performer);
     let item_pos = *context.state.world.get::<&Pos>(item).map_err(|_| {
-        format!(
-            "{the_performer} lost track of {the_item}.",
-            the_performer = performer_name.clone().lookup(assets).definite(),
-            the_item = item_name.definite()
+        assets.messages.text(
+            "item.lost_track",
+            &[
+                (
+                    "performer",
+                    &performer_name.clone().lookup(assets).definite(),
+                ),
+                ("item", &item_name.definite()),
+            ],
         )
     })?;
     let item_name = NameIdData::find(&context.state.world, item);
@@ -80,11 +84,19 @@ pub(super) fn take_item(
     {
         world.despawn(item).unwrap();
 
-        context.view_context.add_message_at(item_pos.get_area(), format!(
-            "{the_performer} tries to pick up {the_item}. But as they do, it disappears in their hand. (Luck has increased by 2 points)",
-            the_performer = performer_name.clone().lookup(assets).definite(),
-            the_item = item_name.lookup(assets).definite(),
-        ), context.state);
+        let message = assets.messages.text(
+            "item.four_leaf_clover.take",
+            &[
+                (
+                    "performer",
+                    &performer_name.clone().lookup(assets).definite(),
+                ),
+                ("item", &item_name.lookup(assets).definite()),
+            ],
+        );
+        context
+            .view_context
+            .add_message_at(item_pos.get_area(), message, context.state);
         return Ok(action::Success);
     }
 
@@ -92,8 +104,17 @@ pub(super) fn take_item(
         .exchange_one::<Pos, _>(item, Held::in_inventory(performer))
         .expect("Tried moving item to inventory");
 
-    behavior::trigger_aggression_in_area(world, item_pos.get_area());
+    if world.satisfies::<&CrewMember>(performer) {
+        behavior::trigger_aggression_in_area(world, item_pos.get_area());
+    }
 
+    if let Ok(item_type) = world.get::<&ItemTypeId>(item).map(deref_clone) {
+        context.view_context.add_event(GameEvent::ItemTaken {
+            character: performer,
+            item,
+            item_type,
+        });
+    }
     context.view_context.add_message_at(
         item_pos.get_area(),
         CombinableMsgType::PickUp(performer_name).message(item_name),
@@ -120,23 +141,35 @@ impl SearchAction {
         let world = context.mut_world();
         let performer_name = NameData::find(world, performer, assets).definite();
         let container_name = NameData::find(world, container, assets).definite();
-        let container_pos = *world
-            .get::<&Pos>(container)
-            .map_err(|_| format!("{performer_name} lost track of {container_name}."))?;
+        let container_pos = *world.get::<&Pos>(container).map_err(|_| {
+            assets.messages.text(
+                "item.lost_track",
+                &[("performer", &performer_name), ("item", &container_name)],
+            )
+        })?;
 
         if !world.satisfies::<&inventory::Container>(container) {
-            return Err(Error::private(format!(
-                "{container_name} is not a searchable container."
-            )));
+            return Err(Error::private(
+                assets
+                    .messages
+                    .text("search.not_a_container", &[("container", &container_name)]),
+            ));
         }
 
         position::push_and_move(world, performer, container_pos, assets)?;
 
         let items = inventory::get_held(world, container);
         if items.is_empty() {
-            context.view_context.add_message_at(container_pos.get_area(), format!(
-                "{performer_name} searched {container_name}, but did not find anything of interest."
-            ), context.state);
+            let message = assets.messages.text(
+                "search.found_nothing",
+                &[
+                    ("performer", &performer_name),
+                    ("container", &container_name),
+                ],
+            );
+            context
+                .view_context
+                .add_message_at(container_pos.get_area(), message, context.state);
             return Ok(action::Success);
         }
 
@@ -152,9 +185,13 @@ impl SearchAction {
         );
         context.view_context.add_message_at(
             container_pos.get_area(),
-            format!(
-                "{performer_name} searched {container_name} and found {items}.",
-                items = text::join_elements(items)
+            assets.messages.text(
+                "search.found_items",
+                &[
+                    ("performer", &performer_name),
+                    ("container", &container_name),
+                    ("items", &text::join_elements(items)),
+                ],
             ),
             context.state,
         );
@@ -182,34 +219,39 @@ pub(super) fn give_item(
         .ok()
         .is_none_or(|in_inv| !in_inv.held_by(performer))
     {
-        return Err(Error::private(format!(
-            "{performer_name} lost track of the item they were going to give."
-        )));
+        return Err(Error::private(
+            assets
+                .messages
+                .text("give.lost_item", &[("performer", &performer_name)]),
+        ));
     }
 
     let performer_pos = *world
         .get::<&Pos>(performer)
         .expect("Expected performer to have a position");
-    let receiver_placement =
-        world
-            .query_one_mut::<PlacementQuery>(receiver)
-            .map(Placement::from)
-            .map_err(|_| {
-                format!(
-                    "{receiver_name} disappeared before {performer_name} could interact with them.",
-                )
-            })?;
+    let receiver_placement = world
+        .query_one_mut::<PlacementQuery>(receiver)
+        .map(Placement::from)
+        .map_err(|_| {
+            assets.messages.text(
+                "give.receiver_disappeared",
+                &[("performer", &performer_name), ("receiver", &receiver_name)],
+            )
+        })?;
 
     if !performer_pos.is_in(receiver_placement.area()) {
-        return Err(Error::private(format!(
-            "{receiver_name} left before {performer_name} could interact with them.",
+        return Err(Error::private(assets.messages.text(
+            "give.receiver_left",
+            &[("performer", &performer_name), ("receiver", &receiver_name)],
         )));
     }
 
     if !status::is_alive(receiver, world) {
-        return Err(Error::private(format!(
-            "{receiver_name} died before they could be given an item."
-        )));
+        return Err(Error::private(
+            assets
+                .messages
+                .text("give.receiver_died", &[("receiver", &receiver_name)]),
+        ));
     }
 
     let movement = position::prepare_move_adjacent_placement(world, performer, receiver_placement)
@@ -232,11 +274,20 @@ pub(super) fn give_item(
         .insert_one(item, Held::in_inventory(receiver))
         .unwrap();
 
+    view_context.add_event(GameEvent::ItemGiven {
+        giver: performer,
+        receiver,
+        item,
+    });
     view_context.add_message_at(
         performer_pos.get_area(),
-        format!(
-            "{performer_name} gave {receiver_name} a {}.",
-            NameData::find(&state.world, item, assets).base(),
+        assets.messages.text(
+            "give.success",
+            &[
+                ("performer", &performer_name),
+                ("receiver", &receiver_name),
+                ("item", &NameData::find(&state.world, item, assets).base()),
+            ],
         ),
         state,
     );
@@ -257,17 +308,33 @@ pub(super) fn wield(
         inventory::unwield_if_needed(world, performer);
         world.insert_one(item, Held::in_hand(performer)).unwrap();
 
+        context.view_context.add_event(GameEvent::ItemWielded {
+            character: performer,
+            item,
+        });
         let performer_area = world.get::<&Pos>(performer).unwrap().get_area();
         context.view_context.add_message_at(
             performer_area,
-            format!("{performer_name} wielded {}.", item_name.definite()),
+            assets.messages.text(
+                "item.wield",
+                &[
+                    ("performer", &performer_name),
+                    ("item", &item_name.definite()),
+                ],
+            ),
             context.state,
         );
         Ok(action::Success)
     } else {
-        let item_pos = *world
-            .get::<&Pos>(item)
-            .map_err(|_| format!("{} lost track of {}.", performer_name, item_name.definite()))?;
+        let item_pos = *world.get::<&Pos>(item).map_err(|_| {
+            assets.messages.text(
+                "item.lost_track",
+                &[
+                    ("performer", &performer_name),
+                    ("item", &item_name.definite()),
+                ],
+            )
+        })?;
         position::push_and_move(world, performer, item_pos, assets)?;
 
         inventory::unwield_if_needed(world, performer);
@@ -277,11 +344,87 @@ pub(super) fn wield(
 
         behavior::trigger_aggression_in_area(world, item_pos.get_area());
 
+        context.view_context.add_event(GameEvent::ItemWielded {
+            character: performer,
+            item,
+        });
+        context.view_context.add_message_at(
+            item_pos.get_area(),
+            assets.messages.text(
+                "item.pick_up_and_wield",
+                &[
+                    ("performer", &performer_name),
+                    ("item", &item_name.definite()),
+                ],
+            ),
+            context.state,
+        );
+        Ok(action::Success)
+    }
+}
+
+pub(super) fn wear(
+    context: &mut Context,
+    performer: Entity,
+    item: Entity,
+    item_name: NameData,
+) -> action::Result {
+    let assets = context.view_context.view_buffer.assets;
+    let world = &mut context.state.world;
+    let performer_name = NameData::find(world, performer, assets).definite();
+
+    if inventory::is_in_inventory(world, item, performer) {
+        inventory::take_off_armor_if_needed(world, performer);
+        world.insert_one(item, Held::worn(performer)).unwrap();
+
+        context.view_context.add_event(GameEvent::ItemWorn {
+            character: performer,
+            item,
+        });
+        let performer_area = world.get::<&Pos>(performer).unwrap().get_area();
+        context.view_context.add_message_at(
+            performer_area,
+            assets.messages.text(
+                "item.wear",
+                &[
+                    ("performer", &performer_name),
+                    ("item", &item_name.definite()),
+                ],
+            ),
+            context.state,
+        );
+        Ok(action::Success)
+    } else {
+        let item_pos = *world.get::<&Pos>(item).map_err(|_| {
+            assets.messages.text(
+                "item.lost_track",
+                &[
+                    ("performer", &performer_name),
+                    ("item", &item_name.definite()),
+                ],
+            )
+        })?;
+        position::push_and_move(world, performer, item_pos, assets)?;
+
+        inventory::take_off_armor_if_needed(world, performer);
+        world
+            .exchange_one::<Pos, _>(item, Held::worn(performer))
+            .expect("Tried moving item");
+
+        behavior::trigger_aggression_in_area(world, item_pos.get_area());
+
+        context.view_context.add_event(GameEvent::ItemWorn {
+            character: performer,
+            item,
+        });
         context.view_context.add_message_at(
             item_pos.get_area(),
-            format!(
-                "{performer_name} picked up and wielded {}.",
-                item_name.definite()
+            assets.messages.text(
+                "item.pick_up_and_wear",
+                &[
+                    ("performer", &performer_name),
+                    ("item", &item_name.definite()),
+                ],
             ),
             context.state,
         );
@@ -289,6 +432,41 @@ pub(super) fn wield(
     }
 }
 
+pub(super) fn take_off(context: &mut Context, performer: Entity, item: Entity) -> action::Result {
+    let assets = context.view_context.view_buffer.assets;
+    let world = &mut context.state.world;
+    let performer_name = NameData::find(world, performer, assets).definite();
+    let item_name = NameData::find(world, item, assets).definite();
+
+    if !world
+        .get::<&Held>(item)
+        .is_ok_and(|held| held.held_by(performer) && held.is_worn())
+    {
+        return Err(Error::private(assets.messages.text(
+            "item.take_off.not_worn",
+            &[("performer", &performer_name), ("item", &item_name)],
+        )));
+    }
+    world
+        .insert_one(item, Held::in_inventory(performer))
+        .unwrap();
+
+    context.view_context.add_event(GameEvent::ItemTakenOff {
+        character: performer,
+        item,
+    });
+    let performer_area = world.get::<&Pos>(performer).unwrap().get_area();
+    context.view_context.add_message_at(
+        performer_area,
+        assets.messages.text(
+            "item.take_off",
+            &[("performer", &performer_name), ("item", &item_name)],
+        ),
+        context.state,
+    );
+    Ok(action::Success)
+}
+
 #[derive(Debug, Clone)]
 pub struct UseAction {
     pub item: Entity,
@@ -318,7 +496,11 @@ impl UseAction {
                     .get::<&Held>()
                     .is_some_and(|held| held.held_by(performer))
             })
-            .ok_or_else(|| format!("{performer_name} tried using an item not held by them."))?;
+            .ok_or_else(|| {
+                assets
+                    .messages
+                    .text("item.use.not_held", &[("performer", &performer_name)])
+            })?;
         let item_name = NameData::find_by_ref(item_ref, assets).definite();
 
         let item_use_type = item_ref
@@ -326,10 +508,16 @@ impl UseAction {
             .and_then(|id| assets.item_type_map.get(&id))
             .and_then(|data| data.usage.as_ref())
             .ok_or_else(|| {
-                Error::private(format!(
-                    "{performer_name} tried to use {item_name}, but it is not usable."
+                Error::private(assets.messages.text(
+                    "item.use.not_usable",
+                    &[("performer", &performer_name), ("item", &item_name)],
                 ))
             })?;
+        let used_event = GameEvent::ItemUsed {
+            character: performer,
+            item: self.item,
+            item_type: item_ref.get::<&ItemTypeId>().map(deref_clone).unwrap(),
+        };
 
         match *item_use_type {
             ItemUseType::Medkit {
@@ -338,9 +526,11 @@ impl UseAction {
             } => {
                 let mut health = performer_ref.get::<&mut Health>().unwrap();
                 if !health.is_hurt() {
-                    return Err(Error::private(format!(
-                        "{performer_name} no longer needs to use a medkit.",
-                    )));
+                    return Err(Error::private(
+                        assets
+                            .messages
+                            .text("item.medkit.not_needed", &[("performer", &performer_name)]),
+                    ));
                 }
 
                 if use_duration > self.use_time + 1 {
@@ -359,9 +549,67 @@ impl UseAction {
                     drop(health);
                     world.despawn(self.item).unwrap();
 
+                    context.view_context.add_event(used_event);
                     context.view_context.add_message_at(
                         area,
-                        format!("{performer_name} used a medkit and recovered some health."),
+                        assets
+                            .messages
+                            .text("item.medkit.used", &[("performer", &performer_name)]),
+                        context.state,
+                    );
+                }
+                Ok(action::Success)
+            }
+            ItemUseType::Consumable {
+                ref effects,
+                ref cures,
+            } => {
+                world.despawn(self.item).unwrap();
+                let cured_effects = cures
+                    .iter()
+                    .copied()
+                    .filter(|&kind| effect::remove_effect(world, performer, kind))
+                    .collect::<Vec<_>>();
+                let applied_effects = effects
+                    .iter()
+                    .filter(|application| {
+                        effect::apply_effect(world, performer, application, assets)
+                    })
+                    .map(|application| application.effect)
+                    .collect::<Vec<_>>();
+
+                context.view_context.add_event(used_event);
+                context.view_context.add_message_at(
+                    area,
+                    assets.messages.text(
+                        "item.use",
+                        &[("performer", &performer_name), ("item", &item_name)],
+                    ),
+                    context.state,
+                );
+                for kind in cured_effects {
+                    context
+                        .view_context
+                        .add_event(GameEvent::StatusEffectEnded {
+                            entity: performer,
+                            effect: kind,
+                        });
+                    context.view_context.add_message_at(
+                        area,
+                        effect::ended_message(kind, &performer_name, assets),
+                        context.state,
+                    );
+                }
+                for kind in applied_effects {
+                    context
+                        .view_context
+                        .add_event(GameEvent::StatusEffectApplied {
+                            entity: performer,
+                            effect: kind,
+                        });
+                    context.view_context.add_message_at(
+                        area,
+                        effect::applied_message(kind, performer, &context.state.world, assets),
                         context.state,
                     );
                 }
@@ -369,21 +617,24 @@ impl UseAction {
             }
             ItemUseType::BlackOrb { change } => {
                 let Some(_) = change.try_apply(performer_ref) else {
-                    context.view_context.add_message_at(area, format!(
-                        "{performer_name} holds up and inspects the orb, but can't figure out what it is."
-                    ), context.state);
+                    let message = assets.messages.text(
+                        "item.black_orb.no_effect",
+                        &[("performer", &performer_name)],
+                    );
+                    context
+                        .view_context
+                        .add_message_at(area, message, context.state);
                     return Ok(action::Success);
                 };
 
                 world.despawn(self.item).unwrap();
 
+                context.view_context.add_event(used_event);
                 context.view_context.add_message_at(
                     area,
-                    format!(
-                        "{performer_name} holds up and inspects the orb. \
-                     {performer_name} gets a sensation of hardiness when suddenly, \
-                     the orb cracks and falls apart into worthless pieces! (Stats have changed)"
-                    ),
+                    assets
+                        .messages
+                        .text("item.black_orb.used", &[("performer", &performer_name)]),
                     context.state,
                 );
                 Ok(action::Success)
@@ -398,19 +649,24 @@ impl UseAction {
                         )
                     })
                 else {
-                    context.view_context.add_message_at(area, format!(
-                        "{performer_name} gazes into the mirror, but nothing seems to come from doing so."
-                    ), context.state);
+                    let message = assets.messages.text(
+                        "item.odd_hand_mirror.no_effect",
+                        &[("performer", &performer_name)],
+                    );
+                    context
+                        .view_context
+                        .add_message_at(area, message, context.state);
                     return Ok(action::Success);
                 };
 
                 world.despawn(self.item).unwrap();
 
+                context.view_context.add_event(used_event);
                 context.view_context.add_message_at(
                     area,
-                    format!(
-                        "{performer_name} holds up and gazes into the mirror. After a few moments, the glass suddenly cracks! \
-                     Turning away from the broken mirror, {performer_name} gives off a different vibe from just a moment ago. (Stats have changed)"
+                    assets.messages.text(
+                        "item.odd_hand_mirror.used",
+                        &[("performer", &performer_name)],
                     ),
                     context.state,
                 );
@@ -420,6 +676,121 @@ impl UseAction {
     }
 }
 
+/// Using a held medkit on another character, or on the performer themselves.
+#[derive(Debug, Clone)]
+pub struct TreatAction {
+    pub item: Entity,
+    pub patient: Entity,
+    pub use_time: u16,
+}
+
+impl From<TreatAction> for super::Action {
+    fn from(value: TreatAction) -> Self {
+        Self::Treat(value)
+    }
+}
+
+impl TreatAction {
+    pub(super) fn run(self, performer: Entity, mut context: Context) -> action::Result {
+        let assets = context.view_context.view_buffer.assets;
+        let Self {
+            item,
+            patient,
+            use_time,
+        } = self;
+        let world = &mut context.state.world;
+        let performer_name = NameData::find(world, performer, assets).definite();
+        let patient_name = NameData::find(world, patient, assets).definite();
+        let area = world.get::<&Pos>(performer).unwrap().get_area();
+
+        let Some((restore_fraction, use_duration)) = world
+            .get::<&Held>(item)
+            .ok()
+            .filter(|held| held.held_by(performer))
+            .and_then(|_| world.get::<&ItemTypeId>(item).ok())
+            .and_then(|item_type| assets.item_type_map.get(&item_type))
+            .and_then(|data| match data.usage {
+                Some(ItemUseType::Medkit {
+                    restore_fraction,
+                    use_duration,
+                }) => Some((restore_fraction, use_duration)),
+                _ => None,
+            })
+        else {
+            return Err(Error::private(
+                assets
+                    .messages
+                    .text("item.medkit.not_held", &[("performer", &performer_name)]),
+            ));
+        };
+
+        if !status::is_alive(patient, world)
+            || !world.get::<&Pos>(patient).is_ok_and(|pos| pos.is_in(area))
+        {
+            return Err(Error::private(assets.messages.text(
+                "item.medkit.lost_patient",
+                &[("performer", &performer_name), ("patient", &patient_name)],
+            )));
+        }
+        if world
+            .get::<&Health>(patient)
+            .is_ok_and(|health| !health.is_hurt())
+        {
+            return Err(Error::private(assets.messages.text(
+                "item.medkit.patient_not_needed",
+                &[("patient", &patient_name)],
+            )));
+        }
+        if patient != performer {
+            let patient_placement =
+                Placement::from(world.query_one_mut::<PlacementQuery>(patient).unwrap());
+            position::move_adjacent_placement(world, performer, patient_placement, assets)?;
+        }
+
+        if use_duration > use_time + 1 {
+            world
+                .insert_one(
+                    performer,
+                    RepeatingAction::Treat {
+                        item,
+                        patient,
+                        use_time: use_time + 1,
+                    },
+                )
+                .unwrap();
+            return Ok(action::Success);
+        }
+
+        let patient_ref = world.entity(patient).unwrap();
+        patient_ref
+            .get::<&mut Health>()
+            .unwrap()
+            .restore_fraction(restore_fraction, patient_ref);
+        let item_type = world.get::<&ItemTypeId>(item).map(deref_clone).unwrap();
+        world.despawn(item).unwrap();
+
+        context.view_context.add_event(GameEvent::ItemUsed {
+            character: performer,
+            item,
+            item_type,
+        });
+        let message = if patient == performer {
+            assets
+                .messages
+                .text("item.medkit.used", &[("performer", &performer_name)])
+        } else {
+            assets.messages.text(
+                "item.medkit.used_on",
+                &[("performer", &performer_name), ("patient", &patient_name)],
+            )
+        };
+        context
+            .view_context
+            .add_message_at(area, message, context.state);
+        Ok(action::Success)
+    }
+}
+
 pub const FOUR_LEAF_CLOVER_EFFECT: StatChanges = StatChanges {
     luck: 2,
     ..StatChanges::DEFAULT
@@ -1,11 +1,16 @@
-use crate::action::{self, Error};
+use crate::action::{self, Error, trade};
 use crate::asset::GameAssets;
-use crate::core::area::{self, FuelAmount, ShipControls, ShipState, ShipStatus};
+use crate::core::area::{
+    self, FuelAmount, SHIP_CARGO_CAPACITY, ShipControls, ShipState, ShipStatus, ShipUpgrade,
+    StoredInShip,
+};
+use crate::core::inventory::Held;
 use crate::core::item::ItemTypeId;
 use crate::core::name::{NameData, NameIdData, NameQuery};
 use crate::core::position::{self, Placement, PlacementQuery, Pos};
 use crate::core::{CrewMember, inventory};
 use crate::game_loop::GameState;
+use crate::view::event::GameEvent;
 use crate::view::text::{self, CombinableMsgType};
 use hecs::{Entity, World};
 
@@ -14,7 +19,7 @@ pub fn refuel(context: &mut action::Context, performer: Entity) -> action::Resul
     let state = &mut *context.state;
     let area = state.world.get::<&Pos>(performer).unwrap().get_area();
 
-    let (status, controls_placement) = lookup_ship_state(state, area)?;
+    let (status, controls_placement) = lookup_ship_state(state, area, assets)?;
 
     position::move_adjacent_placement(&mut state.world, performer, controls_placement, assets)?;
 
@@ -31,9 +36,9 @@ pub fn refuel(context: &mut action::Context, performer: Entity) -> action::Resul
             ),
         },
         ShipStatus::Refueled => {
-            return Err(Error::visible(format!(
-                "{} goes to refuel the ship, but sees that it is already refueled.",
-                name_id.lookup(assets).definite()
+            return Err(Error::visible(assets.messages.text(
+                "ship.already_refueled",
+                &[("performer", &name_id.lookup(assets).definite())],
             )));
         }
         ShipStatus::Launching => return Ok(action::Success),
@@ -46,6 +51,12 @@ pub fn refuel(context: &mut action::Context, performer: Entity) -> action::Resul
             .get::<&mut ShipState>(state.ship_core)
             .unwrap()
             .status = new_status;
+        context
+            .view_context
+            .add_event(GameEvent::ShipStatusChanged {
+                character: performer,
+                status: new_status,
+            });
     }
 
     context.view_context.add_message_at(area, message, state);
@@ -57,13 +68,13 @@ pub fn launch(context: &mut action::Context, performer: Entity) -> action::Resul
     let state = &mut *context.state;
     if state.generation_state.is_at_fortuna() {
         return Err(Error::private(
-            "The crew won't leave until they find the treasure here.",
+            assets.messages.text("ship.launch.at_fortuna", &[]),
         ));
     }
 
     let area = state.world.get::<&Pos>(performer).unwrap().get_area();
 
-    let (status, controls_placement) = lookup_ship_state(state, area)?;
+    let (status, controls_placement) = lookup_ship_state(state, area, assets)?;
 
     position::move_adjacent_placement(&mut state.world, performer, controls_placement, assets)?;
 
@@ -71,14 +82,17 @@ pub fn launch(context: &mut action::Context, performer: Entity) -> action::Resul
         ShipStatus::NeedFuel(amount) => refuel_then_launch(state, performer, amount, assets),
         ShipStatus::Refueled => (
             ShipStatus::Launching,
-            format!(
-                "{} set the ship to launch.",
-                NameData::find(&state.world, performer, assets).definite(),
+            assets.messages.text(
+                "ship.launch",
+                &[(
+                    "performer",
+                    &NameData::find(&state.world, performer, assets).definite(),
+                )],
             ),
         ),
         ShipStatus::Launching => (
             ShipStatus::Launching,
-            "The ship is already launching.".to_string(),
+            assets.messages.text("ship.launch.already_launching", &[]),
         ),
     };
 
@@ -89,12 +103,174 @@ pub fn launch(context: &mut action::Context, performer: Entity) -> action::Resul
             .get::<&mut ShipState>(state.ship_core)
             .unwrap()
             .status = new_status;
+        context
+            .view_context
+            .add_event(GameEvent::ShipStatusChanged {
+                character: performer,
+                status: new_status,
+            });
     }
 
     context.view_context.add_message_at(area, message, state);
     Ok(action::Success)
 }
 
+pub fn store_item(
+    context: &mut action::Context,
+    performer: Entity,
+    item: Entity,
+) -> action::Result {
+    let assets = context.view_context.view_buffer.assets;
+    let world = &mut context.state.world;
+    let performer_name = NameData::find(world, performer, assets).definite();
+    let item_name = NameData::find(world, item, assets).definite();
+    let area = check_in_ship(world, performer, &performer_name, assets)?;
+
+    if world
+        .get::<&Held>(item)
+        .ok()
+        .is_none_or(|held| !held.held_by(performer))
+    {
+        return Err(Error::private(assets.messages.text(
+            "ship.cargo.lost_item",
+            &[("performer", &performer_name), ("item", &item_name)],
+        )));
+    }
+    if area::stored_items(world).len() >= SHIP_CARGO_CAPACITY {
+        return Err(Error::private(assets.messages.text(
+            "ship.cargo.full",
+            &[("performer", &performer_name), ("item", &item_name)],
+        )));
+    }
+
+    world.exchange_one::<Held, _>(item, StoredInShip).unwrap();
+
+    context.view_context.add_event(GameEvent::ItemStoredInShip {
+        character: performer,
+        item,
+    });
+    context.view_context.add_message_at(
+        area,
+        assets.messages.text(
+            "ship.cargo.store",
+            &[("performer", &performer_name), ("item", &item_name)],
+        ),
+        context.state,
+    );
+    Ok(action::Success)
+}
+
+pub fn retrieve_item(
+    context: &mut action::Context,
+    performer: Entity,
+    item: Entity,
+) -> action::Result {
+    let assets = context.view_context.view_buffer.assets;
+    let world = &mut context.state.world;
+    let performer_name = NameData::find(world, performer, assets).definite();
+    let item_name = NameData::find(world, item, assets).definite();
+    let area = check_in_ship(world, performer, &performer_name, assets)?;
+
+    if !world.satisfies::<&StoredInShip>(item) {
+        return Err(Error::private(assets.messages.text(
+            "ship.cargo.lost_item",
+            &[("performer", &performer_name), ("item", &item_name)],
+        )));
+    }
+
+    world
+        .exchange_one::<StoredInShip, _>(item, Held::in_inventory(performer))
+        .unwrap();
+
+    context
+        .view_context
+        .add_event(GameEvent::ItemRetrievedFromShip {
+            character: performer,
+            item,
+        });
+    context.view_context.add_message_at(
+        area,
+        assets.messages.text(
+            "ship.cargo.retrieve",
+            &[("performer", &performer_name), ("item", &item_name)],
+        ),
+        context.state,
+    );
+    Ok(action::Success)
+}
+
+fn check_in_ship(
+    world: &World,
+    performer: Entity,
+    performer_name: &str,
+    assets: &GameAssets,
+) -> Result<Entity, String> {
+    let area = world.get::<&Pos>(performer).unwrap().get_area();
+    if area::is_ship(area, world) {
+        Ok(area)
+    } else {
+        Err(assets
+            .messages
+            .text("ship.cargo.not_in_ship", &[("performer", performer_name)]))
+    }
+}
+
+pub fn install_upgrade(
+    context: &mut action::Context,
+    performer: Entity,
+    upgrade: ShipUpgrade,
+) -> action::Result {
+    let assets = context.view_context.view_buffer.assets;
+    let state = &mut *context.state;
+    let area = state.world.get::<&Pos>(performer).unwrap().get_area();
+
+    let (_, controls_placement) = lookup_ship_state(state, area, assets)?;
+    if state
+        .world
+        .get::<&ShipState>(state.ship_core)
+        .unwrap()
+        .has_upgrade(upgrade)
+    {
+        return Err(Error::private(assets.messages.text(
+            "ship.upgrade.already_installed",
+            &[("upgrade", upgrade.name())],
+        )));
+    }
+
+    position::move_adjacent_placement(&mut state.world, performer, controls_placement, assets)?;
+
+    let crew = state.world.get::<&CrewMember>(performer).unwrap().0;
+    trade::try_spend_points(state.world.entity(crew).unwrap(), upgrade.price(), assets)?;
+    state
+        .world
+        .get::<&mut ShipState>(state.ship_core)
+        .unwrap()
+        .upgrades
+        .push(upgrade);
+
+    context
+        .view_context
+        .add_event(GameEvent::ShipUpgradeInstalled {
+            character: performer,
+            upgrade,
+        });
+    context.view_context.add_message_at(
+        area,
+        assets.messages.text(
+            "ship.upgrade.install",
+            &[
+                (
+                    "performer",
+                    &NameData::find(&state.world, performer, assets).definite(),
+                ),
+                ("upgrade", upgrade.name()),
+            ],
+        ),
+        state,
+    );
+    Ok(action::Success)
+}
+
 fn refuel_then_launch(
     state: &mut GameState,
     performer: Entity,
@@ -106,9 +282,9 @@ fn refuel_then_launch(
         RefuelResult::Incomplete(new_amount) => (
             ShipStatus::NeedFuel(new_amount),
             if new_amount != amount {
-                format!("{name} refueled the ship.")
+                assets.messages.text("ship.refuel", &[("performer", &name)])
             } else {
-                incomplete_refuel_message(new_amount, &name)
+                incomplete_refuel_message(new_amount, &name, assets)
             },
         ),
         RefuelResult::Complete => {
@@ -123,14 +299,19 @@ fn refuel_then_launch(
             if absent_crew.is_empty() {
                 (
                     ShipStatus::Launching,
-                    format!("{name} refueled the ship, and set it to launch."),
+                    assets
+                        .messages
+                        .text("ship.refuel_and_launch", &[("performer", &name)]),
                 )
             } else {
                 (
                     ShipStatus::Refueled,
-                    format!(
-                        "{name} refueled the ship. Warning: not all crew members have boarded the ship yet. {absent_crew} are still absent.",
-                        absent_crew = text::join_elements(absent_crew)
+                    assets.messages.text(
+                        "ship.refuel_with_absent_crew",
+                        &[
+                            ("performer", &name),
+                            ("absent_crew", &text::join_elements(absent_crew)),
+                        ],
                     ),
                 )
             }
@@ -138,11 +319,15 @@ fn refuel_then_launch(
     }
 }
 
-fn lookup_ship_state(state: &GameState, area: Entity) -> Result<(ShipStatus, Placement), String> {
+fn lookup_ship_state(
+    state: &GameState,
+    area: Entity,
+    assets: &GameAssets,
+) -> Result<(ShipStatus, Placement), String> {
     let status = state
         .world
         .get::<&ShipState>(state.ship_core)
-        .map_err(|_| "The crew has no ship.".to_string())?
+        .map_err(|_| assets.messages.text("ship.missing", &[]))?
         .status;
 
     let controls_pos = state
@@ -152,7 +337,7 @@ fn lookup_ship_state(state: &GameState, area: Entity) -> Result<(ShipStatus, Pla
         .iter()
         .map(Placement::from)
         .find(|placement| placement.pos.is_in(area) && area::is_ship(area, &state.world))
-        .ok_or_else(|| "Must be in the ship control room to do this.".to_string())?;
+        .ok_or_else(|| assets.messages.text("ship.not_at_controls", &[]))?;
 
     Ok((status, controls_pos))
 }
@@ -162,11 +347,12 @@ enum RefuelResult {
     Incomplete(FuelAmount),
 }
 
-fn incomplete_refuel_message(amount: FuelAmount, name: &str) -> String {
-    match amount {
-        FuelAmount::TwoCans => format!("{name} need two fuel cans to refuel the ship."),
-        FuelAmount::OneCan => format!("{name} still need one more fuel can to refuel the ship."),
-    }
+fn incomplete_refuel_message(amount: FuelAmount, name: &str, assets: &GameAssets) -> String {
+    let message_id = match amount {
+        FuelAmount::TwoCans => "ship.refuel.need_two_cans",
+        FuelAmount::OneCan => "ship.refuel.need_one_can",
+    };
+    assets.messages.text(message_id, &[("performer", name)])
 }
 
 fn try_refuel(amount: FuelAmount, world: &mut World, performer: Entity) -> RefuelResult {
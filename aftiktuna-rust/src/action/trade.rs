@@ -1,4 +1,5 @@
 use crate::action::{self, Error};
+use crate::asset::GameAssets;
 use crate::core::display::DialogueExpression;
 use crate::core::inventory::{self, Held};
 use crate::core::item::ItemTypeId;
@@ -45,20 +46,28 @@ pub fn buy(
 ) -> action::Result {
     let assets = context.view_context.view_buffer.assets;
     let world = &mut context.state.world;
-    let item_type = try_buy(world, performer, &item_type, amount)?;
+    let item_type = try_buy(world, performer, &item_type, amount, assets)?;
 
     for _ in 0..amount {
         item_type.spawn(world, Held::in_inventory(performer));
     }
 
     context.view_context.view_buffer.add_change_message(
-        format!(
-            "{the_performer} bought {an_item}.",
-            the_performer = NameData::find(world, performer, assets).definite(),
-            an_item = assets
-                .noun_data_map
-                .lookup(&item_type.noun_id())
-                .with_text_count(amount, name::ArticleKind::A),
+        assets.messages.text(
+            "store.buy",
+            &[
+                (
+                    "performer",
+                    &NameData::find(world, performer, assets).definite(),
+                ),
+                (
+                    "items",
+                    &assets
+                        .noun_data_map
+                        .lookup(&item_type.noun_id())
+                        .with_text_count(amount, name::ArticleKind::A),
+                ),
+            ],
         ),
         context.state,
     );
@@ -70,6 +79,7 @@ fn try_buy(
     performer: Entity,
     item_type: &ItemTypeId,
     amount: u16,
+    assets: &GameAssets,
 ) -> Result<ItemTypeId, String> {
     let crew = world.get::<&CrewMember>(performer).unwrap().0;
     let shopkeeper = world
@@ -77,7 +87,8 @@ fn try_buy(
         .map_err(|_| "Tried to buy while not trading.")?
         .0;
     let mut shopkeeper = world.get::<&mut Shopkeeper>(shopkeeper).unwrap();
-    let stock = find_stock(&mut shopkeeper, item_type).ok_or("The item is not in stock.")?;
+    let stock = find_stock(&mut shopkeeper, item_type)
+        .ok_or_else(|| assets.messages.text("store.not_in_stock", &[]))?;
     if amount < 1 {
         return Err("Tried to purchase a non-positive number of items.".to_owned());
     }
@@ -89,6 +100,7 @@ fn try_buy(
     try_spend_points(
         world.entity(crew).unwrap(),
         stock.price.buy_price() * i32::from(amount),
+        assets,
     )?;
     stock.quantity = new_quantity;
 
@@ -129,7 +141,7 @@ pub fn sell(
             .item_type_map
             .get(&item_type)
             .and_then(|data| data.price)
-            .ok_or("That item can not be sold.")?
+            .ok_or_else(|| assets.messages.text("store.cannot_sell", &[]))?
             .sell_price();
         is_selling_fuel |= item_ref
             .get::<&ItemTypeId>()
@@ -143,9 +155,11 @@ pub fn sell(
             fuel_amount > inventory::fuel_cans_held_by_crew(world, &items)
         })
     {
-        return Err(Error::private(format!(
-            "{performer_name} does not want to sell their fuel can, since they need it to refuel their ship."
-        )));
+        return Err(Error::private(
+            assets
+                .messages
+                .text("store.keep_fuel_can", &[("performer", &performer_name)]),
+        ));
     }
 
     let crew = world.get::<&CrewMember>(performer).unwrap().0;
@@ -162,9 +176,13 @@ pub fn sell(
     }
 
     context.view_context.view_buffer.add_change_message(
-        format!(
-            "{performer_name} sold {items} for {value}.",
-            items = text::join_elements(item_list)
+        assets.messages.text(
+            "store.sell",
+            &[
+                ("performer", &performer_name),
+                ("items", &text::join_elements(item_list)),
+                ("value", &value.to_string()),
+            ],
         ),
         context.state,
     );
@@ -176,6 +194,7 @@ pub(super) fn ask_about(
     performer: Entity,
     item_type: &ItemTypeId,
 ) -> action::Result {
+    let assets = context.view_context.view_buffer.assets;
     let shopkeeper = context
         .state
         .world
@@ -187,28 +206,19 @@ pub(super) fn ask_about(
         &context.state.world,
         performer,
         DialogueExpression::Neutral,
-        format!(
-            "What is that {}?",
-            context
-                .view_context
-                .view_buffer
-                .assets
-                .noun_data_map
-                .lookup(&item_type.noun_id())
-                .singular()
+        assets.messages.text(
+            "store.ask_about",
+            &[(
+                "item",
+                assets.noun_data_map.lookup(&item_type.noun_id()).singular(),
+            )],
         ),
     );
-    let response = context
-        .view_context
-        .view_buffer
-        .assets
+    let response = assets
         .item_type_map
         .get(item_type)
-        .and_then(|item_data| item_data.shop_description.as_ref())
-        .map_or(
-            "I am not sure. But if it interests you, how about you buy it and find out?",
-            String::as_str,
-        );
+        .and_then(|item_data| item_data.shop_description.clone())
+        .unwrap_or_else(|| assets.messages.text("store.ask_about.unknown", &[]));
     context.view_context.view_buffer.push_dialogue(
         &context.state.world,
         shopkeeper,
@@ -220,31 +230,38 @@ pub(super) fn ask_about(
 }
 
 pub fn exit(context: &mut action::Context, performer: Entity) -> action::Result {
-    let performer_name = NameData::find(
-        &context.state.world,
-        performer,
-        context.view_context.view_buffer.assets,
-    )
-    .definite();
+    let assets = context.view_context.view_buffer.assets;
+    let performer_name = NameData::find(&context.state.world, performer, assets).definite();
     context
         .state
         .world
         .remove_one::<IsTrading>(performer)
-        .map_err(|_| format!("{performer_name} is already not trading."))?;
+        .map_err(|_| {
+            assets.messages.text(
+                "store.already_not_trading",
+                &[("performer", &performer_name)],
+            )
+        })?;
 
     context.view_context.view_buffer.add_change_message(
-        format!("{performer_name} stops trading with the shopkeeper."),
+        assets
+            .messages
+            .text("store.exit", &[("performer", &performer_name)]),
         context.state,
     );
     Ok(action::Success)
 }
 
-fn try_spend_points(crew_ref: EntityRef, points: i32) -> Result<(), String> {
+pub(super) fn try_spend_points(
+    crew_ref: EntityRef,
+    points: i32,
+    assets: &GameAssets,
+) -> Result<(), String> {
     let mut crew_points = crew_ref
         .get::<&mut Points>()
         .ok_or("The crew is missing its wallet.")?;
     if crew_points.0 < points {
-        return Err("The crew cannot afford that.".to_string());
+        return Err(assets.messages.text("points.cannot_afford", &[]));
     }
 
     crew_points.0 -= points;
@@ -81,16 +81,17 @@ pub mod pathing {
     }
 }
 
-use crate::action::item::UseAction;
+use crate::action::item::{TreatAction, UseAction};
 use crate::action::{Action, ForceDoorAction, TalkAction};
 use crate::asset::species::SpeciesDataMap;
 use crate::asset::{GameAssets, ItemTypeData};
 use crate::core::area::{self, ShipControls, ShipState, ShipStatus};
 use crate::core::behavior::{
-    self, BadlyHurtBehavior, Character, Decision, GivesHuntRewardData, Hostile, Intention,
-    ObservationTarget, Passenger, PassengerPhase, Recruitable, RepeatingAction, Waiting, Wandering,
+    self, BadlyHurtBehavior, BehaviorProfile, Character, Decision, GivesHuntRewardData, Hostile,
+    Intention, ObservationTarget, Order, Passenger, PassengerPhase, Recruitable, RepeatingAction,
+    Waiting, Wandering,
 };
-use crate::core::combat::{self, AttackKind};
+use crate::core::combat::{self, AttackKind, WeaponProperties};
 use crate::core::item::ItemTypeId;
 use crate::core::name::NameData;
 use crate::core::position::{self, OccupiesSpace, Pos};
@@ -164,20 +165,38 @@ fn pick_crew_member_intention(
         }
     }
 
-    let current_properties = combat::get_active_weapon_properties(world, crew_member, assets);
+    let current_value = weapon_value(
+        &combat::get_active_weapon_properties(world, crew_member, assets),
+        world,
+        crew_member,
+    );
 
     for item in inventory::get_inventory(world, crew_member) {
         if let Some(properties) = world
             .get::<&ItemTypeId>(item)
             .ok()
             .and_then(|item_type| assets.item_type_map.get(&item_type))
-            .and_then(|data| data.weapon)
-            && properties.damage_mod > current_properties.damage_mod
+            .and_then(|data| data.weapon.as_ref())
+            && weapon_value(properties, world, crew_member) > current_value
         {
             return Some(Intention::Wield(item));
         }
     }
 
+    if inventory::get_worn(world, crew_member).is_none()
+        && let Some(armor) = inventory::get_inventory(world, crew_member)
+            .into_iter()
+            .find(|&item| {
+                world
+                    .get::<&ItemTypeId>(item)
+                    .ok()
+                    .and_then(|item_type| assets.item_type_map.get(&item_type))
+                    .is_some_and(|data| data.armor.is_some())
+            })
+    {
+        return Some(Intention::Wear(armor));
+    }
+
     let area = world.get::<&Pos>(crew_member).unwrap().get_area();
     if area::is_ship(area, world)
         && world
@@ -247,6 +266,19 @@ pub(crate) fn controlled_character_action(state: &GameState) -> Option<Action> {
     }
 }
 
+/// How much a crew member values wielding a weapon with the given properties.
+/// A ranged weapon is preferred when the crew member carries ammunition for it.
+fn weapon_value(properties: &WeaponProperties, world: &World, crew_member: Entity) -> f32 {
+    let has_ammo = properties.ranged.as_ref().is_some_and(|ranged| {
+        inventory::is_holding(|item_type| *item_type == ranged.ammo, world, crew_member)
+    });
+    if has_ammo {
+        properties.damage_mod + 1.
+    } else {
+        properties.damage_mod
+    }
+}
+
 fn is_wait_requested(world: &World, controlled: Entity) -> bool {
     let area = world.get::<&Pos>(controlled).unwrap().get_area();
     world
@@ -274,6 +306,17 @@ pub fn tick(action_map: &mut HashMap<Entity, Action>, state: &mut GameState, ass
             let action = if let Some(action) = entity_ref.get::<&RepeatingAction>() {
                 buffer.remove_one::<RepeatingAction>(entity);
                 Action::from(*action)
+            } else if let Some(order) = entity_ref.get::<&Order>().map(|order| *order)
+                && let Some(action) = follow_order(
+                    entity_ref,
+                    order,
+                    &state.world,
+                    &mut state.rng,
+                    assets,
+                    &mut buffer,
+                )
+            {
+                action
             } else {
                 pick_action(
                     entity_ref,
@@ -299,6 +342,184 @@ pub fn tick(action_map: &mut HashMap<Entity, Action>, state: &mut GameState, ass
     buffer.run_on(&mut state.world);
 }
 
+enum OrderStep {
+    /// Keep following the order by performing this action.
+    Act(Action),
+    /// The order has nothing to do right now, so the regular behavior is used in the meantime.
+    Idle,
+    /// The order is complete or can no longer be followed, optionally with one last action.
+    Done(Option<Action>),
+}
+
+/// Picks the action for following a crew order given by the player,
+/// and removes the order once it is complete or can no longer be followed.
+fn follow_order(
+    entity_ref: EntityRef,
+    order: Order,
+    world: &World,
+    rng: &mut impl Rng,
+    assets: &GameAssets,
+    buffer: &mut CommandBuffer,
+) -> Option<Action> {
+    let step = match order {
+        Order::Attack(target) => attack_order_step(entity_ref, target, world, rng, assets),
+        Order::Guard(guarded) => guard_order_step(entity_ref, guarded, world, rng, assets),
+        Order::RetreatToShip => retreat_order_step(entity_ref, world),
+        Order::UseMedkitOn(patient) => medkit_order_step(entity_ref, patient, world, assets),
+    };
+    match step {
+        OrderStep::Act(action) => Some(action),
+        OrderStep::Idle => None,
+        OrderStep::Done(action) => {
+            buffer.remove_one::<Order>(entity_ref.entity());
+            if matches!(order, Order::RetreatToShip) {
+                buffer.insert_one(entity_ref.entity(), Waiting { at_ship: true });
+            }
+            action
+        }
+    }
+}
+
+fn attack_order_step(
+    entity_ref: EntityRef,
+    target: Entity,
+    world: &World,
+    rng: &mut impl Rng,
+    assets: &GameAssets,
+) -> OrderStep {
+    if !status::is_alive(target, world) {
+        return OrderStep::Done(None);
+    }
+    let Ok(target_pos) = world.get::<&Pos>(target).map(|pos| *pos) else {
+        return OrderStep::Done(None);
+    };
+    let Some(entity_pos) = entity_ref.get::<&Pos>().map(|pos| *pos) else {
+        return OrderStep::Done(None);
+    };
+
+    if target_pos.is_in(entity_pos.get_area()) {
+        OrderStep::Act(Action::Attack(
+            vec![target],
+            pick_attack_kind(entity_ref, world, rng, assets),
+        ))
+    } else if let Some(path) = unblocked_path_to_area(entity_ref, target_pos.get_area(), world) {
+        OrderStep::Act(Action::EnterDoor(path))
+    } else {
+        OrderStep::Done(None)
+    }
+}
+
+fn guard_order_step(
+    entity_ref: EntityRef,
+    guarded: Entity,
+    world: &World,
+    rng: &mut impl Rng,
+    assets: &GameAssets,
+) -> OrderStep {
+    if !status::is_alive(guarded, world) || !world.satisfies::<&CrewMember>(guarded) {
+        return OrderStep::Done(None);
+    }
+    let (Ok(guarded_pos), Some(entity_pos)) = (
+        world.get::<&Pos>(guarded).map(|pos| *pos),
+        entity_ref.get::<&Pos>().map(|pos| *pos),
+    ) else {
+        return OrderStep::Done(None);
+    };
+
+    if !guarded_pos.is_in(entity_pos.get_area()) {
+        return unblocked_path_to_area(entity_ref, guarded_pos.get_area(), world)
+            .map_or(OrderStep::Idle, |path| {
+                OrderStep::Act(Action::EnterDoor(path))
+            });
+    }
+
+    world
+        .query::<(Entity, &Pos, &Hostile)>()
+        .iter()
+        .filter(|&(foe, foe_pos, hostile)| {
+            foe_pos.is_in(guarded_pos.get_area())
+                && status::is_alive(foe, world)
+                && hostile.aggressive
+        })
+        .min_by_key(|(_, foe_pos, _)| foe_pos.distance_to(guarded_pos))
+        .map_or(OrderStep::Idle, |(foe, _, _)| {
+            OrderStep::Act(Action::Attack(
+                vec![foe],
+                pick_attack_kind(entity_ref, world, rng, assets),
+            ))
+        })
+}
+
+fn retreat_order_step(entity_ref: EntityRef, world: &World) -> OrderStep {
+    let Some(entity_pos) = entity_ref.get::<&Pos>().map(|pos| *pos) else {
+        return OrderStep::Done(None);
+    };
+    if area::is_in_ship(entity_pos, world) {
+        return OrderStep::Done(None);
+    }
+    pathing::find_path_towards(world, entity_pos.get_area(), |area| {
+        area::is_ship(area, world)
+    })
+    .filter(|&path| is_path_unblocked(entity_ref, entity_pos, path, world))
+    .map_or(OrderStep::Idle, |path| {
+        OrderStep::Act(Action::EnterDoor(path))
+    })
+}
+
+fn medkit_order_step(
+    entity_ref: EntityRef,
+    patient: Entity,
+    world: &World,
+    assets: &GameAssets,
+) -> OrderStep {
+    if !world
+        .get::<&status::Health>(patient)
+        .is_ok_and(|health| health.is_alive() && health.is_hurt())
+    {
+        return OrderStep::Done(None);
+    }
+    let Some(item) = inventory::find_held_medkit(world, entity_ref.entity(), assets) else {
+        return OrderStep::Done(None);
+    };
+    let (Ok(patient_pos), Some(entity_pos)) = (
+        world.get::<&Pos>(patient).map(|pos| *pos),
+        entity_ref.get::<&Pos>().map(|pos| *pos),
+    ) else {
+        return OrderStep::Done(None);
+    };
+
+    if patient_pos.is_in(entity_pos.get_area()) {
+        OrderStep::Done(Some(
+            TreatAction {
+                item,
+                patient,
+                use_time: 0,
+            }
+            .into(),
+        ))
+    } else if let Some(path) = unblocked_path_to_area(entity_ref, patient_pos.get_area(), world) {
+        OrderStep::Act(Action::EnterDoor(path))
+    } else {
+        OrderStep::Done(None)
+    }
+}
+
+fn unblocked_path_to_area(
+    entity_ref: EntityRef,
+    destination: Entity,
+    world: &World,
+) -> Option<Entity> {
+    let entity_pos = *entity_ref.get::<&Pos>()?;
+    pathing::find_path_towards(world, entity_pos.get_area(), |area| area == destination)
+        .filter(|&path| is_path_unblocked(entity_ref, entity_pos, path, world))
+}
+
+fn is_path_unblocked(entity_ref: EntityRef, entity_pos: Pos, path: Entity, world: &World) -> bool {
+    world.get::<&Pos>(path).is_ok_and(|path_pos| {
+        position::check_is_blocked(world, entity_ref, entity_pos, *path_pos).is_ok()
+    })
+}
+
 fn pick_action(
     entity_ref: EntityRef,
     world: &World,
@@ -347,14 +568,8 @@ fn pick_foe_action(
     if hostile.aggressive {
         let area = entity_ref.get::<&Pos>()?.get_area();
 
-        let targets = world
-            .query::<(Entity, &Pos)>()
-            .with::<&CrewMember>()
-            .iter()
-            .filter(|&(crew, crew_pos)| crew_pos.is_in(area) && status::is_alive(crew, world))
-            .map(|(entity, _)| entity)
-            .collect::<Vec<_>>();
-        if !targets.is_empty() {
+        let targets = find_crew_targets(area, world);
+        if !targets.is_empty() && is_willing_to_engage(entity_ref, &targets, world, assets) {
             return Some(Action::Attack(
                 targets,
                 pick_attack_kind(entity_ref, world, rng, assets),
@@ -362,6 +577,10 @@ fn pick_foe_action(
         }
     }
 
+    if let Some(action) = pick_profile_action(entity_ref, world, rng, assets) {
+        return Some(action);
+    }
+
     if let Some(wandering) = entity_ref.get::<&Wandering>() {
         let area = entity_ref.get::<&Pos>()?.get_area();
 
@@ -393,6 +612,157 @@ fn pick_foe_action(
     None
 }
 
+fn find_crew_targets(area: Entity, world: &World) -> Vec<Entity> {
+    world
+        .query::<(Entity, &Pos)>()
+        .with::<&CrewMember>()
+        .iter()
+        .filter(|&(crew, crew_pos)| crew_pos.is_in(area) && status::is_alive(crew, world))
+        .map(|(entity, _)| entity)
+        .collect()
+}
+
+/// Checks if an aggressive creature is holding back from attacking the crew in its area because of its behavior profile.
+pub(crate) fn is_holding_back(entity_ref: EntityRef, world: &World, assets: &GameAssets) -> bool {
+    let Some(area) = entity_ref.get::<&Pos>().map(|pos| pos.get_area()) else {
+        return false;
+    };
+    let targets = find_crew_targets(area, world);
+    !targets.is_empty() && !is_willing_to_engage(entity_ref, &targets, world, assets)
+}
+
+fn behavior_profile<'a>(entity_ref: EntityRef, assets: &'a GameAssets) -> &'a BehaviorProfile {
+    static STANDARD: BehaviorProfile = BehaviorProfile::Standard;
+    entity_ref
+        .get::<&SpeciesId>()
+        .and_then(|species_id| assets.species_data_map.get(&species_id))
+        .map_or(&STANDARD, |species_data| &species_data.behavior)
+}
+
+/// Checks if the behavior profile of an aggressive creature lets it attack the given targets.
+fn is_willing_to_engage(
+    entity_ref: EntityRef,
+    targets: &[Entity],
+    world: &World,
+    assets: &GameAssets,
+) -> bool {
+    if entity_ref
+        .get::<&status::Health>()
+        .is_some_and(|health| health.is_hurt())
+    {
+        return true;
+    }
+    let Some(pos) = entity_ref.get::<&Pos>().map(|pos| *pos) else {
+        return false;
+    };
+
+    match behavior_profile(entity_ref, assets) {
+        BehaviorProfile::Standard | BehaviorProfile::Scavenger => true,
+        &BehaviorProfile::Ambush { strike_distance } => targets.iter().any(|&target| {
+            world
+                .get::<&Pos>(target)
+                .is_ok_and(|target_pos| pos.distance_to(*target_pos) <= strike_distance)
+        }),
+        BehaviorProfile::Territorial { area_tag } => has_area_tag(pos.get_area(), area_tag, world),
+        &BehaviorProfile::PackHunter { pack_size } => {
+            count_pack_members(entity_ref, pos.get_area(), world) >= usize::from(pack_size)
+        }
+    }
+}
+
+/// Picks what a hostile creature does when it is not attacking, based on its behavior profile.
+fn pick_profile_action(
+    entity_ref: EntityRef,
+    world: &World,
+    rng: &mut impl Rng,
+    assets: &GameAssets,
+) -> Option<Action> {
+    let pos = *entity_ref.get::<&Pos>()?;
+    let area = pos.get_area();
+
+    match behavior_profile(entity_ref, assets) {
+        BehaviorProfile::Standard => None,
+        BehaviorProfile::Ambush { .. } => Some(Action::Wait),
+        BehaviorProfile::Territorial { area_tag } => {
+            if has_area_tag(area, area_tag, world) {
+                return None;
+            }
+            pathing::find_path_towards(world, area, |area| has_area_tag(area, area_tag, world))
+                .filter(|&path| {
+                    position::check_is_blocked(
+                        world,
+                        entity_ref,
+                        pos,
+                        *world.get::<&Pos>(path).unwrap(),
+                    )
+                    .is_ok()
+                })
+                .map(Action::EnterDoor)
+        }
+        BehaviorProfile::Scavenger => {
+            if inventory::get_inventory(world, entity_ref.entity()).is_empty()
+                && let Some(item) = find_dropped_items(world, area).first().copied()
+            {
+                return Some(Action::TakeItem(item, NameData::find(world, item, assets)));
+            }
+            if let Some(&corpse) = find_corpses(world, area).first() {
+                return Some(Action::Examine(corpse));
+            }
+            pathing::find_random_unblocked_path(entity_ref, world, rng, |destination_area| {
+                !find_corpses(world, destination_area).is_empty()
+            })
+            .map(Action::EnterDoor)
+        }
+        &BehaviorProfile::PackHunter { pack_size } => {
+            if count_pack_members(entity_ref, area, world) >= usize::from(pack_size) {
+                return None;
+            }
+            pathing::find_random_unblocked_path(entity_ref, world, rng, |destination_area| {
+                count_pack_members(entity_ref, destination_area, world) > 0
+            })
+            .map(Action::EnterDoor)
+        }
+    }
+}
+
+fn has_area_tag(area: Entity, area_tag: &Tag, world: &World) -> bool {
+    world.get::<&Tag>(area).is_ok_and(|tag| &*tag == area_tag)
+}
+
+/// Counts the living hostile creatures in the area with the same species as the given creature, including itself if it is in the area.
+fn count_pack_members(entity_ref: EntityRef, area: Entity, world: &World) -> usize {
+    let Some(species) = entity_ref.get::<&SpeciesId>() else {
+        return 0;
+    };
+    world
+        .query::<(Entity, &Pos, &SpeciesId)>()
+        .with::<&Hostile>()
+        .iter()
+        .filter(|&(entity, pos, other_species)| {
+            pos.is_in(area) && *other_species == *species && status::is_alive(entity, world)
+        })
+        .count()
+}
+
+fn find_dropped_items(world: &World, area: Entity) -> Vec<Entity> {
+    world
+        .query::<(Entity, &Pos)>()
+        .with::<&ItemTypeId>()
+        .iter()
+        .filter(|&(_, pos)| pos.is_in(area))
+        .map(|(item, _)| item)
+        .collect()
+}
+
+fn find_corpses(world: &World, area: Entity) -> Vec<Entity> {
+    world
+        .query::<(Entity, &Pos, &status::Health)>()
+        .iter()
+        .filter(|&(_, pos, health)| pos.is_in(area) && health.is_dead())
+        .map(|(corpse, _, _)| corpse)
+        .collect()
+}
+
 fn pick_crew_action(
     entity_ref: EntityRef,
     world: &World,
@@ -473,6 +843,9 @@ fn pick_crew_action(
             Intention::Wield(item) => {
                 return Some(Action::Wield(item, NameData::find(world, item, assets)));
             }
+            Intention::Wear(item) => {
+                return Some(Action::Wear(item, NameData::find(world, item, assets)));
+            }
             Intention::Force { door, assisted } => {
                 return Some(
                     ForceDoorAction {
@@ -1,6 +1,7 @@
 pub mod background;
 pub mod dialogue;
 pub mod location;
+pub mod message;
 pub mod model;
 pub mod placement;
 pub mod profile;
@@ -12,6 +13,7 @@ pub mod color {
     use crate::core::SpeciesId;
     use crate::core::display::SpeciesColorId;
     use crate::core::name::Adjective;
+    use indexmap::IndexMap;
     use serde::{Deserialize, Serialize};
     use std::collections::HashMap;
     use std::fs;
@@ -57,7 +59,7 @@ pub mod color {
     pub const SPECIES_COLOR_DIR: AssetDirectory<HashMap<SpeciesColorId, SpeciesColorEntry>> =
         AssetDirectory::new("species_color");
 
-    pub struct SpeciesColorMap(HashMap<SpeciesId, HashMap<SpeciesColorId, SpeciesColorEntry>>);
+    pub struct SpeciesColorMap(HashMap<SpeciesId, IndexMap<SpeciesColorId, SpeciesColorEntry>>);
 
     impl SpeciesColorMap {
         pub fn load() -> Result<Self, Error> {
@@ -70,7 +72,7 @@ pub mod color {
                     && let [file_name, "json"] = file_name.split('.').collect::<Vec<_>>()[..]
                 {
                     let species_id = SpeciesId::from(file_name);
-                    let species_colors = SPECIES_COLOR_DIR.load(file_name)?;
+                    let species_colors = SPECIES_COLOR_DIR.load_index_map(file_name)?;
                     map.insert(species_id, species_colors);
                 }
             }
@@ -91,7 +93,7 @@ pub mod color {
         ) -> impl Iterator<Item = &SpeciesColorId> {
             self.0
                 .get(species_id)
-                .map(HashMap::keys)
+                .map(IndexMap::keys)
                 .unwrap_or_default()
         }
     }
@@ -182,8 +184,10 @@ pub mod loot {
     }
 }
 
-use crate::core::combat::WeaponProperties;
+use crate::core::combat::{ArmorProperties, WeaponProperties};
 use crate::core::display::SpeciesColorId;
+use crate::core::effect::{self, EffectApplication, StatusEffectData, StatusEffectKind};
+use crate::core::hazard::HazardKind;
 use crate::core::item::{ItemTypeId, Price};
 use crate::core::name::{NounData, NounId};
 use crate::core::status::StatChanges;
@@ -196,6 +200,7 @@ use std::fs::File;
 use std::hash::Hash;
 use std::marker::PhantomData;
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
 #[derive(Debug)]
 pub enum Error {
@@ -226,6 +231,36 @@ impl Display for Error {
     }
 }
 
+static LANGUAGE: OnceLock<String> = OnceLock::new();
+
+/// Selects the language whose asset overrides in `assets/lang/<language>/` are used when loading assets.
+/// Should be called before any assets are loaded, and can only be called once.
+pub fn select_language(language: &str) -> Result<(), Error> {
+    let language_path = PathBuf::from(format!("assets/lang/{language}"));
+    if !language_path.is_dir() {
+        return Err(Error::Validation(format!(
+            "There are no assets for the language \"{language}\""
+        )));
+    }
+    LANGUAGE
+        .set(language.to_owned())
+        .map_err(|_| Error::Validation("The language has already been selected".to_owned()))
+}
+
+fn language_override_path(path: &str) -> Option<PathBuf> {
+    override_path_for_language(LANGUAGE.get()?, path)
+}
+
+fn override_path_for_language(language: &str, path: &str) -> Option<PathBuf> {
+    let override_path = PathBuf::from(format!("assets/lang/{language}/{path}"));
+    override_path.exists().then_some(override_path)
+}
+
+/// The path to load an asset from, which is the override for the selected language if there is one.
+fn resolve_asset_path(path: &str) -> PathBuf {
+    language_override_path(path).unwrap_or_else(|| format!("assets/{path}").into())
+}
+
 pub trait TextureLoader<T, E> {
     fn load_texture(&mut self, name: String) -> Result<T, E>;
 }
@@ -258,14 +293,33 @@ impl<T: DeserializeOwned> AssetFile<T> {
         format!("assets/{}", self.path).into()
     }
     pub fn load(&self) -> Result<T, Error> {
-        load_from_json::<T>(format!("assets/{}", self.path))
+        load_from_json::<T>(resolve_asset_path(self.path))
     }
 }
 
 impl<K: Eq + Hash + DeserializeOwned, V: DeserializeOwned> AssetFile<HashMap<K, V>> {
     /// Loads asset as order-preserved map.
     pub fn load_index_map(&self) -> Result<IndexMap<K, V>, Error> {
-        load_from_json::<IndexMap<K, V>>(format!("assets/{}", self.path))
+        load_from_json::<IndexMap<K, V>>(resolve_asset_path(self.path))
+    }
+
+    /// Loads the asset map, and then replaces entries with those from the override for the selected language.
+    /// Unlike with `load()`, the language override only needs to contain the entries that differ.
+    pub fn load_with_language_entries(&self) -> Result<HashMap<K, V>, Error> {
+        self.load_with_entries_for_language(LANGUAGE.get().map(String::as_str))
+    }
+
+    fn load_with_entries_for_language(
+        &self,
+        language: Option<&str>,
+    ) -> Result<HashMap<K, V>, Error> {
+        let mut map = load_from_json::<HashMap<K, V>>(self.file_path())?;
+        if let Some(override_path) =
+            language.and_then(|language| override_path_for_language(language, self.path))
+        {
+            map.extend(load_from_json::<HashMap<K, V>>(override_path)?);
+        }
+        Ok(map)
     }
 }
 
@@ -288,14 +342,14 @@ impl<T: DeserializeOwned> AssetDirectory<T> {
         format!("assets/{}/{id}.json", self.path).into()
     }
     pub fn load(&self, id: impl Display) -> Result<T, Error> {
-        load_from_json::<T>(format!("assets/{}/{id}.json", self.path))
+        load_from_json::<T>(resolve_asset_path(&format!("{}/{id}.json", self.path)))
     }
 }
 
 impl<K: Eq + Hash + DeserializeOwned, V: DeserializeOwned> AssetDirectory<HashMap<K, V>> {
     /// Loads asset as order-preserved map.
     pub fn load_index_map(&self, id: impl Display) -> Result<IndexMap<K, V>, Error> {
-        load_from_json::<IndexMap<K, V>>(format!("assets/{}/{id}.json", self.path))
+        load_from_json::<IndexMap<K, V>>(resolve_asset_path(&format!("{}/{id}.json", self.path)))
     }
 }
 
@@ -312,10 +366,12 @@ pub(crate) struct NounDataMap {
 
 impl NounDataMap {
     pub(crate) fn load() -> Result<Self, Error> {
-        NOUN_DATA_FILE.load().map(|map| NounDataMap {
-            map,
-            fallback: NounData::default(),
-        })
+        NOUN_DATA_FILE
+            .load_with_language_entries()
+            .map(|map| NounDataMap {
+                map,
+                fallback: NounData::default(),
+            })
     }
 
     pub(crate) fn lookup(&self, noun_id: &NounId) -> &NounData {
@@ -336,14 +392,33 @@ pub(crate) enum ItemUseType {
     OddHandMirror {
         sum_change: i16,
     },
+    /// Applies status effects to the user, and ends any of the cured effects.
+    Consumable {
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        effects: Vec<EffectApplication>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        cures: Vec<StatusEffectKind>,
+    },
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ItemTypeData {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub(crate) weapon: Option<WeaponProperties>,
+    /// Armor is worn separately from the wielded weapon.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) armor: Option<ArmorProperties>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub(crate) usage: Option<ItemUseType>,
+    /// Light sources let their holder see in dark areas.
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub(crate) light_source: bool,
+    /// Key items unlock locked doors that have this key tag.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) key_tag: Option<String>,
+    /// Protects whoever carries the item from these kinds of hazards.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) hazard_protection: Vec<HazardKind>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub(crate) price: Option<Price>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -371,6 +446,8 @@ pub struct GameAssets {
     pub(crate) species_data_map: species::SpeciesDataMap,
     pub(crate) color_map: color::SpeciesColorMap,
     pub(crate) item_type_map: HashMap<ItemTypeId, ItemTypeData>,
+    pub(crate) status_effect_map: HashMap<StatusEffectKind, StatusEffectData>,
+    pub(crate) messages: message::MessageCatalog,
 }
 
 impl GameAssets {
@@ -380,6 +457,37 @@ impl GameAssets {
             species_data_map: species::load_species_map()?,
             color_map: color::SpeciesColorMap::load()?,
             item_type_map: ITEM_TYPES_FILE.load()?,
+            status_effect_map: effect::STATUS_EFFECTS_FILE.load()?,
+            messages: message::MessageCatalog::load()?,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::message::MESSAGES_FILE;
+    use super::{NOUN_DATA_FILE, NounId};
+
+    #[test]
+    fn language_entries_replace_base_entries() {
+        let messages = MESSAGES_FILE
+            .load_with_entries_for_language(Some("test"))
+            .unwrap();
+        assert_eq!(
+            messages["door.force.crowbar"],
+            "{performer} hat die Tür mit dem Brecheisen aufgebrochen."
+        );
+        assert_eq!(
+            messages["door.force.blowtorch"],
+            "{performer} used their blowtorch and cut open the door."
+        );
+
+        let nouns = NOUN_DATA_FILE
+            .load_with_entries_for_language(Some("test"))
+            .unwrap();
+        let crowbar = &nouns[&NounId::from("crowbar")];
+        assert_eq!(crowbar.singular(), "Brecheisen");
+        assert_eq!(crowbar.definite_article(), "das");
+        assert_eq!(nouns[&NounId::from("knife")].definite_article(), "the");
+    }
+}
@@ -50,6 +50,9 @@ pub mod creature {
         pub wandering: Option<Wandering>,
         #[serde(default, skip_serializing_if = "Option::is_none")]
         pub tag: Option<Tag>,
+        /// An item carried by the creature, which is dropped when it dies.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub held_item: Option<ItemTypeId>,
         #[serde(default, skip_serializing_if = "Option::is_none")]
         pub direction: Option<Direction>,
     }
@@ -176,6 +179,7 @@ use crate::asset::loot::{self, LootTableId};
 use crate::asset::{AssetDirectory, AssetFile};
 use crate::core::area::BackgroundId;
 use crate::core::display::ModelId;
+use crate::core::hazard::Hazard;
 use crate::core::item::ItemTypeId;
 use crate::core::name::NounId;
 use crate::core::position::Direction;
@@ -219,6 +223,8 @@ pub struct AreaData {
     pub extra_background_layers: Vec<ParallaxLayer<String>>,
     #[serde(default, skip_serializing_if = "crate::is_default")]
     pub darkness: f32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hazards: Vec<Hazard>,
     pub objects: Vec<String>,
     #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
     pub variant_objects: IndexMap<String, Vec<String>>,
@@ -279,6 +285,9 @@ pub enum SymbolData {
 pub struct DoorPairData {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub block_type: Option<BlockType>,
+    /// The item type id or key tag of the key that unlocks a locked door pair.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
     #[serde(default, skip_serializing_if = "crate::is_default")]
     pub is_cut: bool,
 }
@@ -450,3 +459,93 @@ pub struct FurnishTemplate {
 }
 
 pub const FURNISH_DIR: AssetDirectory<Vec<FurnishTemplate>> = AssetDirectory::new("area_furnish");
+
+/// Rules for assembling a location from randomized parts, instead of from a fixed layout.
+/// A location name that has a generator file is generated from it, rather than loaded from a location file.
+#[derive(Serialize, Deserialize)]
+pub struct LocationGeneratorData {
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub is_society: bool,
+    pub area_count: CountRange,
+    pub area_types: Vec<GeneratedAreaType>,
+    pub connections: ConnectionRules,
+    /// Symbols that are always placed, each in a random area other than the entry area.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fixed_symbols: Vec<SymbolData>,
+    #[serde(default, skip_serializing_if = "Budget::is_empty")]
+    pub creatures: Budget,
+    #[serde(default, skip_serializing_if = "Budget::is_empty")]
+    pub loot: Budget,
+}
+
+pub const LOCATION_GENERATOR_DIR: AssetDirectory<LocationGeneratorData> =
+    AssetDirectory::new("location_generator");
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct CountRange {
+    pub min: u16,
+    pub max: u16,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GeneratedAreaType {
+    pub name: String,
+    pub weight: u32,
+    pub sizes: Vec<GeneratedAreaSize>,
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub darkness: f32,
+    /// Furnish templates of which one is picked at random to be placed in the area.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub furnish: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GeneratedAreaSize {
+    pub size: u16,
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub background: BackgroundId,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ConnectionRules {
+    /// The number of connections to add on top of those needed to connect all areas.
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub extra_connections: u16,
+    pub door_types: Vec<WeightedDoorType>,
+    /// Chances for a connection to be blocked, which are checked in order.
+    /// Connections to the entry area are never blocked.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub block_chances: Vec<BlockChance>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct WeightedDoorType {
+    pub door_type: DoorType,
+    pub weight: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BlockChance {
+    pub block_type: BlockType,
+    pub chance: f32,
+}
+
+/// Symbols that are picked at random and placed until their total cost reaches the budget.
+#[derive(Default, Serialize, Deserialize)]
+pub struct Budget {
+    pub points: u16,
+    pub entries: Vec<BudgetEntry>,
+}
+
+impl Budget {
+    fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BudgetEntry {
+    pub cost: u16,
+    pub weight: u32,
+    pub symbol: SymbolData,
+}
@@ -0,0 +1,77 @@
+use super::{AssetFile, Error};
+use std::collections::HashMap;
+
+pub const MESSAGES_FILE: AssetFile<HashMap<String, String>> = AssetFile::new("messages.json");
+
+/// Message texts generated by the game engine, keyed by message id.
+/// Placeholders in a message are written as `{key}`, in the same way as for dialogue text.
+pub struct MessageCatalog(HashMap<String, String>);
+
+impl MessageCatalog {
+    pub fn load() -> Result<Self, Error> {
+        MESSAGES_FILE.load_with_language_entries().map(Self)
+    }
+
+    /// Looks up the message with the given id, and fills in its placeholders with the given values.
+    pub fn text(&self, id: &str, values: &[(&str, &str)]) -> String {
+        let Some(message) = self.0.get(id) else {
+            eprintln!("Missing message: \"{id}\"");
+            return id.to_owned();
+        };
+        resolve_placeholders(message, |key| {
+            values
+                .iter()
+                .find(|(value_key, _)| *value_key == key)
+                .map(|(_, value)| (*value).to_owned())
+        })
+    }
+}
+
+/// Replaces each `{key}` in the text with the value given by the resolver.
+/// Keys that the resolver does not know are replaced with "???".
+pub(crate) fn resolve_placeholders(
+    mut text: &str,
+    resolver: impl Fn(&str) -> Option<String>,
+) -> String {
+    let mut result = String::new();
+    while !text.is_empty() {
+        if let Some(start) = text.find('{')
+            && let Some(length) = text[start..].find('}')
+        {
+            result.push_str(&text[..start]);
+
+            let key = &text[(start + 1)..(start + length)];
+            if let Some(value) = resolver(key) {
+                result.push_str(&value);
+            } else {
+                eprintln!("Unknown text key: \"{key}\"");
+                result.push_str("???");
+            }
+
+            text = text.split_at(start + length + 1).1;
+        } else {
+            result.push_str(text);
+            break;
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MessageCatalog;
+
+    #[test]
+    fn text_fills_in_placeholders() {
+        let messages = MessageCatalog::load().unwrap();
+        assert_eq!(
+            messages.text("door.force.crowbar", &[("performer", "Mint")]),
+            "Mint used their crowbar and forced open the door."
+        );
+        assert_eq!(
+            messages.text("door.force.crowbar", &[]),
+            "??? used their crowbar and forced open the door."
+        );
+        assert_eq!(messages.text("unknown.message", &[]), "unknown.message");
+    }
+}
@@ -2,9 +2,9 @@ use crate::asset::color::SpeciesColorMap;
 use crate::core::SpeciesId;
 use crate::core::display::SpeciesColorId;
 use crate::core::status::{Stats, Traits};
+use indexmap::IndexMap;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -75,7 +75,7 @@ impl ProfileOrRandom {
     pub(crate) fn unwrap<'a>(
         self,
         character_names: &mut Vec<String>,
-        aftik_color_names: &mut HashMap<SpeciesColorId, Vec<String>>,
+        aftik_color_names: &mut IndexMap<SpeciesColorId, Vec<String>>,
         color_map: &SpeciesColorMap,
         rng: &mut impl Rng,
         query_used_colors: impl FnOnce(&SpeciesId) -> Vec<&'a SpeciesColorId>,
@@ -106,7 +106,7 @@ pub(crate) fn random_profile(
     stats: StatsOrRandom,
     used_colors: &[&SpeciesColorId],
     character_names: &mut Vec<String>,
-    aftik_color_names: &mut HashMap<SpeciesColorId, Vec<String>>,
+    aftik_color_names: &mut IndexMap<SpeciesColorId, Vec<String>>,
     color_map: &SpeciesColorMap,
     rng: &mut impl Rng,
 ) -> Option<CharacterProfile> {
@@ -140,7 +140,7 @@ pub(crate) fn random_profile(
 }
 
 pub(crate) fn random_aftik_profile(
-    aftik_color_names: &mut HashMap<SpeciesColorId, Vec<String>>,
+    aftik_color_names: &mut IndexMap<SpeciesColorId, Vec<String>>,
     rng: &mut impl Rng,
     used_aftik_colors: &[&SpeciesColorId],
 ) -> Option<(String, SpeciesColorId)> {
@@ -1,8 +1,9 @@
 use crate::asset::AssetFile;
 use crate::core::SpeciesId;
-use crate::core::behavior::BadlyHurtBehavior;
+use crate::core::behavior::{BadlyHurtBehavior, BehaviorProfile};
 use crate::core::combat::{AttackSet, UnarmedType, WeaponProperties};
 use crate::core::display::CreatureVariant;
+use crate::core::effect::EffectApplication;
 use crate::core::status::Stats;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -29,7 +30,9 @@ pub struct SpeciesData {
     pub is_large: bool,
     pub unarmed: UnarmedType,
     pub attack_set: AttackSet,
+    pub unarmed_effects: Vec<EffectApplication>,
     pub badly_hurt_behavior: Option<BadlyHurtBehavior>,
+    pub behavior: BehaviorProfile,
     pub variant_groups: Vec<Vec<WeightedVariant>>,
 }
 
@@ -39,6 +42,8 @@ impl SpeciesData {
             damage_mod: 2.0,
             attack_set: self.attack_set,
             stun_attack: false,
+            ranged: None,
+            effects: self.unarmed_effects.clone(),
         }
     }
 }
@@ -72,7 +77,9 @@ impl From<CharacterSpeciesData> for SpeciesData {
             is_large,
             unarmed,
             attack_set,
+            unarmed_effects: Vec::new(),
             badly_hurt_behavior,
+            behavior: BehaviorProfile::Standard,
             variant_groups,
         }
     }
@@ -85,9 +92,14 @@ pub struct FaunaData {
     is_large: bool,
     unarmed: UnarmedType,
     attack_set: AttackSet,
+    /// Status effects that an unarmed hit by the creature may apply to the target.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    unarmed_effects: Vec<EffectApplication>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     badly_hurt_behavior: Option<BadlyHurtBehavior>,
     #[serde(default, skip_serializing_if = "crate::is_default")]
+    behavior: BehaviorProfile,
+    #[serde(default, skip_serializing_if = "crate::is_default")]
     agressive_by_default: bool,
     #[serde(default, skip_serializing_if = "crate::is_default")]
     tameable: bool,
@@ -102,7 +114,9 @@ impl From<FaunaData> for SpeciesData {
             is_large,
             unarmed,
             attack_set,
+            unarmed_effects,
             badly_hurt_behavior,
+            behavior,
             agressive_by_default,
             tameable,
             variant_groups,
@@ -116,7 +130,9 @@ impl From<FaunaData> for SpeciesData {
             is_large,
             unarmed,
             attack_set,
+            unarmed_effects,
             badly_hurt_behavior,
+            behavior,
             variant_groups,
         }
     }
@@ -0,0 +1,462 @@
+use aftiktuna::asset;
+use aftiktuna::core::CREW_SIZE_LIMIT;
+use aftiktuna::game_interface::{self, Game, GameResult};
+use aftiktuna::replay;
+use aftiktuna::run_config::{self, RunConfig};
+use aftiktuna::serialization::{self, LoadError};
+use aftiktuna::view::area::RenderData;
+use aftiktuna::view::{Frame, StoreView, text};
+use std::env;
+use std::io::{self, BufRead, Write};
+
+fn main() {
+    let disable_autosave = env::args().any(|arg| arg.eq("--disable-autosave"));
+    let new_game = env::args().any(|arg| arg.eq("--new-game"));
+    let enable_undo = env::args().any(|arg| arg.eq("--enable-undo"));
+    let enable_combat_log = env::args().any(|arg| arg.eq("--combat-log"));
+    let seed = match env::args()
+        .find_map(|arg| arg.strip_prefix("--seed=").map(str::to_owned))
+        .map(|seed| seed.parse::<u64>())
+        .transpose()
+    {
+        Ok(seed) => seed,
+        Err(error) => {
+            eprintln!("Invalid seed: {error}");
+            return;
+        }
+    };
+    if let Some(language) =
+        env::args().find_map(|arg| arg.strip_prefix("--language=").map(str::to_owned))
+        && let Err(error) = asset::select_language(&language)
+    {
+        eprintln!("{error}");
+        return;
+    }
+    let record_path = env::args().find_map(|arg| arg.strip_prefix("--record=").map(str::to_owned));
+    let slot = env::args().find_map(|arg| arg.strip_prefix("--slot=").map(str::to_owned));
+    if let Some(slot) = &slot
+        && !serialization::is_valid_slot_name(slot)
+    {
+        eprintln!(
+            "Invalid save slot name \"{slot}\". Only letters, digits, '-' and '_' may be used."
+        );
+        return;
+    }
+    if disable_autosave {
+        println!("Running without autosave");
+    }
+
+    let mut input_lines = io::stdin().lock().lines();
+
+    let slot_to_load = if new_game {
+        None
+    } else if let Some(slot) = &slot {
+        serialization::save_slot_path(slot)
+            .exists()
+            .then(|| slot.clone())
+    } else {
+        pick_save_slot(&mut input_lines)
+    };
+    if slot_to_load.is_some() && record_path.is_some() {
+        println!("Only new games can be recorded. Continuing without recording.");
+    }
+    let game = if let Some(slot) = &slot_to_load {
+        game_interface::load(slot).map_err(|error| {
+            let recommendation = if matches!(error, LoadError::UnsupportedVersion(_, _)) {
+                "Consider starting a new game or using a different version of Aftiktuna."
+            } else {
+                "Consider starting a new game."
+            };
+            format!("Unable to load save file: {error}\n{recommendation}")
+        })
+    } else {
+        let run_config = if new_game {
+            RunConfig::default()
+        } else {
+            configure_run(&mut input_lines)
+        };
+        match &record_path {
+            Some(_) => game_interface::setup_recorded(replay::Start::NewGame, run_config, seed),
+            None => game_interface::setup_new(run_config, seed),
+        }
+        .inspect(|game| {
+            if let Some(seed) = game.seed() {
+                println!("Starting new game with seed {seed}");
+            }
+        })
+    };
+    let mut game = match game {
+        Ok(game) => game,
+        Err(error) => {
+            eprintln!("{error}");
+            return;
+        }
+    };
+
+    if enable_undo {
+        game.enable_undo();
+    }
+    if enable_combat_log {
+        game.enable_combat_log();
+    }
+    let save_slot = if disable_autosave {
+        None
+    } else {
+        game.enable_autosave();
+        Some(
+            slot_to_load
+                .or(slot)
+                .unwrap_or_else(serialization::new_slot_name),
+        )
+    };
+    let game = run_game(game, save_slot.as_deref(), &mut input_lines);
+
+    if let Some(path) = record_path
+        && game.replay().is_some()
+    {
+        match replay::write_replay_file(&game, &path) {
+            Ok(()) => println!("Wrote replay to \"{path}\"."),
+            Err(error) => eprintln!("Failed to write replay: {error}"),
+        }
+    }
+}
+
+fn ask_yes_no(question: &str, input_lines: &mut impl Iterator<Item = io::Result<String>>) -> bool {
+    loop {
+        print!("{question} (yes/no) > ");
+        let _ = io::stdout().flush();
+        let Some(Ok(input)) = input_lines.next() else {
+            return false;
+        };
+        match input.trim().to_lowercase().as_str() {
+            "yes" | "y" => return true,
+            "no" | "n" => return false,
+            _ => {}
+        }
+    }
+}
+
+/// Lets the player adjust the settings for a new run. Empty answers keep the default settings.
+fn configure_run(input_lines: &mut impl Iterator<Item = io::Result<String>>) -> RunConfig {
+    let mut run_config = RunConfig::default();
+    if !ask_yes_no("Customize the new run?", input_lines) {
+        return run_config;
+    }
+
+    if let Some(count) = ask_value(
+        &format!(
+            "Number of locations before Fortuna [{}]",
+            run_config.locations_before_fortuna
+        ),
+        |input| input.parse::<i32>().ok().filter(|&count| count >= 1),
+        input_lines,
+    ) {
+        run_config.locations_before_fortuna = count;
+    }
+    if let Some(difficulty) = ask_value(
+        &format!(
+            "Difficulty, from {} (easier) to {} (harder) [{}]",
+            RunConfig::MIN_DIFFICULTY,
+            RunConfig::MAX_DIFFICULTY,
+            run_config.difficulty
+        ),
+        |input| {
+            input.parse::<f32>().ok().filter(|difficulty| {
+                (RunConfig::MIN_DIFFICULTY..=RunConfig::MAX_DIFFICULTY).contains(difficulty)
+            })
+        },
+        input_lines,
+    ) {
+        run_config.difficulty = difficulty;
+    }
+    run_config.starting_points = ask_value(
+        "Starting points [from starting crew]",
+        |input| input.parse::<i32>().ok().filter(|&points| points >= 0),
+        input_lines,
+    );
+
+    let species = run_config::selectable_crew_species().unwrap_or_else(|error| {
+        eprintln!("Unable to load species: {error}");
+        Vec::new()
+    });
+    if !species.is_empty() {
+        let species_list = species
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        run_config.crew_species = ask_value(
+            &format!(
+                "Crew species separated by spaces, with up to {CREW_SIZE_LIMIT} members ({species_list}) [from starting crew]"
+            ),
+            |input| {
+                let crew_species = input
+                    .split_whitespace()
+                    .map(|name| species.iter().find(|species| species.to_string() == name))
+                    .collect::<Option<Vec<_>>>()?;
+                (crew_species.len() <= CREW_SIZE_LIMIT)
+                    .then(|| crew_species.into_iter().cloned().collect())
+            },
+            input_lines,
+        );
+    }
+    run_config
+}
+
+/// Asks for a value until the answer is either empty or accepted by the parser.
+fn ask_value<T>(
+    question: &str,
+    parse: impl Fn(&str) -> Option<T>,
+    input_lines: &mut impl Iterator<Item = io::Result<String>>,
+) -> Option<T> {
+    loop {
+        print!("{question} > ");
+        let _ = io::stdout().flush();
+        let Some(Ok(input)) = input_lines.next() else {
+            return None;
+        };
+        let input = input.trim();
+        if input.is_empty() {
+            return None;
+        }
+        if let Some(value) = parse(input) {
+            return Some(value);
+        }
+    }
+}
+
+/// Lists the existing save slots and lets the player pick one to load.
+/// Returns `None` if there are no saves or if the player chooses to start a new game.
+fn pick_save_slot(input_lines: &mut impl Iterator<Item = io::Result<String>>) -> Option<String> {
+    let slots = match serialization::list_save_slots() {
+        Ok(slots) => slots,
+        Err(error) => {
+            eprintln!("Unable to list saved games: {error}");
+            return None;
+        }
+    };
+    if slots.is_empty() {
+        return None;
+    }
+
+    println!("Saved games:");
+    for (index, slot) in slots.iter().enumerate() {
+        match &slot.metadata {
+            Some(metadata) => println!("{}. {}: {}", index + 1, slot.name, metadata.summary()),
+            None => println!("{}. {}", index + 1, slot.name),
+        }
+    }
+    loop {
+        print!("Pick a saved game to load by number, or leave empty for a new game > ");
+        let _ = io::stdout().flush();
+        let Some(Ok(input)) = input_lines.next() else {
+            return None;
+        };
+        let input = input.trim();
+        if input.is_empty() {
+            return None;
+        }
+        if let Some(slot) = input
+            .parse::<usize>()
+            .ok()
+            .and_then(|number| slots.get(number.wrapping_sub(1)))
+        {
+            return Some(slot.name.clone());
+        }
+    }
+}
+
+fn run_game(
+    mut game: Game,
+    save_slot: Option<&str>,
+    input_lines: &mut impl Iterator<Item = io::Result<String>>,
+) -> Game {
+    loop {
+        match game.next_result() {
+            GameResult::Frame(frame_getter) => {
+                let frame = frame_getter.get();
+                print_frame(&frame);
+                if matches!(frame, Frame::Ending { .. })
+                    && let Some(slot) = save_slot
+                {
+                    serialization::delete_saves_of_finished_run(slot);
+                    return game;
+                }
+            }
+            GameResult::Input => {
+                print!("> ");
+                let _ = io::stdout().flush();
+                let Some(Ok(input)) = input_lines.next() else {
+                    save_game_if_enabled(&game, save_slot);
+                    return game;
+                };
+                let input = input.trim();
+                if input.eq_ignore_ascii_case("exit game") {
+                    save_game_if_enabled(&game, save_slot);
+                    return game;
+                }
+                if input.eq_ignore_ascii_case("undo") {
+                    if !game.undo() {
+                        println!("There is no turn to undo.");
+                    }
+                    continue;
+                }
+                if input.eq_ignore_ascii_case("export combat log") {
+                    match game.export_combat_log() {
+                        Ok(path) => println!("Exported the combat log to \"{path}\"."),
+                        Err(error) => println!("{error}"),
+                    }
+                    continue;
+                }
+
+                if let Err(command_info) = game.handle_input(input) {
+                    for line in command_info.into_text() {
+                        println!("{line}");
+                    }
+                }
+            }
+            GameResult::Stop => return game,
+        }
+    }
+}
+
+fn save_game_if_enabled(game: &Game, save_slot: Option<&str>) {
+    if let Some(slot) = save_slot {
+        if let Err(error) = serialization::write_game_to_save_slot(game, slot) {
+            eprintln!("Failed to save game: {error}");
+        } else {
+            println!("Saved the game to slot \"{slot}\".")
+        }
+    }
+}
+
+fn print_frame(frame: &Frame) {
+    println!();
+    println!("{}", "-".repeat(60));
+    match frame {
+        Frame::AreaView { render_data, .. } => print_area(render_data),
+        Frame::StoreView { view, .. } => print_store(view),
+        Frame::Introduction
+        | Frame::Dialogue { .. }
+        | Frame::LocationChoice(_)
+        | Frame::Map(_)
+        | Frame::Error(_)
+        | Frame::Ending { .. } => {}
+    }
+    for message in frame.get_messages() {
+        println!("{message}");
+    }
+}
+
+fn print_area(render_data: &RenderData) {
+    let mut symbols: Vec<(char, String)> = Vec::new();
+    let mut rows: Vec<Vec<char>> = Vec::new();
+
+    for object in &render_data.objects {
+        let Some(name_data) = &object.name_data else {
+            continue;
+        };
+        let legend_name = if object.is_controlled {
+            format!("{} (controlled)", name_data.modified_name)
+        } else {
+            name_data.modified_name.clone()
+        };
+        let symbol = match symbols.iter().find(|(_, name)| name.eq(&legend_name)) {
+            Some(&(symbol, _)) => symbol,
+            None => {
+                let symbol = pick_symbol(&name_data.name, object.is_controlled, &symbols);
+                symbols.push((symbol, legend_name));
+                symbol
+            }
+        };
+
+        let coord = object.coord as usize;
+        let free_cell = rows
+            .iter_mut()
+            .find(|row| row[coord] == ' ')
+            .map(|row| &mut row[coord]);
+        match free_cell {
+            Some(cell) => *cell = symbol,
+            None => {
+                let mut row = vec![' '; render_data.area_size as usize];
+                row[coord] = symbol;
+                rows.push(row);
+            }
+        }
+    }
+
+    if rows.is_empty() {
+        rows.push(vec![' '; render_data.area_size as usize]);
+    }
+    for row in rows.into_iter().rev() {
+        println!("|{}|", row.into_iter().collect::<String>());
+    }
+    for (hazard, name) in render_data.hazards.iter().zip(&render_data.hazard_names) {
+        let row = (0..render_data.area_size)
+            .map(|coord| if hazard.covers(coord) { '~' } else { ' ' })
+            .collect::<String>();
+        println!("|{row}| {name}");
+    }
+    for (symbol, name) in symbols {
+        println!("{symbol}: {name}");
+    }
+
+    if !render_data.inventory.is_empty() {
+        let items = render_data
+            .inventory
+            .iter()
+            .map(|item| {
+                if item.is_wielded {
+                    format!("{} (wielded)", item.name())
+                } else {
+                    item.name().to_owned()
+                }
+            })
+            .collect::<Vec<_>>();
+        println!("Inventory: {}", items.join(", "));
+    }
+    println!();
+}
+
+/// Picks a symbol from the letters of the name that is not already in use.
+fn pick_symbol(name: &str, is_controlled: bool, used_symbols: &[(char, String)]) -> char {
+    if is_controlled {
+        return '@';
+    }
+    let is_unused = |symbol: &char| used_symbols.iter().all(|(used, _)| used != symbol);
+    let letters = name.chars().filter(char::is_ascii_alphabetic);
+    letters
+        .clone()
+        .map(|letter| letter.to_ascii_lowercase())
+        .chain(letters.map(|letter| letter.to_ascii_uppercase()))
+        .chain('0'..='9')
+        .find(is_unused)
+        .unwrap_or('?')
+}
+
+fn print_store(view: &StoreView) {
+    for stock in &view.items {
+        println!(
+            "{name:<20} | {price:>5}p | {quantity}",
+            name = text::capitalize(stock.item_noun.singular()),
+            price = stock.price.buy_price(),
+            quantity = stock.quantity,
+        );
+    }
+    println!("Crew points: {}p", view.points);
+    if !view.sellable_items.is_empty() {
+        let items = view
+            .sellable_items
+            .iter()
+            .map(|(name, count)| {
+                if *count == 1 {
+                    name.base()
+                } else {
+                    format!("{count} {}", name.plural())
+                }
+            })
+            .collect::<Vec<_>>();
+        println!("Sellable items: {}", items.join(", "));
+    }
+    println!();
+}
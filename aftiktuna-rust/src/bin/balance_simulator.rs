@@ -0,0 +1,193 @@
+use aftiktuna::asset::GameAssets;
+use aftiktuna::core::SpeciesId;
+use aftiktuna::run_config::RunConfig;
+use aftiktuna::simulation::{self, FightConfig, FightResult, RunResult};
+use std::collections::BTreeMap;
+use std::env;
+use std::process::ExitCode;
+use std::str::FromStr;
+
+const DEFAULT_FIGHT_COUNT: u32 = 1000;
+const DEFAULT_RUN_COUNT: u32 = 100;
+const FIGHT_TURN_LIMIT: u32 = 200;
+const LOCATION_TURN_LIMIT: u32 = 1000;
+const USAGE: &str = "Usage:
+  balance_simulator fight <creature>[,<creature>...] [--count=N] [--crew=<species>[,<species>...]] [--weapon=<item>] [--armor=<item>] [--difficulty=D] [--seed=S]
+  balance_simulator runs [--count=N] [--locations=N] [--difficulty=D] [--seed=S]";
+
+fn main() -> ExitCode {
+    let result = match env::args().nth(1).as_deref() {
+        Some("fight") => env::args()
+            .nth(2)
+            .ok_or_else(|| "Expected a comma-separated list of creatures to fight".to_owned())
+            .and_then(|creatures| simulate_fights(&creatures)),
+        Some("runs") => simulate_runs(),
+        _ => Err(USAGE.to_owned()),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn simulate_fights(creatures: &str) -> Result<(), String> {
+    let assets = GameAssets::load().map_err(|error| error.to_string())?;
+    let count = arg_value("--count")?.unwrap_or(DEFAULT_FIGHT_COUNT);
+    let seed = arg_value("--seed")?.unwrap_or(0);
+    let config = FightConfig {
+        crew: arg_text("--crew").map_or_else(
+            || vec![SpeciesId::from("aftik")],
+            |crew| species_list(&crew),
+        ),
+        weapon: arg_text("--weapon").map(|weapon| weapon.as_str().into()),
+        armor: arg_text("--armor").map(|armor| armor.as_str().into()),
+        creatures: species_list(creatures),
+        difficulty: arg_value("--difficulty")?.unwrap_or(1.),
+        turn_limit: FIGHT_TURN_LIMIT,
+    };
+
+    let mut result_counts = BTreeMap::<&str, u32>::new();
+    let mut turns_to_win = 0;
+    let mut crew_deaths = 0;
+    for index in 0..count {
+        let outcome = simulation::simulate_fight(&config, seed + u64::from(index), &assets)?;
+        let result_name = match outcome.result {
+            FightResult::CrewWon => {
+                turns_to_win += outcome.turns;
+                "won"
+            }
+            FightResult::CrewLost => "lost",
+            FightResult::TimedOut => "timed out",
+        };
+        *result_counts.entry(result_name).or_default() += 1;
+        crew_deaths += outcome.crew_deaths;
+    }
+
+    println!("Simulated {count} fights against {creatures}:");
+    for (result_name, result_count) in &result_counts {
+        println!(
+            "  {result_name}: {result_count} ({:.1}%)",
+            percentage(*result_count as f32, count as f32)
+        );
+    }
+    if let Some(&wins) = result_counts.get("won") {
+        println!(
+            "  Average turns to kill: {:.1}",
+            turns_to_win as f32 / wins as f32
+        );
+    }
+    println!(
+        "  Crew casualty rate: {:.1}%",
+        percentage(
+            crew_deaths as f32,
+            (count as usize * config.crew.len()) as f32
+        )
+    );
+    Ok(())
+}
+
+#[derive(Default)]
+struct LocationStats {
+    visits: u32,
+    turns: u32,
+    crew_at_arrival: usize,
+    crew_lost: usize,
+    points_earned: i32,
+    loot_value: i32,
+}
+
+fn simulate_runs() -> Result<(), String> {
+    let assets = GameAssets::load().map_err(|error| error.to_string())?;
+    let count = arg_value("--count")?.unwrap_or(DEFAULT_RUN_COUNT);
+    let seed = arg_value("--seed")?.unwrap_or(0);
+    let default_config = RunConfig::default();
+    let run_config = RunConfig {
+        locations_before_fortuna: arg_value("--locations")?
+            .unwrap_or(default_config.locations_before_fortuna),
+        difficulty: arg_value("--difficulty")?.unwrap_or(default_config.difficulty),
+        ..default_config
+    };
+
+    let mut result_counts = BTreeMap::<&str, u32>::new();
+    let mut location_stats = BTreeMap::<String, LocationStats>::new();
+    let mut total_points = 0;
+    for index in 0..count {
+        let outcome = simulation::simulate_run(
+            run_config.clone(),
+            seed + u64::from(index),
+            LOCATION_TURN_LIMIT,
+            &assets,
+        )?;
+        let result_name = match outcome.result {
+            RunResult::Won => "won",
+            RunResult::Lost => "lost",
+            RunResult::Stranded => "stranded",
+        };
+        *result_counts.entry(result_name).or_default() += 1;
+        for location in outcome.locations {
+            total_points += location.points_earned;
+            let stats = location_stats.entry(location.location).or_default();
+            stats.visits += 1;
+            stats.turns += location.turns;
+            stats.crew_at_arrival += location.crew_at_arrival;
+            stats.crew_lost += location.crew_lost;
+            stats.points_earned += location.points_earned;
+            stats.loot_value += location.loot_value;
+        }
+    }
+
+    println!("Simulated {count} runs:");
+    for (result_name, result_count) in &result_counts {
+        println!(
+            "  {result_name}: {result_count} ({:.1}%)",
+            percentage(*result_count as f32, count as f32)
+        );
+    }
+    println!(
+        "  Average points earned per run: {:.1}",
+        total_points as f32 / count as f32
+    );
+    println!("Per location:");
+    for (location, stats) in &location_stats {
+        let visits = stats.visits as f32;
+        println!(
+            "  {location}: {} visits, {:.1} turns, {:.1}% crew casualties, {:.1} points, {:.1} loot value",
+            stats.visits,
+            stats.turns as f32 / visits,
+            percentage(stats.crew_lost as f32, stats.crew_at_arrival as f32),
+            stats.points_earned as f32 / visits,
+            stats.loot_value as f32 / visits,
+        );
+    }
+    Ok(())
+}
+
+fn percentage(part: f32, total: f32) -> f32 {
+    if total == 0. { 0. } else { part * 100. / total }
+}
+
+fn species_list(text: &str) -> Vec<SpeciesId> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|species| !species.is_empty())
+        .map(SpeciesId::from)
+        .collect()
+}
+
+fn arg_text(name: &str) -> Option<String> {
+    let prefix = format!("{name}=");
+    env::args().find_map(|arg| arg.strip_prefix(&prefix).map(str::to_owned))
+}
+
+fn arg_value<T: FromStr>(name: &str) -> Result<Option<T>, String>
+where
+    T::Err: std::fmt::Display,
+{
+    arg_text(name)
+        .map(|value| value.parse::<T>())
+        .transpose()
+        .map_err(|error| format!("Invalid value for {name}: {error}"))
+}
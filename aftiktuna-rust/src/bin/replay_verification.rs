@@ -0,0 +1,33 @@
+use aftiktuna::replay;
+use std::env;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let Some(path) = env::args().nth(1) else {
+        eprintln!("Expected the path to a replay file as argument");
+        return ExitCode::FAILURE;
+    };
+    let replay = match replay::load_replay_file(&path) {
+        Ok(replay) => replay,
+        Err(error) => {
+            eprintln!("Unable to load replay file \"{path}\": {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match replay.verify() {
+        Ok(()) => {
+            println!(
+                "Replay with seed {} and {} inputs is OK!",
+                replay.seed,
+                replay.input_count(),
+            );
+            ExitCode::SUCCESS
+        }
+        Err(message) => {
+            eprintln!("Replay with seed {} does not match:", replay.seed);
+            eprintln!("{message}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use aftiktuna::game_interface;
+use aftiktuna::serialization;
+use std::env;
+use std::fs::File;
+use std::io::BufReader;
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let result = match args.get(1..).unwrap_or_default() {
+        [command, slot, path] if command == "export" => export(slot, path),
+        [command, path, slot] if command == "import" => import(path, slot),
+        _ => Err(
+            "Usage: save_json export <slot> <json file> | save_json import <json file> <slot>"
+                .to_owned(),
+        ),
+    };
+    if let Err(message) = result {
+        eprintln!("{message}");
+    }
+}
+
+fn export(slot: &str, path: &str) -> Result<(), String> {
+    let game = game_interface::load(slot)
+        .map_err(|error| format!("Unable to load save slot \"{slot}\": {error}"))?;
+    let file =
+        File::create(path).map_err(|error| format!("Unable to create \"{path}\": {error}"))?;
+    serialization::write_game_as_json(&game, file)
+        .map_err(|error| format!("Unable to write \"{path}\": {error}"))?;
+    println!("Exported save slot \"{slot}\" to \"{path}\".");
+    Ok(())
+}
+
+fn import(path: &str, slot: &str) -> Result<(), String> {
+    if !serialization::is_valid_slot_name(slot) {
+        return Err(format!("Invalid save slot name \"{slot}\""));
+    }
+    let file = File::open(path).map_err(|error| format!("Unable to open \"{path}\": {error}"))?;
+    let game = game_interface::load_json(BufReader::new(file))
+        .map_err(|error| format!("Unable to load \"{path}\": {error}"))?;
+    serialization::write_game_to_save_slot(&game, slot)
+        .map_err(|error| format!("Unable to save to slot \"{slot}\": {error}"))?;
+    println!("Imported \"{path}\" into save slot \"{slot}\".");
+    Ok(())
+}
@@ -22,6 +22,7 @@ pub enum Target {
 pub enum CommandResult {
     Action(Action, Target),
     ChangeControlled(Entity),
+    ShowMap(view::map::MapView),
     Info(CommandInfo),
 }
 
@@ -55,11 +56,11 @@ fn crew_action(action: impl Into<Action>) -> Result<CommandResult, String> {
 
 pub fn try_parse_input(
     input: &str,
-    state: &GameState,
+    state: &mut GameState,
     assets: &GameAssets,
 ) -> Result<CommandResult, String> {
     if let Ok(decision) = state.world.get::<&Decision>(state.controlled) {
-        parse_decision_command(input, &decision)
+        parse_decision_command(input, &decision, assets)
     } else if let Some(shopkeeper) = core::store::get_shop_info(&state.world, state.controlled) {
         store::parse(input, &shopkeeper, state, assets)
     } else {
@@ -68,10 +69,14 @@ pub fn try_parse_input(
     .map_err(text::capitalize)
 }
 
-fn parse_decision_command(input: &str, decision: &Decision) -> Result<CommandResult, String> {
+fn parse_decision_command(
+    input: &str,
+    decision: &Decision,
+    assets: &GameAssets,
+) -> Result<CommandResult, String> {
     match decision {
         Decision::Recruit(_) | Decision::Passenger(_) => {
-            let decision = parse_yes_no(input)?;
+            let decision = parse_yes_no(input, assets)?;
             action_result(AnswerYesNoDecisionAction {
                 decision_outcome: decision,
             })
@@ -79,13 +84,13 @@ fn parse_decision_command(input: &str, decision: &Decision) -> Result<CommandRes
     }
 }
 
-fn parse_yes_no(input: &str) -> Result<bool, String> {
+fn parse_yes_no(input: &str, assets: &GameAssets) -> Result<bool, String> {
     if ["yes", "y", "sure"].contains(&input) {
         Ok(true)
     } else if ["no", "n"].contains(&input) {
         Ok(false)
     } else {
-        Err("Input does not match \"yes\" or \"no\".".to_owned())
+        Err(assets.messages.text("decision.not_yes_or_no", &[]))
     }
 }
 
@@ -1,22 +1,28 @@
 use crate::action::{Action, ForceDoorAction};
 use crate::asset::GameAssets;
 use crate::command::parse::{Parse, first_match_or};
-use crate::command::{self, CommandResult};
-use crate::core::area::{self, ShipControls, ShipState, ShipStatus};
+use crate::command::{self, CommandInfo, CommandResult};
+use crate::core::area::{self, ShipControls, ShipState, ShipStatus, ShipUpgrade};
 use crate::core::behavior::{self, Character};
 use crate::core::inventory::{self, Held};
 use crate::core::item::ItemTypeId;
 use crate::core::name::{Name, NameData, NameQuery};
 use crate::core::position::{self, Blockage, Placement, PlacementQuery, Pos};
+use crate::core::store::Points;
 use crate::core::{self, CrewMember, FortunaChest, status};
 use crate::game_loop::GameState;
+use crate::view::{map, text};
 use hecs::{Entity, Query, World};
 
 mod combat;
 mod dialogue;
 mod item;
 
-pub fn parse(input: &str, state: &GameState, assets: &GameAssets) -> Result<CommandResult, String> {
+pub fn parse(
+    input: &str,
+    state: &mut GameState,
+    assets: &GameAssets,
+) -> Result<CommandResult, String> {
     let world = &state.world;
     let character = state.controlled;
     let area = world.get::<&Pos>(character).unwrap().get_area();
@@ -28,30 +34,30 @@ pub fn parse(input: &str, state: &GameState, assets: &GameAssets) -> Result<Comm
             parse.match_against(
                 targets_in_room::<&core::Door>(area, world, assets),
                 |parse, door| parse.done_or_err(|| enter(door, character, world, assets)),
-                |_| Err("There is no such door or path here to go through.".to_owned()),
+                |_| Err(assets.messages.text("enter.no_such_door", &[])),
             )
         }),
         parse.literal("force", |parse| {
             parse.match_against(
                 targets_in_room::<&core::Door>(area, world, assets),
                 |parse, door| parse.done_or_err(|| force(door, character, world, assets)),
-                |_| Err("There is no such door here.".to_owned()),
+                |_| Err(assets.messages.text("force.no_such_door", &[])),
             )
         }),
         parse.literal("go to", |parse|
             first_match_or!(
                 parse.literal("ship", |parse|
-                    parse.done_or_err(|| go_to_ship(world, character))
+                    parse.done_or_err(|| go_to_ship(world, character, assets))
                 );
                 parse.default_err()
             )
         ),
-        combat::commands(&parse, state.world.entity(state.controlled).unwrap(), &state.world, assets),
+        combat::commands(&parse, state.world.entity(state.controlled).unwrap(), &state.world, &mut state.rng, assets),
         dialogue::commands(&parse, state, assets),
         parse.literal("wait", |parse| {
             parse.done_or_err(|| command::action_result(Action::Wait))
         }),
-        parse.literal("rest", |parse| parse.done_or_err(|| rest(world, character))),
+        parse.literal("rest", |parse| parse.done_or_err(|| rest(world, character, assets))),
         parse.literal("refuel", |parse| {
             first_match_or!(
                 parse.literal("ship", |parse| parse.done_or_err(|| refuel_ship(state, assets)));
@@ -67,39 +73,53 @@ pub fn parse(input: &str, state: &GameState, assets: &GameAssets) -> Result<Comm
         parse.literal("status", |parse| {
             parse.done_or_err(|| command::status(state, assets))
         }),
+        parse.literal("upgrades", |parse| parse.done_or_err(|| upgrades(state, assets))),
+        parse.literal("install", |parse| {
+            parse.match_against(
+                ShipUpgrade::variants().iter().map(|&upgrade| (upgrade.name().to_owned(), upgrade)),
+                |parse, upgrade| parse.done_or_err(|| install_upgrade(state, upgrade, assets)),
+                |input| Err(assets.messages.text("ship.upgrade.unknown", &[("input", input)])),
+            )
+        }),
+        parse.literal("combat log", |parse| {
+            parse.done_or_err(|| combat_log(state, assets))
+        }),
+        parse.literal("map", |parse| {
+            parse.done_or_err(|| Ok(CommandResult::ShowMap(map::prepare_map_view(state, assets))))
+        }),
         parse.literal("check", |parse| {
             parse.match_against(
                 check_item_targets(world, character, assets),
-                |parse, item| parse.done_or_err(|| check(world, item, assets)),
-                |input| Err(format!("There is no item by the name \"{input}\" here.")),
+                |parse, item| parse.done_or_err(|| check(world, character, item, assets)),
+                |input| Err(assets.messages.text("check.no_such_item", &[("input", input)])),
             )
         }),
         parse.literal("control", |parse| {
             parse.match_against(
                 crew_character_targets(world, assets),
-                |parse, target| parse.done_or_err(|| control(character, target)),
-                |input| Err(format!("There is no crew member by the name \"{input}\".")),
+                |parse, target| parse.done_or_err(|| control(character, target, assets)),
+                |input| Err(assets.messages.text("control.no_such_crew_member", &[("input", input)])),
             )
         }),
         parse.literal("open", |parse| {
             parse.match_against(
                 fortuna_chest_targets(world, character, assets),
                 |parse, target| parse.done_or_err(|| open(world, character, target, assets)),
-                |input| Err(format!("\"{input}\" is not a valid target.")),
+                |input| Err(assets.messages.text("command.invalid_target", &[("input", input)])),
             )
         }),
         parse.literal("tame", |parse| {
             parse.match_against(
                 combat::hostile_targets(world, character, assets).into_iter().flat_map(|(name, targets)| targets.into_iter().map(move |target| (name.clone(), target))),
                 |parse, target| parse.done_or_err(|| tame(world, character, target, assets)),
-                |input| Err(format!("\"{input}\" is not a valid target.")),
+                |input| Err(assets.messages.text("command.invalid_target", &[("input", input)])),
             )
         }),
         parse.literal("name", |parse| {
             parse.match_against(
                 crew_targets_in_room(area, world, assets),
                 |parse, target| parse.take_remaining(|name| give_name(world, character, target, name.to_owned(), assets)),
-                |input| Err(format!("\"{input}\" is not a valid target.")),
+                |input| Err(assets.messages.text("command.invalid_target", &[("input", input)])),
             )
         });
         parse.default_err()
@@ -186,18 +206,22 @@ fn force(
     })
 }
 
-fn go_to_ship(world: &World, character: Entity) -> Result<CommandResult, String> {
+fn go_to_ship(
+    world: &World,
+    character: Entity,
+    assets: &GameAssets,
+) -> Result<CommandResult, String> {
     let area = world.get::<&Pos>(character).unwrap().get_area();
     if area::is_ship(area, world) {
-        return Err("You are already at the ship.".to_string());
+        return Err(assets.messages.text("go_to_ship.already_there", &[]));
     }
     command::crew_action(Action::GoToShip)
 }
 
-fn rest(world: &World, character: Entity) -> Result<CommandResult, String> {
+fn rest(world: &World, character: Entity, assets: &GameAssets) -> Result<CommandResult, String> {
     let area = world.get::<&Pos>(character).unwrap().get_area();
     if !behavior::is_safe(world, area) {
-        return Err("This area is not safe to rest in.".to_string());
+        return Err(assets.messages.text("rest.not_safe", &[]));
     }
 
     let need_rest = world
@@ -207,7 +231,7 @@ fn rest(world: &World, character: Entity) -> Result<CommandResult, String> {
         .any(|(stamina, pos)| pos.is_in(area) && stamina.need_rest());
 
     if !need_rest {
-        return Err("The crew is already rested.".to_string());
+        return Err(assets.messages.text("rest.already_rested", &[]));
     }
 
     command::action_result(Action::Rest(true))
@@ -225,25 +249,31 @@ fn refuel_ship(state: &GameState, assets: &GameAssets) -> Result<CommandResult,
         .find(|(_, pos)| pos.is_in(area) && area::is_ship(area, world))
         .map(|(entity, _)| entity)
         .ok_or_else(|| {
-            format!(
-                "{} needs to be in the ship control room in order to refuel it.",
-                NameData::find(world, character, assets).definite()
+            assets.messages.text(
+                "refuel.not_in_control_room",
+                &[(
+                    "performer",
+                    &NameData::find(world, character, assets).definite(),
+                )],
             )
         })?;
     check_adjacent_accessible_with_message(ship_controls, character, world, assets)?;
 
     let status = world
         .get::<&ShipState>(state.ship_core)
-        .map_err(|_| "The crew has no ship.".to_string())?
+        .map_err(|_| assets.messages.text("ship.missing", &[]))?
         .status;
 
     if !matches!(status, ShipStatus::NeedFuel(_)) {
-        return Err("The ship is already refueled.".to_string());
+        return Err(assets.messages.text("refuel.already_refueled", &[]));
     }
     if !inventory::is_holding(ItemTypeId::is_fuel_can, world, character) {
-        return Err(format!(
-            "{} needs a fuel can to refuel the ship.",
-            NameData::find(world, character, assets).definite()
+        return Err(assets.messages.text(
+            "refuel.needs_fuel_can",
+            &[(
+                "performer",
+                &NameData::find(world, character, assets).definite(),
+            )],
         ));
     }
     command::action_result(Action::Refuel)
@@ -253,7 +283,7 @@ fn launch_ship(state: &GameState, assets: &GameAssets) -> Result<CommandResult,
     let world = &state.world;
     let character = state.controlled;
     if state.generation_state.is_at_fortuna() {
-        return Err("You are already at your final destination. You should find the fortuna chest before leaving!".to_string());
+        return Err(assets.messages.text("launch.at_fortuna", &[]));
     }
 
     let area = world.get::<&Pos>(character).unwrap().get_area();
@@ -264,31 +294,155 @@ fn launch_ship(state: &GameState, assets: &GameAssets) -> Result<CommandResult,
         .find(|(_, pos)| pos.is_in(area) && area::is_ship(area, world))
         .map(|(entity, _)| entity)
         .ok_or_else(|| {
-            format!(
-                "{} needs to be in the ship control room in order to launch it.",
-                NameData::find(world, character, assets).definite()
+            assets.messages.text(
+                "launch.not_in_control_room",
+                &[(
+                    "performer",
+                    &NameData::find(world, character, assets).definite(),
+                )],
             )
         })?;
     check_adjacent_accessible_with_message(ship_controls, character, world, assets)?;
 
     let status = world
         .get::<&ShipState>(state.ship_core)
-        .map_err(|_| "The crew has no ship.".to_string())?
+        .map_err(|_| assets.messages.text("ship.missing", &[]))?
         .status;
     if matches!(status, ShipStatus::NeedFuel(_))
         && !inventory::is_holding(ItemTypeId::is_fuel_can, world, character)
     {
-        return Err(format!(
-            "{} needs a fuel can to launch the ship.",
-            NameData::find(world, character, assets).definite()
+        return Err(assets.messages.text(
+            "launch.needs_fuel_can",
+            &[(
+                "performer",
+                &NameData::find(world, character, assets).definite(),
+            )],
         ));
     }
     command::action_result(Action::Launch)
 }
 
-fn control(character: Entity, target: Entity) -> Result<CommandResult, String> {
+fn crew_points(state: &GameState) -> i32 {
+    let crew = state.world.get::<&CrewMember>(state.controlled).unwrap().0;
+    state
+        .world
+        .get::<&Points>(crew)
+        .map_or(0, |points| points.0)
+}
+
+fn upgrades(state: &GameState, assets: &GameAssets) -> Result<CommandResult, String> {
+    let ship_state = state
+        .world
+        .get::<&ShipState>(state.ship_core)
+        .map_err(|_| assets.messages.text("ship.missing", &[]))?;
+    let mut lines = vec![assets.messages.text(
+        "ship.upgrade.list.header",
+        &[("points", &crew_points(state).to_string())],
+    )];
+    for &upgrade in ShipUpgrade::variants() {
+        let price_text = if ship_state.has_upgrade(upgrade) {
+            assets.messages.text("ship.upgrade.list.installed", &[])
+        } else {
+            assets.messages.text(
+                "ship.upgrade.list.price",
+                &[("price", &upgrade.price().to_string())],
+            )
+        };
+        lines.push(assets.messages.text(
+            "ship.upgrade.list.entry",
+            &[
+                ("upgrade", &text::capitalize(upgrade.name())),
+                ("price", &price_text),
+                ("description", &upgrade.description(assets)),
+            ],
+        ));
+    }
+    Ok(CommandResult::Info(CommandInfo::Message(lines)))
+}
+
+/// The number of attacks shown by the "combat log" command.
+const SHOWN_COMBAT_LOG_ATTACKS: usize = 5;
+
+fn combat_log(state: &GameState, assets: &GameAssets) -> Result<CommandResult, String> {
+    let combat_log = state
+        .combat_log
+        .as_ref()
+        .ok_or_else(|| assets.messages.text("combat_log.disabled", &[]))?;
+    if combat_log.records().is_empty() {
+        return Err(assets.messages.text("combat_log.empty", &[]));
+    }
+    let mut lines = vec![
+        assets.messages.text(
+            "combat_log.header",
+            &[
+                (
+                    "shown",
+                    &SHOWN_COMBAT_LOG_ATTACKS
+                        .min(combat_log.records().len())
+                        .to_string(),
+                ),
+                ("total", &combat_log.records().len().to_string()),
+            ],
+        ),
+    ];
+    lines.extend(combat_log.recent_lines(SHOWN_COMBAT_LOG_ATTACKS));
+    Ok(CommandResult::Info(CommandInfo::Message(lines)))
+}
+
+fn install_upgrade(
+    state: &GameState,
+    upgrade: ShipUpgrade,
+    assets: &GameAssets,
+) -> Result<CommandResult, String> {
+    let world = &state.world;
+    let character = state.controlled;
+    let area = world.get::<&Pos>(character).unwrap().get_area();
+    let ship_controls = world
+        .query::<(Entity, &Pos)>()
+        .with::<&ShipControls>()
+        .iter()
+        .find(|(_, pos)| pos.is_in(area) && area::is_ship(area, world))
+        .map(|(entity, _)| entity)
+        .ok_or_else(|| {
+            assets.messages.text(
+                "ship.upgrade.not_in_control_room",
+                &[(
+                    "performer",
+                    &NameData::find(world, character, assets).definite(),
+                )],
+            )
+        })?;
+    check_adjacent_accessible_with_message(ship_controls, character, world, assets)?;
+
+    if world
+        .get::<&ShipState>(state.ship_core)
+        .map_err(|_| assets.messages.text("ship.missing", &[]))?
+        .has_upgrade(upgrade)
+    {
+        return Err(assets.messages.text(
+            "ship.upgrade.already_installed",
+            &[("upgrade", upgrade.name())],
+        ));
+    }
+    if crew_points(state) < upgrade.price() {
+        return Err(assets.messages.text(
+            "ship.upgrade.cannot_afford",
+            &[
+                ("upgrade", upgrade.name()),
+                ("price", &upgrade.price().to_string()),
+            ],
+        ));
+    }
+    command::action_result(Action::InstallUpgrade(upgrade))
+}
+
+fn control(
+    character: Entity,
+    target: Entity,
+    assets: &GameAssets,
+) -> Result<CommandResult, String> {
     if target == character {
-        Err("You're already in control of them.".to_string())
+        Err(assets.messages.text("control.already_controlled", &[]))
     } else {
         Ok(CommandResult::ChangeControlled(target))
     }
@@ -353,7 +507,22 @@ fn held_item_targets(world: &World, holder: Entity, assets: &GameAssets) -> Vec<
         .collect()
 }
 
-fn check(world: &World, item: Entity, assets: &GameAssets) -> Result<CommandResult, String> {
+fn check(
+    world: &World,
+    character: Entity,
+    item: Entity,
+    assets: &GameAssets,
+) -> Result<CommandResult, String> {
+    let area = world.get::<&Pos>(character).unwrap().get_area();
+    if core::area::is_dark(area, world, assets) {
+        return Err(assets.messages.text(
+            "darkness.too_dark_to_examine",
+            &[(
+                "performer",
+                &NameData::find(world, character, assets).definite(),
+            )],
+        ));
+    }
     Ok(CommandResult::Info(crate::CommandInfo::Message(
         core::item::description(world.entity(item).unwrap(), assets),
     )))
@@ -368,9 +537,12 @@ fn tame(
     check_adjacent_accessible_with_message(target, character, world, assets)?;
 
     if !inventory::is_holding(ItemTypeId::is_food_ration, world, character) {
-        return Err(format!(
-            "{} needs a food ration to tame.",
-            NameData::find(world, character, assets).definite()
+        return Err(assets.messages.text(
+            "tame.needs_food_ration",
+            &[(
+                "performer",
+                &NameData::find(world, character, assets).definite(),
+            )],
         ));
     }
 
@@ -387,9 +559,9 @@ fn give_name(
     check_adjacent_accessible_with_message(target, character, world, assets)?;
 
     if world.entity(target).unwrap().has::<Name>() {
-        return Err(format!(
-            "{} already has a name.",
-            NameData::find(world, target, assets).definite()
+        return Err(assets.messages.text(
+            "name.already_named",
+            &[("target", &NameData::find(world, target, assets).definite())],
         ));
     }
 
@@ -410,10 +582,15 @@ impl Inaccessible {
         assets: &GameAssets,
     ) -> String {
         match self {
-            Inaccessible::NotHere => format!(
-                "{} can not reach {} from here.",
-                NameData::find(world, character, assets).definite(),
-                NameData::find(world, target, assets).definite()
+            Inaccessible::NotHere => assets.messages.text(
+                "access.cannot_reach",
+                &[
+                    (
+                        "performer",
+                        &NameData::find(world, character, assets).definite(),
+                    ),
+                    ("target", &NameData::find(world, target, assets).definite()),
+                ],
             ),
             Inaccessible::Blocked(blockage) => blockage.into_message(world, assets),
         }
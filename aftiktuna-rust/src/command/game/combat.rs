@@ -4,24 +4,27 @@ use crate::asset::GameAssets;
 use crate::command::parse::{Parse, first_match_or};
 use crate::command::{self, CommandResult};
 use crate::core::behavior::Hostile;
+use crate::core::combat;
 use crate::core::position::Pos;
 use crate::core::status;
 use hecs::{Entity, EntityRef, World};
+use rand::Rng;
 use std::collections::HashMap;
 
 pub fn commands(
     parse: &Parse,
     performer_ref: EntityRef,
     world: &World,
+    rng: &mut impl Rng,
     assets: &GameAssets,
 ) -> Option<Result<CommandResult, String>> {
     parse.literal("attack", |parse| {
         first_match_or!(
-            parse.empty(|| attack_any(performer_ref, world, assets));
+            parse.empty(|| attack_any(performer_ref, world, rng, assets));
             parse.match_against(
                 hostile_targets(world, performer_ref.entity(), assets),
-                |parse, targets| parse.done_or_err(|| attack(performer_ref, targets, world, assets)),
-                |_| Err("There is no such target here.".to_string())
+                |parse, targets| parse.done_or_err(|| attack(performer_ref, targets, world, rng, assets)),
+                |_| Err(assets.messages.text("attack.no_such_target", &[]))
             )
         )
     })
@@ -30,6 +33,7 @@ pub fn commands(
 fn attack_any(
     performer_ref: EntityRef,
     world: &World,
+    rng: &mut impl Rng,
     assets: &GameAssets,
 ) -> Result<CommandResult, String> {
     let area = performer_ref.get::<&Pos>().unwrap().get_area();
@@ -42,11 +46,11 @@ fn attack_any(
         .collect::<Vec<_>>();
 
     if foes.is_empty() {
-        Err("There is no appropriate target to attack here.".to_string())
+        Err(assets.messages.text("attack.no_target", &[]))
     } else {
         command::action_result(Action::Attack(
             foes,
-            ai::pick_attack_kind(performer_ref, world, &mut rand::rng(), assets),
+            ai::pick_attack_kind(performer_ref, world, rng, assets),
         ))
     }
 }
@@ -77,8 +81,16 @@ fn attack(
     performer_ref: EntityRef,
     targets: Vec<Entity>,
     world: &World,
+    rng: &mut impl Rng,
     assets: &GameAssets,
 ) -> Result<CommandResult, String> {
+    if combat::get_usable_ranged_properties(world, performer_ref.entity(), assets).is_some() {
+        return command::action_result(Action::Attack(
+            targets,
+            ai::pick_attack_kind(performer_ref, world, rng, assets),
+        ));
+    }
+
     let character_pos = *performer_ref.get::<&Pos>().unwrap();
 
     let target_access = targets
@@ -106,6 +118,6 @@ fn attack(
 
     command::action_result(Action::Attack(
         targets,
-        ai::pick_attack_kind(performer_ref, world, &mut rand::rng(), assets),
+        ai::pick_attack_kind(performer_ref, world, rng, assets),
     ))
 }
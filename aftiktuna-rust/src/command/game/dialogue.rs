@@ -3,11 +3,11 @@ use crate::asset::GameAssets;
 use crate::command;
 use crate::command::CommandResult;
 use crate::command::parse::{Parse, first_match, first_match_or};
-use crate::core::behavior::{Character, GivesHuntRewardData, Waiting};
+use crate::core::behavior::{Character, GivesHuntRewardData, Order, Waiting};
 use crate::core::name::{Name, NameData};
 use crate::core::position::Pos;
 use crate::core::store::Shopkeeper;
-use crate::core::{CrewMember, area, status};
+use crate::core::{CrewMember, area, inventory, status};
 use crate::dialogue::TalkTopic;
 use crate::game_loop::GameState;
 use hecs::Entity;
@@ -24,7 +24,7 @@ pub fn commands(
                     parse.match_against(
                         talk_targets(state, assets),
                         |parse, target| parse.done_or_err(|| talk_to(state, target, assets)),
-                        |input| Err(format!("\"{input}\" is not a valid target.")),
+                        |input| Err(assets.messages.text("command.invalid_target", &[("input", input)])),
                     )
                 });
                 parse.default_err()
@@ -40,7 +40,7 @@ pub fn commands(
                         parse.default_err()
                     )
                 },
-                |input| Err(format!("\"{input}\" is not a valid target.")),
+                |input| Err(assets.messages.text("command.invalid_target", &[("input", input)])),
             )
         }),
         parse.literal("ask", |parse| {
@@ -55,7 +55,7 @@ pub fn commands(
                         parse.default_err()
                     )
                 },
-                |input| Err(format!("\"{input}\" is not a valid target.")),
+                |input| Err(assets.messages.text("command.invalid_target", &[("input", input)])),
             )
         }),
     )
@@ -76,7 +76,34 @@ fn subcommands_for_tell(
             )
         ),
         parse.literal("follow", |parse|
-            parse.done_or_err(|| tell_to_follow(state, target, assets)));
+            parse.done_or_err(|| tell_to_follow(state, target, assets))),
+        parse.literal("attack", |parse|
+            parse.match_against(
+                super::combat::hostile_targets(&state.world, state.controlled, assets),
+                |parse, foes| parse.done_or_err(|| tell_to_attack(state, target, foes, assets)),
+                |input| Err(assets.messages.text("command.invalid_target", &[("input", input)])),
+            )
+        ),
+        parse.literal("guard", |parse|
+            parse.match_against(
+                super::crew_character_targets(&state.world, assets),
+                |parse, guarded| {
+                    parse.done_or_err(|| tell_to_guard(state, target, guarded, assets))
+                },
+                |input| Err(assets.messages.text("command.invalid_target", &[("input", input)])),
+            )
+        ),
+        parse.literal("retreat to ship", |parse|
+            parse.done_or_err(|| tell_to_retreat_to_ship(state, target, assets))),
+        parse.literal("use medkit on", |parse|
+            parse.match_against(
+                super::crew_character_targets(&state.world, assets),
+                |parse, patient| {
+                    parse.done_or_err(|| tell_to_use_medkit(state, target, patient, assets))
+                },
+                |input| Err(assets.messages.text("command.invalid_target", &[("input", input)])),
+            )
+        );
         parse.default_err()
     )
 }
@@ -113,17 +140,29 @@ fn talk_to(
     let Some(topic) = TalkTopic::pick(target, &state.world) else {
         return Err(
             if let Ok(gives_hunt_reward) = state.world.get::<&GivesHuntRewardData>(target) {
-                format!(
-                    "{the_target} is still waiting for {the_hunt_target} to be gone.",
-                    the_target = NameData::find(&state.world, target, assets,).definite(),
-                    the_hunt_target = gives_hunt_reward.target_label,
+                assets.messages.text(
+                    "talk.waiting_for_hunt",
+                    &[
+                        (
+                            "target",
+                            &NameData::find(&state.world, target, assets).definite(),
+                        ),
+                        ("hunt_target", &gives_hunt_reward.target_label),
+                    ],
                 )
             } else {
-                format!(
-                    "{the_speaker} has nothing to say to {the_target}.",
-                    the_speaker =
-                        NameData::find(&state.world, state.controlled, assets,).definite(),
-                    the_target = NameData::find(&state.world, target, assets,).definite(),
+                assets.messages.text(
+                    "talk.nothing_to_say",
+                    &[
+                        (
+                            "performer",
+                            &NameData::find(&state.world, state.controlled, assets).definite(),
+                        ),
+                        (
+                            "target",
+                            &NameData::find(&state.world, target, assets).definite(),
+                        ),
+                    ],
                 )
             },
         );
@@ -146,10 +185,18 @@ fn ask_for_name(
         .get::<&Name>(target)
         .is_ok_and(|name| name.is_known)
     {
-        return Err(format!(
-            "{the_performer} already knows {the_target}'s name.",
-            the_performer = NameData::find(&state.world, state.controlled, assets).definite(),
-            the_target = NameData::find(&state.world, target, assets).definite(),
+        return Err(assets.messages.text(
+            "talk.already_knows_name",
+            &[
+                (
+                    "performer",
+                    &NameData::find(&state.world, state.controlled, assets).definite(),
+                ),
+                (
+                    "target",
+                    &NameData::find(&state.world, target, assets).definite(),
+                ),
+            ],
         ));
     }
 
@@ -167,9 +214,12 @@ fn ask_to_join(
     check_is_valid_talk_target(target, state, assets)?;
 
     if state.world.satisfies::<&CrewMember>(target) {
-        return Err(format!(
-            "{the_target} is already part of the crew.",
-            the_target = NameData::find(&state.world, target, assets).definite(),
+        return Err(assets.messages.text(
+            "recruit.already_crew",
+            &[(
+                "target",
+                &NameData::find(&state.world, target, assets).definite(),
+            )],
         ));
     }
     super::check_adjacent_accessible_with_message(target, state.controlled, &state.world, assets)?;
@@ -183,15 +233,21 @@ fn check_is_valid_talk_target(
     assets: &GameAssets,
 ) -> Result<(), String> {
     if target == state.controlled {
-        return Err(format!(
-            "{} does not want to talk to themselves.",
-            NameData::find(&state.world, state.controlled, assets).definite(),
+        return Err(assets.messages.text(
+            "talk.self",
+            &[(
+                "performer",
+                &NameData::find(&state.world, state.controlled, assets).definite(),
+            )],
         ));
     }
     if !status::is_alive(target, &state.world) {
-        return Err(format!(
-            "{} cannot talk to the dead.",
-            NameData::find(&state.world, state.controlled, assets).definite(),
+        return Err(assets.messages.text(
+            "talk.dead",
+            &[(
+                "performer",
+                &NameData::find(&state.world, state.controlled, assets).definite(),
+            )],
         ));
     }
     Ok(())
@@ -202,26 +258,15 @@ fn tell_to_wait(
     target: Entity,
     assets: &GameAssets,
 ) -> Result<CommandResult, String> {
-    if state.controlled == target {
-        return Err(format!(
-            "{} can't give an order to themselves.",
-            NameData::find(&state.world, state.controlled, assets).definite()
-        ));
-    }
-    let controlled_pos = state.world.get::<&Pos>(state.controlled).unwrap();
-    let target_pos = state.world.get::<&Pos>(target).unwrap();
-    if !controlled_pos.is_in(target_pos.get_area()) {
-        return Err(format!(
-            "{} can't tell {} to do things from here.",
-            NameData::find(&state.world, state.controlled, assets).definite(),
-            NameData::find(&state.world, target, assets).definite()
-        ));
-    }
+    check_can_give_order(state, target, assets)?;
 
     if state.world.satisfies::<&Waiting>(target) {
-        return Err(format!(
-            "{} is already waiting.",
-            NameData::find(&state.world, target, assets).definite()
+        return Err(assets.messages.text(
+            "order.wait.already_waiting",
+            &[(
+                "target",
+                &NameData::find(&state.world, target, assets).definite(),
+            )],
         ));
     }
 
@@ -233,25 +278,15 @@ fn tell_to_wait_at_ship(
     target: Entity,
     assets: &GameAssets,
 ) -> Result<CommandResult, String> {
-    if state.controlled == target {
-        return Err(format!(
-            "{} can't give an order to themselves.",
-            NameData::find(&state.world, state.controlled, assets).definite()
-        ));
-    }
-    let controlled_pos = *state.world.get::<&Pos>(state.controlled).unwrap();
+    check_can_give_order(state, target, assets)?;
     let target_pos = *state.world.get::<&Pos>(target).unwrap();
-    if !controlled_pos.is_in(target_pos.get_area()) {
-        return Err(format!(
-            "{} can't tell {} to do things from here.",
-            NameData::find(&state.world, state.controlled, assets).definite(),
-            NameData::find(&state.world, target, assets).definite()
-        ));
-    }
     if area::is_in_ship(target_pos, &state.world) && state.world.satisfies::<&Waiting>(target) {
-        return Err(format!(
-            "{} is already waiting at the ship.",
-            NameData::find(&state.world, target, assets).definite()
+        return Err(assets.messages.text(
+            "order.wait_at_ship.already_waiting",
+            &[(
+                "target",
+                &NameData::find(&state.world, target, assets).definite(),
+            )],
         ));
     }
     if state
@@ -259,9 +294,12 @@ fn tell_to_wait_at_ship(
         .get::<&Waiting>(target)
         .is_ok_and(|waiting| waiting.at_ship)
     {
-        return Err(format!(
-            "{} is already on their way to the ship.",
-            NameData::find(&state.world, target, assets).definite()
+        return Err(assets.messages.text(
+            "order.wait_at_ship.on_the_way",
+            &[(
+                "target",
+                &NameData::find(&state.world, target, assets).definite(),
+            )],
         ));
     }
 
@@ -273,21 +311,144 @@ fn tell_to_follow(
     target: Entity,
     assets: &GameAssets,
 ) -> Result<CommandResult, String> {
+    check_can_give_order(state, target, assets)?;
+
+    command::action_result(Action::TellToFollow(target))
+}
+
+fn tell_to_attack(
+    state: &GameState,
+    target: Entity,
+    foes: Vec<Entity>,
+    assets: &GameAssets,
+) -> Result<CommandResult, String> {
+    check_can_give_order(state, target, assets)?;
+    let target_pos = *state.world.get::<&Pos>(target).unwrap();
+    let foe = foes
+        .into_iter()
+        .min_by_key(|&foe| {
+            state
+                .world
+                .get::<&Pos>(foe)
+                .map_or(u32::MAX, |foe_pos| foe_pos.distance_to(target_pos))
+        })
+        .ok_or_else(|| assets.messages.text("order.attack.no_target", &[]))?;
+
+    command::action_result(Action::GiveOrder(target, Order::Attack(foe)))
+}
+
+fn tell_to_guard(
+    state: &GameState,
+    target: Entity,
+    guarded: Entity,
+    assets: &GameAssets,
+) -> Result<CommandResult, String> {
+    check_can_give_order(state, target, assets)?;
+    if target == guarded {
+        return Err(assets.messages.text(
+            "order.guard.self",
+            &[(
+                "target",
+                &NameData::find(&state.world, target, assets).definite(),
+            )],
+        ));
+    }
+    if !status::is_alive(guarded, &state.world) {
+        return Err(assets.messages.text(
+            "order.guard.dead",
+            &[(
+                "target",
+                &NameData::find(&state.world, guarded, assets).definite(),
+            )],
+        ));
+    }
+
+    command::action_result(Action::GiveOrder(target, Order::Guard(guarded)))
+}
+
+fn tell_to_retreat_to_ship(
+    state: &GameState,
+    target: Entity,
+    assets: &GameAssets,
+) -> Result<CommandResult, String> {
+    check_can_give_order(state, target, assets)?;
+    let target_pos = *state.world.get::<&Pos>(target).unwrap();
+    if area::is_in_ship(target_pos, &state.world) {
+        return Err(assets.messages.text(
+            "order.retreat_to_ship.already_there",
+            &[(
+                "target",
+                &NameData::find(&state.world, target, assets).definite(),
+            )],
+        ));
+    }
+
+    command::action_result(Action::GiveOrder(target, Order::RetreatToShip))
+}
+
+fn tell_to_use_medkit(
+    state: &GameState,
+    target: Entity,
+    patient: Entity,
+    assets: &GameAssets,
+) -> Result<CommandResult, String> {
+    check_can_give_order(state, target, assets)?;
+    if inventory::find_held_medkit(&state.world, target, assets).is_none() {
+        return Err(assets.messages.text(
+            "order.use_medkit.no_medkit",
+            &[(
+                "target",
+                &NameData::find(&state.world, target, assets).definite(),
+            )],
+        ));
+    }
+    if !state
+        .world
+        .get::<&status::Health>(patient)
+        .is_ok_and(|health| health.is_alive() && health.is_hurt())
+    {
+        return Err(assets.messages.text(
+            "order.use_medkit.not_needed",
+            &[(
+                "target",
+                &NameData::find(&state.world, patient, assets).definite(),
+            )],
+        ));
+    }
+
+    command::action_result(Action::GiveOrder(target, Order::UseMedkitOn(patient)))
+}
+
+fn check_can_give_order(
+    state: &GameState,
+    target: Entity,
+    assets: &GameAssets,
+) -> Result<(), String> {
     if state.controlled == target {
-        return Err(format!(
-            "{} can't give an order to themselves.",
-            NameData::find(&state.world, state.controlled, assets).definite()
+        return Err(assets.messages.text(
+            "order.self",
+            &[(
+                "performer",
+                &NameData::find(&state.world, state.controlled, assets).definite(),
+            )],
         ));
     }
     let controlled_pos = state.world.get::<&Pos>(state.controlled).unwrap();
     let target_pos = state.world.get::<&Pos>(target).unwrap();
     if !controlled_pos.is_in(target_pos.get_area()) {
-        return Err(format!(
-            "{} can't tell {} to do things from here.",
-            NameData::find(&state.world, state.controlled, assets).definite(),
-            NameData::find(&state.world, target, assets).definite()
+        return Err(assets.messages.text(
+            "order.not_here",
+            &[
+                (
+                    "performer",
+                    &NameData::find(&state.world, state.controlled, assets).definite(),
+                ),
+                (
+                    "target",
+                    &NameData::find(&state.world, target, assets).definite(),
+                ),
+            ],
         ));
     }
-
-    command::action_result(Action::TellToFollow(target))
+    Ok(())
 }
@@ -2,11 +2,12 @@ use crate::action::Action;
 use crate::action::item::{SearchAction, UseAction};
 use crate::asset::GameAssets;
 use crate::command::parse::{Parse, first_match, first_match_or};
-use crate::command::{self, CommandResult};
+use crate::command::{self, CommandInfo, CommandResult};
+use crate::core::area::{self, SHIP_CARGO_CAPACITY};
 use crate::core::behavior;
 use crate::core::inventory::{Container, Held};
 use crate::core::item::ItemTypeId;
-use crate::core::name::NameData;
+use crate::core::name::{self, ArticleKind, CountFormat, NameData, NameIdData};
 use crate::core::position::Pos;
 use crate::core::status::Health;
 use crate::game_loop::GameState;
@@ -19,15 +20,30 @@ pub fn commands(
 ) -> Option<Result<CommandResult, String>> {
     let character_pos = *state.world.get::<&Pos>(state.controlled).unwrap();
     first_match!(
+        parse.literal("take off", |parse| {
+            parse.match_against(
+                worn_items(state.controlled, &state.world, assets),
+                |parse, item| parse.done_or_err(|| command::action_result(Action::TakeOff(item))),
+                |input| {
+                    Err(assets.messages.text(
+                        "item.take_off.no_such_item",
+                        &[
+                            ("performer", &NameData::find(&state.world, state.controlled, assets).definite()),
+                            ("input", input),
+                        ],
+                    ))
+                },
+            )
+        }),
         parse.literal("take", |parse| {
             first_match_or!(
                 parse.literal("all", |parse| {
-                    parse.done_or_err(|| take_all(state))
+                    parse.done_or_err(|| take_all(state, assets))
                 });
                 parse.match_against(
                     super::targets_by_proximity::<&ItemTypeId>(character_pos, &state.world, assets),
                     |parse, item| parse.done_or_err(|| take(item, state, assets)),
-                    |input| Err(format!("There is no {input} here to pick up.")),
+                    |input| Err(assets.messages.text("item.take.no_such_item", &[("input", input)])),
                 )
             )
         }),
@@ -39,7 +55,7 @@ pub fn commands(
                     assets,
                 ),
                 |parse, container| parse.done_or_err(|| search(container, state, assets)),
-                |input| Err(format!("\"{input}\" is not a valid searchable container.")),
+                |input| Err(assets.messages.text("search.no_such_container", &[("input", input)])),
             )
         }),
         parse.literal("give", |parse| {
@@ -52,25 +68,28 @@ pub fn commands(
                             .chain(items_in_hand(state.controlled, &state.world, assets)),
                         |parse, item| parse.done_or_err(|| give(receiver, item, state, assets)),
                         |input| {
-                            Err(format!(
-                                "{} has no {input} to give.",
-                                NameData::find(&state.world, state.controlled, assets).definite(),
+                            Err(assets.messages.text(
+                                "give.no_such_item",
+                                &[
+                                    ("performer", &NameData::find(&state.world, state.controlled, assets).definite()),
+                                    ("input", input),
+                                ],
                             ))
                         },
                     )
                 },
-                |input| Err(format!("\"{input}\" is not a valid target.")),
+                |input| Err(assets.messages.text("command.invalid_target", &[("input", input)])),
             )
         }),
         parse.literal("wield", |parse| {
             parse.match_against(
                 items_in_hand(state.controlled, &state.world, assets)
                     .into_iter()
-                    .map(|(name, item)| (name, WieldItemTarget::InHand(item)))
+                    .map(|(name, item)| (name, EquipItemTarget::Equipped(item)))
                     .chain(
                         inventory_items(state.controlled, &state.world, assets)
                             .into_iter()
-                            .map(|(name, item)| (name, WieldItemTarget::InInventory(item))),
+                            .map(|(name, item)| (name, EquipItemTarget::InInventory(item))),
                     )
                     .chain(
                         super::targets_by_proximity::<&ItemTypeId>(
@@ -79,24 +98,92 @@ pub fn commands(
                             assets,
                         )
                         .into_iter()
-                        .map(|(name, item)| (name, WieldItemTarget::OnGround(item))),
+                        .map(|(name, item)| (name, EquipItemTarget::OnGround(item))),
                     ),
                 |parse, item| parse.done_or_err(|| wield(item, state, assets)),
                 |input| {
-                    Err(format!(
-                        "There is no {input} that {} can wield.",
-                        NameData::find(&state.world, state.controlled, assets).definite(),
+                    Err(assets.messages.text(
+                        "item.wield.no_such_item",
+                        &[
+                            ("performer", &NameData::find(&state.world, state.controlled, assets).definite()),
+                            ("input", input),
+                        ],
+                    ))
+                },
+            )
+        }),
+        parse.literal("wear", |parse| {
+            parse.match_against(
+                worn_items(state.controlled, &state.world, assets)
+                    .into_iter()
+                    .map(|(name, item)| (name, EquipItemTarget::Equipped(item)))
+                    .chain(
+                        inventory_items(state.controlled, &state.world, assets)
+                            .into_iter()
+                            .map(|(name, item)| (name, EquipItemTarget::InInventory(item))),
+                    )
+                    .chain(
+                        super::targets_by_proximity::<&ItemTypeId>(
+                            character_pos,
+                            &state.world,
+                            assets,
+                        )
+                        .into_iter()
+                        .map(|(name, item)| (name, EquipItemTarget::OnGround(item))),
+                    ),
+                |parse, item| parse.done_or_err(|| wear(item, state, assets)),
+                |input| {
+                    Err(assets.messages.text(
+                        "item.wear.no_such_item",
+                        &[
+                            ("performer", &NameData::find(&state.world, state.controlled, assets).definite()),
+                            ("input", input),
+                        ],
                     ))
                 },
             )
         }),
+        parse.literal("store", |parse| {
+            parse.match_against(
+                inventory_items(state.controlled, &state.world, assets)
+                    .into_iter()
+                    .chain(items_in_hand(state.controlled, &state.world, assets)),
+                |parse, item| {
+                    first_match_or!(
+                        parse.literal("in ship", |parse| {
+                            parse.done_or_err(|| store(item, state, assets))
+                        });
+                        parse.default_err()
+                    )
+                },
+                |input| {
+                    Err(assets.messages.text(
+                        "ship.cargo.no_such_item",
+                        &[
+                            ("performer", &NameData::find(&state.world, state.controlled, assets).definite()),
+                            ("input", input),
+                        ],
+                    ))
+                },
+            )
+        }),
+        parse.literal("retrieve", |parse| {
+            parse.match_against(
+                stored_items(&state.world, assets),
+                |parse, item| parse.done_or_err(|| retrieve(item, state, assets)),
+                |input| Err(assets.messages.text("ship.cargo.no_such_stored_item", &[("input", input)])),
+            )
+        }),
+        parse.literal("cargo", |parse| {
+            parse.done_or_err(|| cargo(state, assets))
+        }),
         parse.literal("use", |parse| {
             parse.match_against(
                 items_in_hand(state.controlled, &state.world, assets)
                     .into_iter()
                     .chain(inventory_items(state.controlled, &state.world, assets)),
                 |parse, item| parse.done_or_err(|| use_item(item, state, assets)),
-                |input| Err(format!("No held item by the name \"{input}\".")),
+                |input| Err(assets.messages.text("item.use.no_such_item", &[("input", input)])),
             )
         }),
     )
@@ -116,6 +203,20 @@ fn inventory_items(character: Entity, world: &World, assets: &GameAssets) -> Vec
         .collect()
 }
 
+fn worn_items(character: Entity, world: &World, assets: &GameAssets) -> Vec<(String, Entity)> {
+    world
+        .query::<(Entity, &Held)>()
+        .with::<&ItemTypeId>()
+        .iter()
+        .filter(|&(_, held)| held.held_by(character) && held.is_worn())
+        .flat_map(|(entity, _)| {
+            command::entity_names(world.entity(entity).unwrap(), assets)
+                .into_iter()
+                .map(move |name| (name, entity))
+        })
+        .collect()
+}
+
 fn items_in_hand(character: Entity, world: &World, assets: &GameAssets) -> Vec<(String, Entity)> {
     world
         .query::<(Entity, &Held)>()
@@ -130,7 +231,18 @@ fn items_in_hand(character: Entity, world: &World, assets: &GameAssets) -> Vec<(
         .collect()
 }
 
-fn take_all(state: &GameState) -> Result<CommandResult, String> {
+fn stored_items(world: &World, assets: &GameAssets) -> Vec<(String, Entity)> {
+    area::stored_items(world)
+        .into_iter()
+        .flat_map(|item| {
+            command::entity_names(world.entity(item).unwrap(), assets)
+                .into_iter()
+                .map(move |name| (name, item))
+        })
+        .collect()
+}
+
+fn take_all(state: &GameState, assets: &GameAssets) -> Result<CommandResult, String> {
     let character_pos = *state.world.get::<&Pos>(state.controlled).unwrap();
     if !state
         .world
@@ -139,11 +251,11 @@ fn take_all(state: &GameState) -> Result<CommandResult, String> {
         .iter()
         .any(|pos| pos.is_in(character_pos.get_area()))
     {
-        return Err("There are no items to take here.".to_string());
+        return Err(assets.messages.text("item.take.nothing_here", &[]));
     }
 
     if !behavior::is_safe(&state.world, character_pos.get_area()) {
-        return Err("You should take care of all foes here before taking all items.".to_string());
+        return Err(assets.messages.text("item.take_all.not_safe", &[]));
     }
 
     command::action_result(Action::TakeAll)
@@ -175,9 +287,12 @@ fn give(
     assets: &GameAssets,
 ) -> Result<CommandResult, String> {
     if state.controlled == receiver {
-        return Err(format!(
-            "{} can't give an item to themselves.",
-            NameData::find(&state.world, state.controlled, assets).definite()
+        return Err(assets.messages.text(
+            "give.self",
+            &[(
+                "performer",
+                &NameData::find(&state.world, state.controlled, assets).definite(),
+            )],
         ));
     }
 
@@ -191,27 +306,103 @@ fn give(
     command::action_result(Action::GiveItem(item, receiver))
 }
 
-enum WieldItemTarget {
-    InHand(Entity),
+fn check_in_ship(state: &GameState, assets: &GameAssets) -> Result<(), String> {
+    let character_pos = *state.world.get::<&Pos>(state.controlled).unwrap();
+    if area::is_in_ship(character_pos, &state.world) {
+        Ok(())
+    } else {
+        Err(assets.messages.text(
+            "ship.cargo.not_in_ship",
+            &[(
+                "performer",
+                &NameData::find(&state.world, state.controlled, assets).definite(),
+            )],
+        ))
+    }
+}
+
+fn store(item: Entity, state: &GameState, assets: &GameAssets) -> Result<CommandResult, String> {
+    check_in_ship(state, assets)?;
+    if area::stored_items(&state.world).len() >= SHIP_CARGO_CAPACITY {
+        return Err(assets.messages.text(
+            "ship.cargo.full",
+            &[
+                (
+                    "performer",
+                    &NameData::find(&state.world, state.controlled, assets).definite(),
+                ),
+                (
+                    "item",
+                    &NameData::find(&state.world, item, assets).definite(),
+                ),
+            ],
+        ));
+    }
+
+    command::action_result(Action::StoreInShip(item))
+}
+
+fn retrieve(item: Entity, state: &GameState, assets: &GameAssets) -> Result<CommandResult, String> {
+    check_in_ship(state, assets)?;
+
+    command::action_result(Action::RetrieveFromShip(item))
+}
+
+fn cargo(state: &GameState, assets: &GameAssets) -> Result<CommandResult, String> {
+    check_in_ship(state, assets)?;
+    let stored_items = area::stored_items(&state.world);
+    let contents = if stored_items.is_empty() {
+        assets.messages.text("ship.cargo.empty", &[])
+    } else {
+        name::names_with_counts(
+            stored_items
+                .iter()
+                .map(|&item| NameIdData::find(&state.world, item)),
+            ArticleKind::One,
+            CountFormat::Numeric,
+            assets,
+        )
+        .join(", ")
+    };
+
+    Ok(CommandResult::Info(CommandInfo::Message(vec![
+        assets.messages.text(
+            "ship.cargo.header",
+            &[
+                ("count", &stored_items.len().to_string()),
+                ("capacity", &SHIP_CARGO_CAPACITY.to_string()),
+            ],
+        ),
+        contents,
+    ])))
+}
+
+enum EquipItemTarget {
+    Equipped(Entity),
     InInventory(Entity),
     OnGround(Entity),
 }
 
 fn wield(
-    item: WieldItemTarget,
+    item: EquipItemTarget,
     state: &GameState,
     assets: &GameAssets,
 ) -> Result<CommandResult, String> {
     let item = match item {
-        WieldItemTarget::InHand(item) => {
-            return Err(format!(
-                "{the_character} is already wielding a {item}.",
-                the_character = NameData::find(&state.world, state.controlled, assets).definite(),
-                item = NameData::find(&state.world, item, assets).base(),
+        EquipItemTarget::Equipped(item) => {
+            return Err(assets.messages.text(
+                "item.wield.already_wielding",
+                &[
+                    (
+                        "performer",
+                        &NameData::find(&state.world, state.controlled, assets).definite(),
+                    ),
+                    ("item", &NameData::find(&state.world, item, assets).base()),
+                ],
             ));
         }
-        WieldItemTarget::InInventory(item) => item,
-        WieldItemTarget::OnGround(item) => {
+        EquipItemTarget::InInventory(item) => item,
+        EquipItemTarget::OnGround(item) => {
             super::check_accessible_with_message(
                 item,
                 state.controlled,
@@ -229,9 +420,12 @@ fn wield(
         .get(&item_type)
         .is_none_or(|data| data.weapon.is_none())
     {
-        return Err(format!(
-            "{the_item} is not a wieldable item.",
-            the_item = NameData::find(&state.world, item, assets).definite(),
+        return Err(assets.messages.text(
+            "item.wield.not_wieldable",
+            &[(
+                "item",
+                &NameData::find(&state.world, item, assets).definite(),
+            )],
         ));
     }
 
@@ -241,6 +435,58 @@ fn wield(
     ))
 }
 
+fn wear(
+    item: EquipItemTarget,
+    state: &GameState,
+    assets: &GameAssets,
+) -> Result<CommandResult, String> {
+    let item = match item {
+        EquipItemTarget::Equipped(item) => {
+            return Err(assets.messages.text(
+                "item.wear.already_wearing",
+                &[
+                    (
+                        "performer",
+                        &NameData::find(&state.world, state.controlled, assets).definite(),
+                    ),
+                    ("item", &NameData::find(&state.world, item, assets).base()),
+                ],
+            ));
+        }
+        EquipItemTarget::InInventory(item) => item,
+        EquipItemTarget::OnGround(item) => {
+            super::check_accessible_with_message(
+                item,
+                state.controlled,
+                true,
+                &state.world,
+                assets,
+            )?;
+            item
+        }
+    };
+
+    let item_type = state.world.get::<&ItemTypeId>(item).unwrap();
+    if assets
+        .item_type_map
+        .get(&item_type)
+        .is_none_or(|data| data.armor.is_none())
+    {
+        return Err(assets.messages.text(
+            "item.wear.not_wearable",
+            &[(
+                "item",
+                &NameData::find(&state.world, item, assets).definite(),
+            )],
+        ));
+    }
+
+    command::action_result(Action::Wear(
+        item,
+        NameData::find(&state.world, item, assets),
+    ))
+}
+
 fn use_item(item: Entity, state: &GameState, assets: &GameAssets) -> Result<CommandResult, String> {
     let world = &state.world;
     let character = state.controlled;
@@ -254,25 +500,37 @@ fn use_item(item: Entity, state: &GameState, assets: &GameAssets) -> Result<Comm
         if matches!(usage, crate::asset::ItemUseType::Medkit { .. })
             && !world.get::<&Health>(character).unwrap().is_hurt()
         {
-            return Err(format!(
-                "{} is not hurt, and does not need to use the medkit.",
-                NameData::find(world, character, assets).definite(),
+            return Err(assets.messages.text(
+                "item.medkit.not_hurt",
+                &[(
+                    "performer",
+                    &NameData::find(world, character, assets).definite(),
+                )],
             ));
         }
         command::action_result(UseAction { item, use_time: 0 })
+    } else if item_data.is_some_and(|data| data.armor.is_some()) {
+        if item_ref.get::<&Held>().is_some_and(|held| held.is_worn()) {
+            Err(assets.messages.text(
+                "item.wear.already_worn",
+                &[("item", &NameData::find_by_ref(item_ref, assets).definite())],
+            ))
+        } else {
+            command::action_result(Action::Wear(item, NameData::find_by_ref(item_ref, assets)))
+        }
     } else if item_data.is_some_and(|data| data.weapon.is_some()) {
         if item_ref
             .get::<&Held>()
             .is_some_and(|held| held.is_in_hand())
         {
-            Err(format!(
-                "{} is already being held.",
-                NameData::find_by_ref(item_ref, assets).definite(),
+            Err(assets.messages.text(
+                "item.wield.already_held",
+                &[("item", &NameData::find_by_ref(item_ref, assets).definite())],
             ))
         } else {
             command::action_result(Action::Wield(item, NameData::find_by_ref(item_ref, assets)))
         }
     } else {
-        Err("The item can not be used in any meaningful way.".to_string())
+        Err(assets.messages.text("item.use.no_use", &[]))
     }
 }
@@ -25,14 +25,14 @@ pub fn parse(
                 parse.numeric(|parse, amount| {
                     parse.match_against(
                         store_entries(shopkeeper, amount, &assets.noun_data_map),
-                        |parse, item| parse.done_or_err(|| buy(item, amount, &assets.noun_data_map)),
-                        |input| Err(format!("\"{input}\" does not match an item in the store.")),
+                        |parse, item| parse.done_or_err(|| buy(item, amount, assets)),
+                        |input| Err(assets.messages.text("store.no_such_stock", &[("input", input)])),
                     )
                 });
                 parse.match_against(
                     store_entries(shopkeeper, 1, &assets.noun_data_map),
-                    |parse, item| parse.done_or_err(|| buy(item, 1, &assets.noun_data_map)),
-                    |input| Err(format!("\"{input}\" does not match an item in the store.")),
+                    |parse, item| parse.done_or_err(|| buy(item, 1, assets)),
+                    |input| Err(assets.messages.text("store.no_such_stock", &[("input", input)])),
                 )
             )
         }),
@@ -42,9 +42,12 @@ pub fn parse(
                     parse.match_against(
                         held_item_lists_by_plurality(character, true, world, assets),
                         |parse, items| parse.done_or_err(|| command::action_result(Action::Sell(items))),
-                        |input| Err(format!(
-                            "{} is holding no item by the name \"{input}\".",
-                            NameData::find(world, character, assets).definite(),
+                        |input| Err(assets.messages.text(
+                            "store.not_holding_item",
+                            &[
+                                ("performer", &NameData::find(world, character, assets).definite()),
+                                ("input", input),
+                            ],
                         )),
                     )
                 }),
@@ -54,20 +57,19 @@ pub fn parse(
                         |parse, items| parse.done_or_err(|| {
                             sell_count(count, prioritize_inventory(items, world), character, world, assets)
                         }),
-                        |input| Err(format!(
-                            "{} is holding no item by the name \"{input}\".",
-                            NameData::find(world, character, assets).definite(),
+                        |input| Err(assets.messages.text(
+                            "store.not_holding_item",
+                            &[
+                                ("performer", &NameData::find(world, character, assets).definite()),
+                                ("input", input),
+                            ],
                         )),
                     )
                 });
                 parse.match_against(
                     held_items(world, character, assets),
                     |parse, item| parse.done_or_err(|| command::action_result(Action::Sell(vec![item]))),
-                    |input| {
-                        Err(format!(
-                            "\"{input}\" does not match an item in your inventory.",
-                        ))
-                    },
+                    |input| Err(assets.messages.text("store.no_such_held_item", &[("input", input)])),
                 )
             )
         }),
@@ -75,7 +77,7 @@ pub fn parse(
                     parse.match_against(
                         store_entries(shopkeeper, 1, &assets.noun_data_map),
                         |parse, stock| parse.done_or_err(|| command::action_result(Action::AskAbout(stock.item.clone()))),
-                        |input| Err(format!("\"{input}\" does not match an item in the store.")),
+                        |input| Err(assets.messages.text("store.no_such_stock", &[("input", input)])),
                     )
         }),
         parse.literal("exit", |parse| {
@@ -108,11 +110,14 @@ fn store_entries<'a>(
         .collect::<Vec<_>>()
 }
 
-fn buy(stock: &StoreStock, amount: u16, noun_map: &NounDataMap) -> Result<CommandResult, String> {
+fn buy(stock: &StoreStock, amount: u16, assets: &GameAssets) -> Result<CommandResult, String> {
     if stock.quantity.subtracted(amount).is_none() {
-        return Err(format!(
-            "There are not enough {} in stock.",
-            noun_map.lookup(&stock.item.noun_id()).plural()
+        return Err(assets.messages.text(
+            "store.not_enough_in_stock",
+            &[(
+                "items",
+                assets.noun_data_map.lookup(&stock.item.noun_id()).plural(),
+            )],
         ));
     }
 
@@ -178,10 +183,18 @@ fn sell_count(
 ) -> Result<CommandResult, String> {
     let count = usize::from(count);
     if items.len() < count {
-        return Err(format!(
-            "{} does not have that many {}.",
-            NameData::find(world, character, assets).definite(),
-            NameData::find(world, *items.first().unwrap(), assets).plural(),
+        return Err(assets.messages.text(
+            "store.not_enough_held",
+            &[
+                (
+                    "performer",
+                    &NameData::find(world, character, assets).definite(),
+                ),
+                (
+                    "items",
+                    &NameData::find(world, *items.first().unwrap(), assets).plural(),
+                ),
+            ],
         ));
     }
     command::action_result(Action::Sell(items[0..count].to_owned()))
@@ -1,6 +1,6 @@
 use crate::asset::GameAssets;
 use crate::core::area::ShipControls;
-use crate::core::behavior::{Character, Hostile, Recruitable, Waiting};
+use crate::core::behavior::{Character, Hostile, Order, Recruitable, Waiting};
 use crate::core::inventory::Container;
 use crate::core::item::ItemTypeId;
 use crate::core::name::{Name, NameData};
@@ -88,6 +88,7 @@ pub enum InteractionType {
     Item,
     Container,
     Wieldable,
+    Wearable,
     Door,
     Forceable,
     ShipControls,
@@ -111,10 +112,16 @@ impl InteractionType {
             InteractionType::Item => vec![simple!("take {name}"), simple!("check {name}")],
             InteractionType::Container => vec![simple!("search {name}")],
             InteractionType::Wieldable => vec![simple!("wield {name}")],
+            InteractionType::Wearable => vec![simple!("wear {name}")],
             InteractionType::Door => vec![simple!("enter {name}")],
             InteractionType::Forceable => vec![simple!("force {name}")],
             InteractionType::ShipControls => {
-                vec![simple!("launch ship"), simple!("refuel ship")]
+                vec![
+                    simple!("launch ship"),
+                    simple!("refuel ship"),
+                    simple!("cargo"),
+                    simple!("upgrades"),
+                ]
             }
             InteractionType::Openable => vec![simple!("open {name}")],
             InteractionType::CrewMember => {
@@ -123,12 +130,14 @@ impl InteractionType {
                     simple!("status"),
                     simple!("rest"),
                     simple!("tell {name} to wait at ship"),
+                    simple!("tell {name} to retreat to ship"),
                     recursive!(inventory.iter().map(ItemProfile::name), "give {name} {}"),
                 ]
             }
             InteractionType::Controlled => {
                 vec![
                     simple!("status"),
+                    simple!("map"),
                     simple!("rest"),
                     simple!("wait"),
                     simple!("go to ship"),
@@ -139,6 +148,20 @@ impl InteractionType {
                             .map(ItemProfile::name),
                         "wield {}"
                     ),
+                    recursive!(
+                        inventory
+                            .iter()
+                            .filter(|item| item.is_wearable && !item.is_worn)
+                            .map(ItemProfile::name),
+                        "wear {}"
+                    ),
+                    recursive!(
+                        inventory
+                            .iter()
+                            .filter(|item| item.is_worn)
+                            .map(ItemProfile::name),
+                        "take off {}"
+                    ),
                     recursive!(
                         inventory
                             .iter()
@@ -179,6 +202,13 @@ pub fn interactions_for(
         {
             interactions.push(InteractionType::Wieldable);
         }
+        if assets
+            .item_type_map
+            .get(&item_type)
+            .is_some_and(|data| data.armor.is_some())
+        {
+            interactions.push(InteractionType::Wearable);
+        }
     }
 
     if entity_ref.satisfies::<&Container>() {
@@ -196,7 +226,7 @@ pub fn interactions_for(
     }
     if entity != state.controlled && entity_ref.satisfies::<(&CrewMember, &Character)>() {
         interactions.push(InteractionType::CrewMember);
-        if entity_ref.satisfies::<&Waiting>() {
+        if entity_ref.satisfies::<hecs::Or<&Waiting, &Order>>() {
             interactions.push(InteractionType::Waiting);
         } else {
             interactions.push(InteractionType::Following);
@@ -2,6 +2,8 @@ pub mod area;
 pub mod behavior;
 pub(crate) mod combat;
 pub mod display;
+pub mod effect;
+pub mod hazard;
 pub(crate) mod inventory;
 pub mod item;
 pub mod name;
@@ -80,12 +82,13 @@ pub mod store {
             .unwrap();
 
         view_buffer.add_change_message(
-            "\"Welcome to the store. What do you want to buy?\"".to_owned(),
+            view_buffer.assets.messages.text("store.welcome", &[]),
             state,
         );
     }
 }
 
+use crate::asset::GameAssets;
 use hecs::Entity;
 use serde::{Deserialize, Serialize};
 
@@ -94,11 +97,20 @@ pub const CREW_SIZE_LIMIT: usize = 3;
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CrewMember(pub Entity);
 
+/// The number of crew members that fit in the ship, which is raised by the crew bunks upgrade.
+pub(crate) fn crew_size_limit(world: &hecs::World) -> usize {
+    if area::has_ship_upgrade(world, area::ShipUpgrade::CrewBunks) {
+        CREW_SIZE_LIMIT + 1
+    } else {
+        CREW_SIZE_LIMIT
+    }
+}
+
 /// Checks if the crew has space for one more recruitment and indicates the outcome in the result.
-pub(crate) fn check_crew_size(world: &hecs::World) -> Result<(), &'static str> {
+pub(crate) fn check_crew_size(world: &hecs::World, assets: &GameAssets) -> Result<(), String> {
     let crew_size = world.query::<&CrewMember>().iter().count();
-    if crew_size >= CREW_SIZE_LIMIT {
-        Err("There is not enough room for another crew member.")
+    if crew_size >= crew_size_limit(world) {
+        Err(assets.messages.text("crew.no_room", &[]))
     } else {
         Ok(())
     }
@@ -181,18 +193,21 @@ pub struct IsCut;
 pub enum BlockType {
     Stuck,
     Sealed,
+    /// Opened by the key of the door pair, which is stored as a [`LockKey`].
+    Locked,
 }
 
 impl BlockType {
     pub fn variants() -> &'static [Self] {
         use BlockType::*;
-        &[Stuck, Sealed]
+        &[Stuck, Sealed, Locked]
     }
 
     pub fn description(self) -> &'static str {
         match self {
             BlockType::Stuck => "stuck",
             BlockType::Sealed => "sealed shut",
+            BlockType::Locked => "locked",
         }
     }
 
@@ -200,9 +215,15 @@ impl BlockType {
         match self {
             BlockType::Stuck => vec![item::Tool::Crowbar, item::Tool::Blowtorch],
             BlockType::Sealed => vec![item::Tool::Blowtorch],
+            BlockType::Locked => vec![],
         }
     }
 }
 
+/// The key of a locked door pair.
+/// Any item with this item type id or key tag may be used to unlock the door pair.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LockKey(pub String);
+
 /// Represents a dialogue asset path, starting from the dialogue directory and without the file ending.
 pub type DialogueId = String;
@@ -1,5 +1,10 @@
+use crate::asset::GameAssets;
 use crate::asset::background::ParallaxLayer;
+use crate::core::CrewMember;
+use crate::core::inventory::Held;
+use crate::core::item::ItemTypeId;
 use crate::core::position::{Coord, Pos};
+use crate::core::status::Health;
 use hecs::{Entity, World};
 use serde::{Deserialize, Serialize};
 
@@ -11,6 +16,59 @@ pub struct Area {
     pub background_offset: i32,
     pub extra_background_layers: Vec<ParallaxLayer<String>>,
     pub darkness: f32,
+    #[serde(default)]
+    pub pos_in_overview: (i32, i32),
+}
+
+/// Marks an area that has been visited by the crew, which makes it show up on the map.
+#[derive(Serialize, Deserialize)]
+pub struct Explored;
+
+pub(crate) fn mark_explored_areas(world: &mut World) {
+    let areas = world
+        .query::<&Pos>()
+        .with::<&CrewMember>()
+        .iter()
+        .map(|pos| pos.get_area())
+        .filter(|&area| !world.satisfies::<&Explored>(area))
+        .collect::<Vec<_>>();
+    for area in areas {
+        world.insert_one(area, Explored).unwrap();
+    }
+}
+
+/// Areas with at least this much darkness are too dark to see properly in without a light source.
+pub const DARKNESS_THRESHOLD: f32 = 0.5;
+
+/// Checks if the area is too dark to see properly in.
+/// An area is lit up by a light source that is either placed in the area or held by a creature in the area.
+pub fn is_dark(area: Entity, world: &World, assets: &GameAssets) -> bool {
+    let Ok(area_data) = world.get::<&Area>(area) else {
+        return false;
+    };
+    if area_data.darkness < DARKNESS_THRESHOLD {
+        return false;
+    }
+    !world
+        .query::<(&ItemTypeId, Option<&Pos>, Option<&Held>)>()
+        .iter()
+        .filter(|(item_type, _, _)| {
+            assets
+                .item_type_map
+                .get(item_type)
+                .is_some_and(|data| data.light_source)
+        })
+        .any(|(_, pos, held)| {
+            let item_area = match (pos, held) {
+                (Some(pos), _) => Some(pos.get_area()),
+                (None, Some(held)) if world.satisfies::<&Health>(held.holder) => world
+                    .get::<&Pos>(held.holder)
+                    .ok()
+                    .map(|pos| pos.get_area()),
+                _ => None,
+            };
+            item_area == Some(area)
+        })
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
@@ -40,11 +98,96 @@ pub struct ShipState {
     pub exit_pos: Pos,
     pub item_pos: Pos,
     pub dialogue_pos: [Pos; 2],
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub upgrades: Vec<ShipUpgrade>,
+}
+
+impl ShipState {
+    pub fn has_upgrade(&self, upgrade: ShipUpgrade) -> bool {
+        self.upgrades.contains(&upgrade)
+    }
+
+    /// The fuel that the ship needs before it can launch from a new location.
+    pub fn fuel_needed_on_landing(&self) -> FuelAmount {
+        if self.has_upgrade(ShipUpgrade::FuelRecycler) {
+            FuelAmount::OneCan
+        } else {
+            FuelAmount::TwoCans
+        }
+    }
+}
+
+/// Permanent improvements to the ship, which are bought with crew points at the ship controls.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShipUpgrade {
+    CrewBunks,
+    Medbay,
+    FuelRecycler,
+    Scanner,
+}
+
+impl ShipUpgrade {
+    pub fn variants() -> &'static [Self] {
+        use ShipUpgrade::*;
+        &[CrewBunks, Medbay, FuelRecycler, Scanner]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ShipUpgrade::CrewBunks => "crew bunks",
+            ShipUpgrade::Medbay => "medbay",
+            ShipUpgrade::FuelRecycler => "fuel recycler",
+            ShipUpgrade::Scanner => "scanner",
+        }
+    }
+
+    pub fn description(self, assets: &GameAssets) -> String {
+        let message_id = match self {
+            ShipUpgrade::CrewBunks => "ship.upgrade.description.crew_bunks",
+            ShipUpgrade::Medbay => "ship.upgrade.description.medbay",
+            ShipUpgrade::FuelRecycler => "ship.upgrade.description.fuel_recycler",
+            ShipUpgrade::Scanner => "ship.upgrade.description.scanner",
+        };
+        assets.messages.text(message_id, &[])
+    }
+
+    pub fn price(self) -> i32 {
+        match self {
+            ShipUpgrade::CrewBunks => 8000,
+            ShipUpgrade::Medbay => 6000,
+            ShipUpgrade::FuelRecycler => 10000,
+            ShipUpgrade::Scanner => 4000,
+        }
+    }
+}
+
+pub fn has_ship_upgrade(world: &World, upgrade: ShipUpgrade) -> bool {
+    world
+        .query::<&ShipState>()
+        .iter()
+        .any(|ship_state| ship_state.has_upgrade(upgrade))
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct ShipRoom;
 
+/// The number of items that fit in the cargo hold of the ship.
+pub const SHIP_CARGO_CAPACITY: usize = 12;
+
+/// Marks an item that is stored in the cargo hold of the ship.
+/// Stored items have neither a position nor a holder, and stay with the ship between locations.
+#[derive(Serialize, Deserialize)]
+pub struct StoredInShip;
+
+pub fn stored_items(world: &World) -> Vec<Entity> {
+    world
+        .query::<Entity>()
+        .with::<(&ItemTypeId, &StoredInShip)>()
+        .iter()
+        .collect()
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum FuelAmount {
     OneCan,
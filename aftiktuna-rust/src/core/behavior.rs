@@ -30,9 +30,30 @@ pub enum BadlyHurtBehavior {
     Determined,
 }
 
+/// Decides when a hostile creature engages the crew, and what it does while it is not fighting.
+/// A creature that has been hurt will always fight back, regardless of its profile.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BehaviorProfile {
+    /// Attacks the crew whenever it is aggressive.
+    #[default]
+    Standard,
+    /// Stays still and holds back until a crew member comes within the strike distance.
+    Ambush { strike_distance: u32 },
+    /// Only attacks inside areas with the given tag, and returns to such an area when outside of it.
+    Territorial { area_tag: Tag },
+    /// Picks up items lying around and lingers by corpses when not fighting,
+    /// and seeks out corpses in neighboring areas.
+    Scavenger,
+    /// Waits until enough creatures of the same species are in the area before attacking,
+    /// and seeks out other members of its pack in neighboring areas.
+    PackHunter { pack_size: u16 },
+}
+
 #[derive(Serialize, Deserialize)]
 pub enum Intention {
     Wield(hecs::Entity),
+    Wear(hecs::Entity),
     Force {
         door: hecs::Entity,
         assisted: hecs::Entity,
@@ -49,13 +70,24 @@ pub enum RepeatingAction {
     TakeAll,
     Rest,
     GoToShip,
-    UseItem { item: hecs::Entity, use_time: u16 },
+    UseItem {
+        item: hecs::Entity,
+        use_time: u16,
+    },
     ChargedAttack(hecs::Entity),
+    Treat {
+        item: hecs::Entity,
+        patient: hecs::Entity,
+        use_time: u16,
+    },
 }
 
 impl RepeatingAction {
     pub fn cancel_if_unsafe(self) -> bool {
-        !matches!(self, Self::ChargedAttack(_)) && !matches!(self, Self::UseItem { .. })
+        !matches!(
+            self,
+            Self::ChargedAttack(_) | Self::UseItem { .. } | Self::Treat { .. }
+        )
     }
 }
 
@@ -64,6 +96,20 @@ pub struct Waiting {
     pub at_ship: bool,
 }
 
+/// A tactical order given to a crew member, which the crew member follows over their own judgement
+/// until it is done, or until they are told to wait or follow.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum Order {
+    /// Attack the target until it is dead, following it into other areas.
+    Attack(hecs::Entity),
+    /// Stay with the guarded crew member, and attack the creatures closest to them.
+    Guard(hecs::Entity),
+    /// Head back to the ship without stopping to fight, and then wait there.
+    RetreatToShip,
+    /// Use a held medkit on the patient.
+    UseMedkitOn(hecs::Entity),
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CrewLossMemory {
     pub name: String,
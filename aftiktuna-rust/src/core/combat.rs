@@ -1,10 +1,11 @@
+use super::effect::EffectApplication;
 use super::item::ItemTypeId;
 use super::{SpeciesId, inventory};
 use crate::asset::GameAssets;
 use crate::asset::species::SpeciesData;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AttackKind {
     Light,
     Rash,
@@ -39,6 +40,13 @@ impl AttackKind {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HitType {
+    DirectHit,
+    GrazingHit,
+    Dodge,
+}
+
 #[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum UnarmedType {
@@ -50,21 +58,26 @@ pub enum UnarmedType {
 }
 
 impl UnarmedType {
-    pub fn attack_verb(self) -> &'static str {
-        match self {
-            Self::Bite | Self::Pounce => "jumps at",
-            Self::Scratch => "scratches at",
-            Self::Punch => "launches a punch at",
-            Self::Slash => "slashes at",
-        }
+    pub fn attack_verb(self, assets: &GameAssets) -> String {
+        let message_id = match self {
+            Self::Bite => "attack.unarmed.bite",
+            Self::Scratch => "attack.unarmed.scratch",
+            Self::Punch => "attack.unarmed.punch",
+            Self::Pounce => "attack.unarmed.pounce",
+            Self::Slash => "attack.unarmed.slash",
+        };
+        assets.messages.text(message_id, &[])
     }
 
-    pub fn hit_verb(self) -> &'static str {
-        match self {
-            Self::Bite => "bites",
-            Self::Scratch | Self::Punch | Self::Slash => "hits",
-            Self::Pounce => "pounces",
-        }
+    pub fn hit_verb(self, assets: &GameAssets) -> String {
+        let message_id = match self {
+            Self::Bite => "attack.unarmed.bite.hit",
+            Self::Scratch => "attack.unarmed.scratch.hit",
+            Self::Punch => "attack.unarmed.punch.hit",
+            Self::Pounce => "attack.unarmed.pounce.hit",
+            Self::Slash => "attack.unarmed.slash.hit",
+        };
+        assets.messages.text(message_id, &[])
     }
 }
 
@@ -91,12 +104,17 @@ impl AttackSet {
     }
 }
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct WeaponProperties {
     pub damage_mod: f32,
     pub attack_set: AttackSet,
     #[serde(default, skip_serializing_if = "crate::is_default")]
     pub stun_attack: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ranged: Option<RangedProperties>,
+    /// Status effects that a hit may apply to the target.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub effects: Vec<EffectApplication>,
 }
 
 impl Default for WeaponProperties {
@@ -105,10 +123,34 @@ impl Default for WeaponProperties {
             damage_mod: 2.,
             attack_set: AttackSet::Varied,
             stun_attack: false,
+            ranged: None,
+            effects: Vec::new(),
         }
     }
 }
 
+/// Properties of a weapon that can hit targets anywhere in the same area, as long as the attacker has ammunition.
+/// Without ammunition, the weapon can only be used in close combat.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RangedProperties {
+    /// The item type that is used up with each shot.
+    pub ammo: ItemTypeId,
+    /// Added to the hit difficulty for each step of distance beyond being adjacent to the target.
+    #[serde(default = "default_accuracy_falloff")]
+    pub accuracy_falloff: f32,
+}
+
+fn default_accuracy_falloff() -> f32 {
+    0.5
+}
+
+impl RangedProperties {
+    /// The added hit difficulty for a shot over the given distance.
+    pub fn distance_penalty(&self, distance: u32) -> i16 {
+        (self.accuracy_falloff * distance.saturating_sub(1) as f32).round() as i16
+    }
+}
+
 pub fn get_active_weapon_properties(
     world: &hecs::World,
     attacker: hecs::Entity,
@@ -120,7 +162,7 @@ pub fn get_active_weapon_properties(
                 .get::<&ItemTypeId>(item)
                 .ok()
                 .and_then(|item_type| assets.item_type_map.get(&item_type))
-                .and_then(|data| data.weapon)
+                .and_then(|data| data.weapon.clone())
         })
         .or_else(|| {
             assets
@@ -130,3 +172,57 @@ pub fn get_active_weapon_properties(
         })
         .unwrap_or_default()
 }
+
+/// The ranged properties of the wielded weapon of the attacker, if it is a ranged weapon
+/// and the attacker is carrying ammunition for it.
+pub fn get_usable_ranged_properties(
+    world: &hecs::World,
+    attacker: hecs::Entity,
+    assets: &GameAssets,
+) -> Option<RangedProperties> {
+    get_active_weapon_properties(world, attacker, assets)
+        .ranged
+        .filter(|ranged| {
+            inventory::is_holding(|item_type| *item_type == ranged.ammo, world, attacker)
+        })
+}
+
+/// Properties of armor, which reduce the damage taken by whoever wears it.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ArmorProperties {
+    /// Subtracted from the damage of each hit.
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub flat_reduction: f32,
+    /// The fraction of the remaining damage that is blocked.
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub percent_reduction: f32,
+    /// Subtracted from the agility of the wearer when dodging.
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub agility_penalty: i16,
+    /// Added to the difficulty of stunning the wearer.
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub stun_resistance: i16,
+}
+
+impl ArmorProperties {
+    pub fn reduce_damage(&self, damage: f32) -> f32 {
+        ((damage - self.flat_reduction) * (1. - self.percent_reduction.clamp(0., 1.))).max(0.)
+    }
+}
+
+/// The properties of the armor worn by the entity, or no protection if it wears no armor.
+pub fn get_worn_armor_properties(
+    world: &hecs::World,
+    entity: hecs::Entity,
+    assets: &GameAssets,
+) -> ArmorProperties {
+    inventory::get_worn(world, entity)
+        .and_then(|item| {
+            world
+                .get::<&ItemTypeId>(item)
+                .ok()
+                .and_then(|item_type| assets.item_type_map.get(&item_type))
+                .and_then(|data| data.armor.clone())
+        })
+        .unwrap_or_default()
+}
@@ -0,0 +1,357 @@
+use crate::asset::{AssetFile, GameAssets};
+use crate::core::name::{self, NameData, NameIdData};
+use crate::core::position::Pos;
+use crate::core::status::{self, Health, Morale, Stamina};
+use crate::view::event::GameEvent;
+use crate::view::{self, text};
+use hecs::{Entity, EntityRef, World};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusEffectKind {
+    Bleeding,
+    Poison,
+    Burning,
+    Adrenaline,
+    Exhaustion,
+}
+
+impl StatusEffectKind {
+    pub fn variants() -> &'static [Self] {
+        use StatusEffectKind::*;
+        &[Bleeding, Poison, Burning, Adrenaline, Exhaustion]
+    }
+
+    fn id(self) -> &'static str {
+        match self {
+            StatusEffectKind::Bleeding => "bleeding",
+            StatusEffectKind::Poison => "poison",
+            StatusEffectKind::Burning => "burning",
+            StatusEffectKind::Adrenaline => "adrenaline",
+            StatusEffectKind::Exhaustion => "exhaustion",
+        }
+    }
+
+    /// The display name of the effect from its data, or its id if the data is missing.
+    fn name(self, assets: &GameAssets) -> &str {
+        assets
+            .status_effect_map
+            .get(&self)
+            .map_or(self.id(), |data| &data.name)
+    }
+}
+
+/// How a new application of an effect combines with the same effect already being active.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StackingRule {
+    /// The remaining duration is reset to the longer of the two durations.
+    #[default]
+    Refresh,
+    /// The new duration is added to the remaining duration.
+    Extend,
+    /// The effect gains a stack, up to the maximum, and its duration is refreshed.
+    /// Per-tick changes are multiplied by the number of stacks.
+    Intensify,
+}
+
+/// Changes applied to the affected creature each tick, per stack of the effect.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PerTickChanges {
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub damage: f32,
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub stamina_drain: i16,
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub stamina_recovery: i16,
+    /// Positive values lift morale, while negative values lower it.
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub morale: f32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StatusEffectData {
+    pub name: String,
+    #[serde(default)]
+    pub stacking: StackingRule,
+    #[serde(default = "default_max_stacks")]
+    pub max_stacks: u16,
+    #[serde(default)]
+    pub per_tick: PerTickChanges,
+}
+
+fn default_max_stacks() -> u16 {
+    1
+}
+
+pub const STATUS_EFFECTS_FILE: AssetFile<HashMap<StatusEffectKind, StatusEffectData>> =
+    AssetFile::new("status_effects.json");
+
+/// An effect applied by a weapon, an unarmed attack or a consumable item.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EffectApplication {
+    pub effect: StatusEffectKind,
+    pub duration: u16,
+    /// The chance for the effect to be applied by a hit. Consumables always apply their effects.
+    #[serde(default = "default_chance", skip_serializing_if = "is_certain")]
+    pub chance: f32,
+}
+
+fn default_chance() -> f32 {
+    1.
+}
+
+fn is_certain(chance: &f32) -> bool {
+    *chance >= 1.
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ActiveEffect {
+    pub kind: StatusEffectKind,
+    pub remaining: u16,
+    pub stacks: u16,
+}
+
+/// The status effects currently active on a creature.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct StatusEffects(pub Vec<ActiveEffect>);
+
+impl StatusEffects {
+    /// Short descriptions of the active effects, such as "Bleeding (3 turns)".
+    pub fn descriptions(&self, assets: &GameAssets) -> Vec<String> {
+        self.0
+            .iter()
+            .map(|effect| {
+                let name = effect.kind.name(assets);
+                let turns = effect.remaining.to_string();
+                if effect.stacks > 1 {
+                    assets.messages.text(
+                        "status_effect.description.stacked",
+                        &[
+                            ("effect", name),
+                            ("stacks", &effect.stacks.to_string()),
+                            ("turns", &turns),
+                        ],
+                    )
+                } else {
+                    assets.messages.text(
+                        "status_effect.description",
+                        &[("effect", name), ("turns", &turns)],
+                    )
+                }
+            })
+            .collect()
+    }
+}
+
+pub fn has_effect(entity_ref: EntityRef, kind: StatusEffectKind) -> bool {
+    entity_ref
+        .get::<&StatusEffects>()
+        .is_some_and(|effects| effects.0.iter().any(|effect| effect.kind == kind))
+}
+
+/// Applies the effect to the entity, following the stacking rule of the effect.
+/// Returns true if the effect was not already active.
+pub fn apply_effect(
+    world: &mut World,
+    entity: Entity,
+    application: &EffectApplication,
+    assets: &GameAssets,
+) -> bool {
+    let Some(data) = assets.status_effect_map.get(&application.effect) else {
+        return false;
+    };
+    if world.get::<&StatusEffects>(entity).is_err() {
+        let _ = world.insert_one(entity, StatusEffects::default());
+    }
+    let Ok(mut effects) = world.get::<&mut StatusEffects>(entity) else {
+        return false;
+    };
+
+    if let Some(active) = effects
+        .0
+        .iter_mut()
+        .find(|effect| effect.kind == application.effect)
+    {
+        match data.stacking {
+            StackingRule::Refresh => {
+                active.remaining = active.remaining.max(application.duration);
+            }
+            StackingRule::Extend => {
+                active.remaining = active.remaining.saturating_add(application.duration);
+            }
+            StackingRule::Intensify => {
+                active.stacks = (active.stacks + 1).min(data.max_stacks.max(1));
+                active.remaining = active.remaining.max(application.duration);
+            }
+        }
+        false
+    } else {
+        effects.0.push(ActiveEffect {
+            kind: application.effect,
+            remaining: application.duration,
+            stacks: 1,
+        });
+        true
+    }
+}
+
+pub fn remove_effect(world: &mut World, entity: Entity, kind: StatusEffectKind) -> bool {
+    let Ok(mut effects) = world.get::<&mut StatusEffects>(entity) else {
+        return false;
+    };
+    let count = effects.0.len();
+    effects.0.retain(|effect| effect.kind != kind);
+    effects.0.len() != count
+}
+
+/// Rolls the chance of each effect of a hit and applies those that succeed to the target.
+/// Returns the effects that were not already active on the target.
+pub(crate) fn apply_hit_effects(
+    world: &mut World,
+    target: Entity,
+    applications: &[EffectApplication],
+    rng: &mut impl Rng,
+    assets: &GameAssets,
+) -> Vec<StatusEffectKind> {
+    applications
+        .iter()
+        .filter(|application| rng.random_bool(f64::from(application.chance.clamp(0., 1.))))
+        .filter(|application| apply_effect(world, target, application, assets))
+        .map(|application| application.effect)
+        .collect()
+}
+
+pub fn applied_message(
+    kind: StatusEffectKind,
+    entity: Entity,
+    world: &World,
+    assets: &GameAssets,
+) -> String {
+    assets.messages.text(
+        &format!("status_effect.{}.applied", kind.id()),
+        &[("entity", &NameData::find(world, entity, assets).definite())],
+    )
+}
+
+pub fn ended_message(kind: StatusEffectKind, entities: &str, assets: &GameAssets) -> String {
+    assets.messages.text(
+        &format!("status_effect.{}.ended", kind.id()),
+        &[("entities", entities)],
+    )
+}
+
+/// Applies the per-tick changes of all active effects, counts down their durations
+/// and removes the effects that have run out.
+pub(crate) fn tick_status_effects(
+    world: &mut World,
+    view_buffer: &mut view::Buffer,
+    character: Entity,
+) {
+    let assets = view_buffer.assets;
+    let player_area = world.get::<&Pos>(character).unwrap().get_area();
+    let affected_entities = world
+        .query::<(Entity, &StatusEffects, &Pos, &Health)>()
+        .iter()
+        .filter(|&(_, _, _, health)| health.is_alive())
+        .map(|(entity, effects, pos, _)| (entity, pos.get_area(), effects.0.clone()))
+        .collect::<Vec<_>>();
+
+    let mut killed_entities = Vec::new();
+    for (entity, area, effects) in affected_entities {
+        let entity_ref = world.entity(entity).unwrap();
+        for effect in effects {
+            let Some(data) = assets.status_effect_map.get(&effect.kind) else {
+                continue;
+            };
+            if apply_per_tick_changes(entity_ref, &data.per_tick, effect.stacks) {
+                killed_entities.push((entity, area, effect.kind));
+                break;
+            }
+        }
+    }
+
+    let mut ended_effects: Vec<(StatusEffectKind, Entity, bool)> = Vec::new();
+    for (entity, effects, pos, health) in
+        world.query_mut::<(Entity, &mut StatusEffects, &Pos, &Health)>()
+    {
+        if health.is_dead() {
+            effects.0.clear();
+            continue;
+        }
+        effects.0.retain_mut(|effect| {
+            effect.remaining = effect.remaining.saturating_sub(1);
+            let is_active = effect.remaining > 0;
+            if !is_active {
+                ended_effects.push((effect.kind, entity, pos.is_in(player_area)));
+            }
+            is_active
+        });
+    }
+
+    for &(effect, entity, _) in &ended_effects {
+        view_buffer.add_event(GameEvent::StatusEffectEnded { entity, effect });
+    }
+    for &kind in StatusEffectKind::variants() {
+        let names = ended_effects
+            .iter()
+            .filter(|&&(ended_kind, _, is_visible)| ended_kind == kind && is_visible)
+            .map(|&(_, entity, _)| NameIdData::find(world, entity))
+            .collect::<Vec<_>>();
+        if names.is_empty() {
+            continue;
+        }
+        view_buffer.messages.add(ended_message(
+            kind,
+            &text::join_elements(name::names_with_counts(
+                names,
+                name::ArticleKind::The,
+                name::CountFormat::Text,
+                assets,
+            )),
+            assets,
+        ));
+    }
+    for (entity, area, kind) in killed_entities {
+        status::clear_dead_creature(world, entity);
+        if area == player_area {
+            view_buffer.messages.add(assets.messages.text(
+                "status_effect.killed",
+                &[
+                    ("entity", &NameData::find(world, entity, assets).definite()),
+                    ("effect", kind.name(assets)),
+                ],
+            ));
+        }
+        view_buffer.add_event(GameEvent::Died { entity });
+    }
+}
+
+/// Applies the changes of one tick of an effect. Returns true if the creature was killed by the effect.
+fn apply_per_tick_changes(entity_ref: EntityRef, changes: &PerTickChanges, stacks: u16) -> bool {
+    if changes.damage > 0.
+        && let Some(mut health) = entity_ref.get::<&mut Health>()
+    {
+        health.take_damage(changes.damage * f32::from(stacks), entity_ref);
+        if health.is_dead() {
+            return true;
+        }
+    }
+    if let Some(mut stamina) = entity_ref.get::<&mut Stamina>() {
+        let stacks = stacks as i16;
+        stamina.drain(changes.stamina_drain * stacks);
+        stamina.recover(changes.stamina_recovery * stacks);
+    }
+    if let Some(mut morale) = entity_ref.get::<&mut Morale>() {
+        let intensity = changes.morale * f32::from(stacks);
+        if intensity > 0. {
+            morale.apply_positive_effect(intensity, Morale::SHALLOW_DEPTH);
+        } else if intensity < 0. {
+            morale.apply_negative_effect(-intensity, Morale::SHALLOW_DEPTH);
+        }
+    }
+    false
+}
@@ -0,0 +1,190 @@
+use crate::asset::GameAssets;
+use crate::core::inventory;
+use crate::core::name::{self, NameData, NameIdData};
+use crate::core::position::{Coord, Pos};
+use crate::core::status::{self, Health, Stamina};
+use crate::view::event::GameEvent;
+use crate::view::{self, text};
+use hecs::{Entity, World};
+use serde::{Deserialize, Serialize};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HazardKind {
+    ToxicGas,
+    Fire,
+    Radiation,
+    UnstableFloor,
+}
+
+impl HazardKind {
+    pub fn variants() -> &'static [Self] {
+        use HazardKind::*;
+        &[ToxicGas, Fire, Radiation, UnstableFloor]
+    }
+
+    pub fn name(self, assets: &GameAssets) -> String {
+        let message_id = match self {
+            HazardKind::ToxicGas => "hazard.name.toxic_gas",
+            HazardKind::Fire => "hazard.name.fire",
+            HazardKind::Radiation => "hazard.name.radiation",
+            HazardKind::UnstableFloor => "hazard.name.unstable_floor",
+        };
+        assets.messages.text(message_id, &[])
+    }
+
+    /// The damage dealt each tick to an exposed creature.
+    fn damage(self) -> f32 {
+        match self {
+            HazardKind::ToxicGas => 1.,
+            HazardKind::Fire => 2.,
+            HazardKind::Radiation => 0.5,
+            HazardKind::UnstableFloor => 0.,
+        }
+    }
+
+    /// The stamina drained each tick from an exposed creature.
+    fn stamina_drain(self) -> i16 {
+        match self {
+            HazardKind::ToxicGas => 1,
+            HazardKind::Fire | HazardKind::Radiation => 0,
+            HazardKind::UnstableFloor => 2,
+        }
+    }
+
+    fn message_id(self) -> &'static str {
+        match self {
+            HazardKind::ToxicGas => "hazard.toxic_gas",
+            HazardKind::Fire => "hazard.fire",
+            HazardKind::Radiation => "hazard.radiation",
+            HazardKind::UnstableFloor => "hazard.unstable_floor",
+        }
+    }
+}
+
+/// A hazard that covers either a whole area, or the coordinates within the radius of a center coordinate.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Hazard {
+    pub kind: HazardKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coord: Option<Coord>,
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub radius: Coord,
+}
+
+impl Hazard {
+    pub fn covers(&self, coord: Coord) -> bool {
+        self.coord
+            .is_none_or(|center| center.abs_diff(coord) <= self.radius)
+    }
+}
+
+/// The hazards present in an area, attached to the area entity.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AreaHazards(pub Vec<Hazard>);
+
+pub fn hazards_at(pos: Pos, world: &World) -> Vec<HazardKind> {
+    world
+        .get::<&AreaHazards>(pos.get_area())
+        .map(|hazards| {
+            hazards
+                .0
+                .iter()
+                .filter(|hazard| hazard.covers(pos.get_coord()))
+                .map(|hazard| hazard.kind)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub fn is_protected(entity: Entity, kind: HazardKind, world: &World, assets: &GameAssets) -> bool {
+    inventory::is_holding(
+        |item_type| {
+            assets
+                .item_type_map
+                .get(item_type)
+                .is_some_and(|data| data.hazard_protection.contains(&kind))
+        },
+        world,
+        entity,
+    )
+}
+
+/// Applies the effects of hazards to all living creatures standing in them,
+/// unless they are carrying an item that protects against the hazard.
+pub(crate) fn apply_hazard_effects(
+    world: &mut World,
+    view_buffer: &mut view::Buffer,
+    character: Entity,
+) {
+    let assets = view_buffer.assets;
+    let player_area = world.get::<&Pos>(character).unwrap().get_area();
+    let exposed_entities = world
+        .query::<(Entity, &Pos, &Health)>()
+        .iter()
+        .filter(|&(_, _, health)| health.is_alive())
+        .flat_map(|(entity, pos, _)| {
+            hazards_at(*pos, world)
+                .into_iter()
+                .map(move |kind| (entity, pos.get_area(), kind))
+        })
+        .filter(|&(entity, _, kind)| !is_protected(entity, kind, world, assets))
+        .collect::<Vec<_>>();
+
+    let mut visible_exposures: Vec<(HazardKind, Vec<NameIdData>)> = Vec::new();
+    let mut killed_entities = Vec::new();
+    for (entity, area, kind) in exposed_entities {
+        let entity_ref = world.entity(entity).unwrap();
+        if let Some(mut health) = entity_ref.get::<&mut Health>()
+            && health.is_alive()
+            && kind.damage() > 0.
+        {
+            health.take_damage(kind.damage(), entity_ref);
+            if health.is_dead() {
+                killed_entities.push((entity, area, kind));
+            }
+        }
+        if let Some(mut stamina) = entity_ref.get::<&mut Stamina>() {
+            stamina.drain(kind.stamina_drain());
+        }
+
+        if area == player_area {
+            let name = NameIdData::find(world, entity);
+            match visible_exposures
+                .iter_mut()
+                .find(|(exposure_kind, _)| *exposure_kind == kind)
+            {
+                Some((_, names)) => names.push(name),
+                None => visible_exposures.push((kind, vec![name])),
+            }
+        }
+    }
+
+    for (kind, names) in visible_exposures {
+        view_buffer.messages.add(assets.messages.text(
+            kind.message_id(),
+            &[(
+                "entities",
+                &text::join_elements(name::names_with_counts(
+                    names,
+                    name::ArticleKind::The,
+                    name::CountFormat::Text,
+                    assets,
+                )),
+            )],
+        ));
+    }
+    for (entity, area, kind) in killed_entities {
+        status::clear_dead_creature(world, entity);
+        if area == player_area {
+            view_buffer.messages.add(assets.messages.text(
+                "hazard.killed",
+                &[
+                    ("entity", &NameData::find(world, entity, assets).definite()),
+                    ("hazard", &kind.name(assets)),
+                ],
+            ));
+        }
+        view_buffer.add_event(GameEvent::Died { entity });
+    }
+}
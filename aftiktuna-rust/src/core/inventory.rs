@@ -1,3 +1,5 @@
+use crate::asset::{GameAssets, ItemTypeData};
+use crate::core::LockKey;
 use crate::core::item::{ItemTypeId, Tool};
 use crate::core::position::Pos;
 use hecs::{Entity, World};
@@ -7,6 +9,8 @@ use serde::{Deserialize, Serialize};
 pub struct Held {
     pub holder: Entity,
     in_hand: bool,
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    worn: bool,
 }
 
 impl Held {
@@ -18,14 +22,19 @@ impl Held {
         self.in_hand
     }
 
+    pub fn is_worn(&self) -> bool {
+        self.worn
+    }
+
     pub fn is_in_inventory(&self, holder: Entity) -> bool {
-        self.held_by(holder) && !self.in_hand
+        self.held_by(holder) && !self.in_hand && !self.worn
     }
 
     pub fn in_inventory(holder: Entity) -> Self {
         Self {
             holder,
             in_hand: false,
+            worn: false,
         }
     }
 
@@ -33,6 +42,15 @@ impl Held {
         Self {
             holder,
             in_hand: true,
+            worn: false,
+        }
+    }
+
+    pub fn worn(holder: Entity) -> Self {
+        Self {
+            holder,
+            in_hand: false,
+            worn: true,
         }
     }
 }
@@ -48,6 +66,21 @@ pub fn is_holding(
         .any(|(item_type, held)| held.held_by(holder) && item_type_matcher(item_type))
 }
 
+pub fn is_holding_key(world: &World, holder: Entity, key: &LockKey, assets: &GameAssets) -> bool {
+    is_holding(|item_type| item_type.fits_lock(key, assets), world, holder)
+}
+
+/// Finds a medkit held by the holder, either in their inventory or wielded.
+pub fn find_held_medkit(world: &World, holder: Entity, assets: &GameAssets) -> Option<Entity> {
+    get_held(world, holder).into_iter().find(|&item| {
+        world
+            .get::<&ItemTypeId>(item)
+            .ok()
+            .and_then(|item_type| assets.item_type_map.get(&item_type))
+            .is_some_and(ItemTypeData::is_medkit)
+    })
+}
+
 pub fn is_holding_tool(world: &World, holder: Entity, requested_tool: Tool) -> bool {
     world
         .query::<(&ItemTypeId, &Held)>()
@@ -88,6 +121,14 @@ pub fn get_wielded(world: &World, holder: Entity) -> Option<Entity> {
         .map(|(item, _)| item)
 }
 
+pub fn get_worn(world: &World, holder: Entity) -> Option<Entity> {
+    world
+        .query::<(Entity, &Held)>()
+        .iter()
+        .find(|(_, held)| held.held_by(holder) && held.worn)
+        .map(|(item, _)| item)
+}
+
 pub fn consume_one(
     item_type_matcher: impl Fn(&ItemTypeId) -> bool,
     world: &mut World,
@@ -108,6 +149,14 @@ pub fn unwield_if_needed(world: &mut World, holder: Entity) {
         .for_each(|held| held.in_hand = false);
 }
 
+pub fn take_off_armor_if_needed(world: &mut World, holder: Entity) {
+    world
+        .query_mut::<&mut Held>()
+        .into_iter()
+        .filter(|held| held.held_by(holder))
+        .for_each(|held| held.worn = false);
+}
+
 pub fn drop_all_items(world: &mut World, entity: Entity) {
     let pos = *world.get::<&Pos>(entity).unwrap();
     let items = get_held(world, entity);
@@ -1,5 +1,6 @@
 use super::display::ModelId;
 use crate::asset::GameAssets;
+use crate::core::LockKey;
 use crate::core::name::NounId;
 use crate::view::text;
 use hecs::{Component, Entity, EntityBuilder, EntityRef, World};
@@ -13,15 +14,14 @@ pub enum Tool {
 }
 
 impl Tool {
-    pub fn into_message(self, character_name: &str) -> String {
-        match self {
-            Tool::Crowbar => {
-                format!("{character_name} used their crowbar and forced open the door.")
-            }
-            Tool::Blowtorch => {
-                format!("{character_name} used their blowtorch and cut open the door.")
-            }
-        }
+    pub fn into_message(self, character_name: &str, assets: &GameAssets) -> String {
+        let message_id = match self {
+            Tool::Crowbar => "door.force.crowbar",
+            Tool::Blowtorch => "door.force.blowtorch",
+        };
+        assets
+            .messages
+            .text(message_id, &[("performer", character_name)])
     }
 
     pub fn matches(self, item_type: &ItemTypeId) -> bool {
@@ -76,6 +76,15 @@ impl ItemTypeId {
         self.0 == "cursed_nail"
     }
 
+    pub fn fits_lock(&self, key: &LockKey, assets: &GameAssets) -> bool {
+        self.0 == key.0
+            || assets
+                .item_type_map
+                .get(self)
+                .and_then(|data| data.key_tag.as_ref())
+                .is_some_and(|key_tag| *key_tag == key.0)
+    }
+
     pub fn noun_id(&self) -> NounId {
         NounId(self.0.clone())
     }
@@ -96,6 +105,12 @@ impl ItemTypeId {
     }
 }
 
+impl From<&str> for ItemTypeId {
+    fn from(value: &str) -> Self {
+        Self(value.to_owned())
+    }
+}
+
 impl Display for ItemTypeId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         self.0.fmt(f)
@@ -117,8 +132,33 @@ pub(crate) fn description(item_ref: EntityRef, assets: &GameAssets) -> Vec<Strin
     let item_type = item_ref.get::<&ItemTypeId>().unwrap();
     let item_type_data = assets.item_type_map.get(&item_type);
 
-    if let Some(weapon_properties) = item_type_data.and_then(|data| data.weapon) {
-        messages.push(format!("Weapon value: {}", weapon_properties.damage_mod));
+    if let Some(weapon_properties) = item_type_data.and_then(|data| data.weapon.as_ref()) {
+        messages.push(assets.messages.text(
+            "item.description.weapon_value",
+            &[("value", &weapon_properties.damage_mod.to_string())],
+        ));
+        if let Some(ranged) = &weapon_properties.ranged {
+            messages.push(assets.messages.text(
+                "item.description.ranged",
+                &[(
+                    "ammo",
+                    assets.noun_data_map.lookup(&ranged.ammo.noun_id()).plural(),
+                )],
+            ));
+        }
+    }
+
+    if let Some(armor) = item_type_data.and_then(|data| data.armor.as_ref()) {
+        messages.push(assets.messages.text(
+            "item.description.armor",
+            &[
+                ("flat", &armor.flat_reduction.to_string()),
+                (
+                    "percent",
+                    &(armor.percent_reduction * 100.).round().to_string(),
+                ),
+            ],
+        ));
     }
 
     if let Some(extra_description) = item_type_data.and_then(|data| data.extra_description.as_ref())
@@ -127,7 +167,7 @@ pub(crate) fn description(item_ref: EntityRef, assets: &GameAssets) -> Vec<Strin
     }
 
     if item_type_data.is_some_and(|data| data.price.is_some()) {
-        messages.push("Can be sold at a store.".into());
+        messages.push(assets.messages.text("item.description.sellable", &[]));
     }
     messages
 }
@@ -4,8 +4,8 @@ use crate::asset::GameAssets;
 use crate::asset::color::SpeciesColorMap;
 use crate::core::SpeciesId;
 use hecs::{Entity, EntityRef, World};
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 use std::fmt::Display;
 use std::ops::Deref;
 
@@ -148,7 +148,8 @@ impl NameData {
         match self {
             Self::Name(name) => name.clone(),
             Self::Noun(adjective, noun) => format!(
-                "the {adjective}{entity}",
+                "{the} {adjective}{entity}",
+                the = noun.definite_article(),
                 adjective = format_option_with_space(adjective.as_ref()),
                 entity = noun.singular
             ),
@@ -271,7 +272,8 @@ impl NameWithAttribute {
             NameData::Name(name) => name.to_owned(),
             NameData::Noun(adjective, noun) => {
                 format!(
-                    "the {adjective}{attribute}{entity}",
+                    "{the} {adjective}{attribute}{entity}",
+                    the = noun.definite_article(),
                     adjective = format_option_with_space(adjective.as_ref()),
                     attribute = format_option_with_space(self.1),
                     entity = noun.singular
@@ -322,6 +324,9 @@ pub struct NounData {
     plural: String,
     #[serde(default)]
     article: IndefiniteArticle,
+    /// Replaces "the" for this noun, for languages where the definite article depends on the noun.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    definite_article: Option<String>,
 }
 
 impl Default for NounData {
@@ -330,11 +335,16 @@ impl Default for NounData {
             singular: "???".to_string(),
             plural: "???".to_string(),
             article: Default::default(),
+            definite_article: None,
         }
     }
 }
 
 impl NounData {
+    pub fn definite_article(&self) -> &str {
+        self.definite_article.as_deref().unwrap_or("the")
+    }
+
     pub fn singular(&self) -> &str {
         &self.singular
     }
@@ -365,13 +375,15 @@ impl NounData {
         if article == ArticleKind::The {
             if count == 1 {
                 format!(
-                    "the {adjective}{name}",
+                    "{the} {adjective}{name}",
+                    the = self.definite_article(),
                     adjective = format_option_with_space(adjective),
                     name = self.singular(),
                 )
             } else {
                 format!(
-                    "the {count} {adjective}{name}",
+                    "{the} {count} {adjective}{name}",
+                    the = self.definite_article(),
                     count = format.apply(count),
                     adjective = format_option_with_space(adjective),
                     name = self.for_count(count),
@@ -439,20 +451,21 @@ pub(crate) enum ArticleKind {
     One,
 }
 
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub(crate) enum IndefiniteArticle {
-    #[default]
-    A,
-    An,
+/// The indefinite article of a noun, such as "a" or "an".
+/// Any word may be used, so that nouns in other languages can use their own articles.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub(crate) struct IndefiniteArticle(String);
+
+impl Default for IndefiniteArticle {
+    fn default() -> Self {
+        Self("a".to_owned())
+    }
 }
 
 impl Display for IndefiniteArticle {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(match self {
-            IndefiniteArticle::A => "a",
-            IndefiniteArticle::An => "an",
-        })
+        f.write_str(&self.0)
     }
 }
 
@@ -463,7 +476,7 @@ pub(crate) fn names_with_counts(
     assets: &GameAssets,
 ) -> Vec<String> {
     let mut names = Vec::new();
-    let mut nouns = HashMap::new();
+    let mut nouns = IndexMap::new();
 
     for name_data in data {
         match name_data {
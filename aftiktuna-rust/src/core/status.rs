@@ -1,13 +1,14 @@
-use super::behavior::{BadlyHurtBehavior, Character, CrewLossMemory, TalkState};
+use super::behavior::{BadlyHurtBehavior, Character, CrewLossMemory, Hostile, TalkState};
+use super::combat::ArmorProperties;
 use super::item::ItemTypeId;
 use super::name::NameWithAttribute;
-use super::position::Pos;
+use super::position::{OccupiesSpace, Pos};
 use super::{CrewMember, SpeciesId};
 use crate::asset::{GameAssets, ItemTypeData};
 use crate::view;
 use hecs::{CommandBuffer, Entity, EntityRef, World};
 use serde::{Deserialize, Serialize};
-use std::cmp::min;
+use std::cmp::{max, min};
 use std::collections::HashSet;
 use std::fmt::Display;
 
@@ -93,7 +94,7 @@ impl Stats {
         Err(OutsideBounds)
     }
 
-    pub fn agility_for_dodging(&self, entity_ref: EntityRef) -> i16 {
+    pub fn agility_for_dodging(&self, entity_ref: EntityRef, armor: &ArmorProperties) -> i16 {
         let morale = entity_ref
             .get::<&Morale>()
             .as_deref()
@@ -106,6 +107,7 @@ impl Stats {
                 0
             }
             + morale.dodge_mod()
+            - armor.agility_penalty
     }
 }
 
@@ -345,6 +347,12 @@ pub fn is_alive_ref(entity_ref: EntityRef) -> bool {
     }
 }
 
+/// Removes the components that only apply to living creatures from a creature that just died.
+pub(crate) fn clear_dead_creature(world: &mut World, entity: Entity) {
+    let _ = world.remove_one::<OccupiesSpace>(entity);
+    let _ = world.remove_one::<Hostile>(entity);
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Stamina {
     dodge_stamina: i16,
@@ -383,6 +391,14 @@ impl Stamina {
     pub fn on_move(&mut self) {
         self.dodge_stamina -= 1;
     }
+
+    pub fn drain(&mut self, amount: i16) {
+        self.dodge_stamina = max(self.dodge_stamina - amount, 0);
+    }
+
+    pub fn recover(&mut self, amount: i16) {
+        self.dodge_stamina = min(self.dodge_stamina + amount, self.max);
+    }
 }
 
 #[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
@@ -556,7 +572,7 @@ pub(crate) fn apply_morale_effects_from_crew_state(
             assets
                 .item_type_map
                 .get(item_type)
-                .and_then(|data| data.weapon)
+                .and_then(|data| data.weapon.clone())
         })
         .map(|weapon_properties| {
             (weapon_properties.damage_mod - 1.
@@ -643,22 +659,20 @@ pub(crate) fn detect_low_health(
             if entity != character {
                 let the_entity =
                     NameWithAttribute::lookup_by_ref(entity_ref, view_buffer.assets).definite();
+                let message_id = match entity_ref
+                    .get::<&SpeciesId>()
+                    .and_then(|species| view_buffer.assets.species_data_map.get(&species))
+                    .and_then(|species_data| species_data.badly_hurt_behavior)
+                {
+                    Some(BadlyHurtBehavior::Fearful) => "status.badly_hurt.fearful",
+                    Some(BadlyHurtBehavior::Determined) => "status.badly_hurt.determined",
+                    None => "status.badly_hurt",
+                };
                 view_buffer.messages.add(
-                    match entity_ref
-                        .get::<&SpeciesId>()
-                        .and_then(|species| view_buffer.assets.species_data_map.get(&species))
-                        .and_then(|species_data| species_data.badly_hurt_behavior)
-                    {
-                        Some(BadlyHurtBehavior::Fearful) => {
-                            format!("{the_entity} is badly hurt, and turns to flee.")
-                        }
-                        Some(BadlyHurtBehavior::Determined) => {
-                            format!(
-                                "{the_entity} is badly hurt, and readies themselves to go all-out."
-                            )
-                        }
-                        None => format!("{the_entity} is badly hurt."),
-                    },
+                    view_buffer
+                        .assets
+                        .messages
+                        .text(message_id, &[("entity", &the_entity)]),
                 );
             }
         }
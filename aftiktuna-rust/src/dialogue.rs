@@ -1,5 +1,5 @@
 mod context {
-    use crate::asset::GameAssets;
+    use crate::asset::{GameAssets, message};
     use crate::core::behavior::{CrewLossMemory, Reward};
     use crate::core::name::{Name, NameData};
     use std::collections::HashMap;
@@ -14,29 +14,10 @@ mod context {
             self.resolver_map.insert(key, Box::new(resolver));
         }
 
-        pub(super) fn resolve(&self, mut text: &str) -> String {
-            let mut result = String::new();
-            while !text.is_empty() {
-                if let Some(start) = text.find('{')
-                    && let Some(length) = text[start..].find('}')
-                {
-                    result.push_str(&text[..start]);
-
-                    let key = &text[(start + 1)..(start + length)];
-                    if let Some(resolver) = self.resolver_map.get(key) {
-                        result.push_str(&resolver());
-                    } else {
-                        eprintln!("Unknown dialogue text key: \"{key}\"");
-                        result.push_str("???");
-                    }
-
-                    text = text.split_at(start + length + 1).1;
-                } else {
-                    result.push_str(text);
-                    break;
-                }
-            }
-            result
+        pub(super) fn resolve(&self, text: &str) -> String {
+            message::resolve_placeholders(text, |key| {
+                self.resolver_map.get(key).map(|resolver| resolver())
+            })
         }
     }
 
@@ -192,7 +173,7 @@ impl TalkTopic {
                         view_buffer,
                     );
                     if state.world.satisfies::<&Recruitable>(performer)
-                        && core::check_crew_size(&state.world).is_ok()
+                        && core::check_crew_size(&state.world, view_buffer.assets).is_ok()
                     {
                         trigger_recruit_request(performer, target, state, view_buffer);
                     } else {
@@ -212,9 +193,8 @@ fn prompt_npc_dialogue(
     view_buffer: &mut view::Buffer,
 ) {
     let npc_ref = state.world.entity(npc).unwrap();
-    let gives_hunt_reward = npc_ref.get::<&mut GivesHuntRewardData>();
-    if gives_hunt_reward.is_some() {
-        let mut gives_hunt_reward = gives_hunt_reward.unwrap();
+    if npc_ref.has::<GivesHuntRewardData>() {
+        let mut gives_hunt_reward = npc_ref.get::<&mut GivesHuntRewardData>().unwrap();
 
         if !gives_hunt_reward.is_fulfilled(&state.world) {
             gives_hunt_reward.presented = true;
@@ -267,7 +247,6 @@ fn prompt_npc_dialogue(
             let _ = state.world.remove_one::<GivesHuntRewardData>(npc);
         }
     } else {
-        drop(gives_hunt_reward);
         if let Some(talk) = npc_ref.get::<&Talk>().map(crate::deref_clone) {
             trigger_dialogue_by_name(&talk.0, npc, crew_member, state, view_buffer);
         } else if npc_ref
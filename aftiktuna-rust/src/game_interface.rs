@@ -2,40 +2,87 @@ use crate::asset::GameAssets;
 use crate::command::{self, CommandResult};
 use crate::game_loop::{self, GameState, Step};
 use crate::location::{self, GenerationState};
+use crate::replay::{self, Replay};
+use crate::run_config::RunConfig;
 use crate::serialization::{self, LoadError};
 use crate::view::Frame;
+use crate::view::combat_log::{self, CombatLog};
+use crate::view::map::MapView;
 use crate::{CommandInfo, StopType};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::VecDeque;
 use std::fs::File;
+use std::io::{BufReader, Read};
 use std::mem;
 
-pub fn load() -> Result<Game, LoadError> {
+/// The number of turns that can be undone in a row.
+const UNDO_LIMIT: usize = 10;
+
+pub fn load(slot: &str) -> Result<Game, LoadError> {
+    let file = BufReader::new(File::open(serialization::save_slot_path(slot))?);
+    load_with(|assets| serialization::load_game(file, assets))
+}
+
+/// Loads a game from json written by [serialization::write_game_as_json].
+pub fn load_json(reader: impl Read) -> Result<Game, LoadError> {
+    load_with(|assets| serialization::load_game_from_json(reader, assets))
+}
+
+fn load_with(
+    load_state: impl FnOnce(&GameAssets) -> Result<SerializedState, LoadError>,
+) -> Result<Game, LoadError> {
     let assets = GameAssets::load()?;
-    let file = File::open(serialization::SAVE_FILE_NAME)?;
-    let serialized_state = serialization::load_game(file, &assets)?;
+    let serialized_state = load_state(&assets)?;
     Ok(Game {
         serialized_state,
         is_in_error_state: false,
         assets,
+        replay: None,
+        autosave: false,
+        undo_snapshots: None,
     })
 }
 
-pub fn setup_new() -> Result<Game, String> {
-    let locations = GenerationState::load_new(3).map_err(|error| error.to_string())?;
-    setup_new_with(locations)
+pub fn setup_new(run_config: RunConfig, seed: Option<u64>) -> Result<Game, String> {
+    let locations = GenerationState::load_new(run_config).map_err(|error| error.to_string())?;
+    setup_new_with(locations, seed)
+}
+
+/// Sets up a new game with the given locations.
+/// If no seed is given, a random seed is picked instead.
+pub fn setup_new_with(locations: GenerationState, seed: Option<u64>) -> Result<Game, String> {
+    setup(locations, seed.unwrap_or_else(rand::random), None)
+}
+
+/// Sets up a new game where accepted inputs and produced frames are recorded,
+/// so that they can be written to a replay file.
+pub fn setup_recorded(
+    start: replay::Start,
+    run_config: RunConfig,
+    seed: Option<u64>,
+) -> Result<Game, String> {
+    let seed = seed.unwrap_or_else(rand::random);
+    let locations = start.generation_state(run_config.clone())?;
+    setup(locations, seed, Some(Replay::new(start, run_config, seed)))
 }
 
-pub fn setup_new_with(locations: GenerationState) -> Result<Game, String> {
+fn setup(locations: GenerationState, seed: u64, replay: Option<Replay>) -> Result<Game, String> {
     let assets = GameAssets::load().map_err(|error| error.to_string())?;
     let mut game = Game {
         serialized_state: SerializedState {
             phase: Phase::Invalid,
-            state: game_loop::setup(locations, &assets)?,
+            state: game_loop::setup(locations, seed, &assets)?,
             frame_cache: FrameCache::new(vec![Frame::Introduction]),
         },
         is_in_error_state: false,
         assets,
+        replay,
+        autosave: false,
+        undo_snapshots: None,
     };
+    if let Some(replay) = &mut game.replay {
+        replay.add_frames([Frame::Introduction]);
+    }
     game.run_from_step(Step::PrepareNextLocation);
     Ok(game)
 }
@@ -56,6 +103,10 @@ pub struct Game {
     pub(crate) serialized_state: SerializedState,
     is_in_error_state: bool,
     assets: GameAssets,
+    replay: Option<Replay>,
+    autosave: bool,
+    /// Snapshots of the game from before each of the latest turns. Absent if undo is not enabled.
+    undo_snapshots: Option<VecDeque<Vec<u8>>>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -85,8 +136,8 @@ impl Phase {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PhaseResult {
-    next_phase: Phase,
-    load_error: Option<String>,
+    pub(crate) next_phase: Phase,
+    pub(crate) load_error: Option<String>,
 }
 
 impl From<Phase> for PhaseResult {
@@ -99,6 +150,91 @@ impl From<Phase> for PhaseResult {
 }
 
 impl Game {
+    /// The seed that this run was started with, if known.
+    pub fn seed(&self) -> Option<u64> {
+        self.serialized_state.state.seed
+    }
+
+    /// The recording of this run, if it was set up to be recorded.
+    pub fn replay(&self) -> Option<&Replay> {
+        self.replay.as_ref()
+    }
+
+    /// Enables writing the game to the autosave slot each time the ship lands at a new location.
+    pub fn enable_autosave(&mut self) {
+        self.autosave = true;
+    }
+
+    /// Enables undoing turns with [Game::undo].
+    /// Undo is not available for recorded games, since the recording would no longer match the run.
+    pub fn enable_undo(&mut self) {
+        if self.replay.is_none() {
+            self.undo_snapshots.get_or_insert_default();
+        }
+    }
+
+    /// Enables recording the rolls and modifiers behind each attack,
+    /// which can be viewed with the "combat log" command and exported with [Game::export_combat_log].
+    pub fn enable_combat_log(&mut self) {
+        self.serialized_state
+            .state
+            .combat_log
+            .get_or_insert_default();
+    }
+
+    #[cfg(test)]
+    pub(crate) fn assets_mut(&mut self) -> &mut GameAssets {
+        &mut self.assets
+    }
+
+    #[cfg(test)]
+    pub(crate) fn state_and_assets_mut(&mut self) -> (&mut GameState, &GameAssets) {
+        (&mut self.serialized_state.state, &self.assets)
+    }
+
+    pub fn combat_log(&self) -> Option<&CombatLog> {
+        self.serialized_state.state.combat_log.as_ref()
+    }
+
+    /// Writes the combat log as csv to [combat_log::EXPORT_PATH].
+    pub fn export_combat_log(&self) -> Result<&'static str, String> {
+        let combat_log = self
+            .combat_log()
+            .ok_or_else(|| "The combat log is not enabled.".to_owned())?;
+        combat_log
+            .export(combat_log::EXPORT_PATH)
+            .map_err(|error| format!("Failed to export the combat log: {error}"))?;
+        Ok(combat_log::EXPORT_PATH)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.undo_snapshots
+            .as_ref()
+            .is_some_and(|snapshots| !snapshots.is_empty())
+    }
+
+    /// Restores the game to how it was before the last turn, including the frame that was shown at that point.
+    /// Returns false if there is no turn to undo.
+    pub fn undo(&mut self) -> bool {
+        let Some(snapshot) = self.undo_snapshots.as_mut().and_then(VecDeque::pop_back) else {
+            return false;
+        };
+        match serialization::read_snapshot(&snapshot) {
+            Ok(serialized_state) => {
+                // The combat log is not part of the snapshot, and is kept as a record of every attack.
+                let combat_log = self.serialized_state.state.combat_log.take();
+                self.serialized_state = serialized_state;
+                self.serialized_state.state.combat_log = combat_log;
+                self.is_in_error_state = false;
+                true
+            }
+            Err(error) => {
+                eprintln!("Failed to restore snapshot: {error}");
+                false
+            }
+        }
+    }
+
     pub fn next_result(&mut self) -> GameResult<'_> {
         if self.serialized_state.frame_cache.has_more_frames() {
             GameResult::Frame(FrameGetter(&mut self.serialized_state.frame_cache))
@@ -138,18 +274,29 @@ impl Game {
                     .generation_state
                     .try_make_choice(choice, input, &mut self.serialized_state.state.rng)
                     .map_err(|error| vec![error])?;
+                self.record_input(input);
                 self.run_from_step(Step::LoadLocation(location));
             }
             Phase::CommandInput => {
-                match command::try_parse_input(input, &self.serialized_state.state, &self.assets)
-                    .map_err(|error| vec![error])?
+                match command::try_parse_input(
+                    input,
+                    &mut self.serialized_state.state,
+                    &self.assets,
+                )
+                .map_err(|error| vec![error])?
                 {
                     CommandResult::Action(action, target) => {
+                        self.record_input(input);
+                        self.store_undo_snapshot();
                         self.run_from_step(Step::Tick(Some((action, target))));
                     }
                     CommandResult::ChangeControlled(character) => {
+                        self.record_input(input);
                         self.run_from_step(Step::ChangeControlled(character));
                     }
+                    CommandResult::ShowMap(map_view) => {
+                        self.serialized_state.frame_cache.show_map(map_view);
+                    }
                     CommandResult::Info(info) => return Err(info),
                 }
             }
@@ -158,14 +305,48 @@ impl Game {
         Ok(())
     }
 
+    fn store_undo_snapshot(&mut self) {
+        let Some(snapshots) = &mut self.undo_snapshots else {
+            return;
+        };
+        match serialization::write_snapshot(&self.serialized_state) {
+            Ok(snapshot) => {
+                if snapshots.len() >= UNDO_LIMIT {
+                    snapshots.pop_front();
+                }
+                snapshots.push_back(snapshot);
+            }
+            Err(error) => eprintln!("Failed to store snapshot for undo: {error}"),
+        }
+    }
+
+    fn record_input(&mut self, input: &str) {
+        if let Some(replay) = &mut self.replay {
+            replay.add_input(input);
+        }
+    }
+
     fn run_from_step(&mut self, step: Step) {
+        let is_landing = matches!(step, Step::LoadLocation(_));
         let (phase_result, frames) =
             game_loop::run(step, &mut self.serialized_state.state, &self.assets);
         self.serialized_state.phase = phase_result.next_phase;
+        if let Some(replay) = &mut self.replay {
+            replay.add_frames(frames.iter().cloned());
+        }
         self.serialized_state.frame_cache.add_new_frames(frames);
         if let Some(message) = phase_result.load_error {
             self.is_in_error_state = true;
+            if let Some(replay) = &mut self.replay {
+                replay.add_frames([Frame::Error(message.clone())]);
+            }
             self.serialized_state.frame_cache.error_frame = Some(Frame::Error(message));
+        } else if is_landing
+            && self.autosave
+            && let Err(error) =
+                serialization::write_game_to_save_slot(self, serialization::AUTOSAVE_SLOT)
+        {
+            eprintln!("Failed to autosave: {error}");
         }
     }
 }
@@ -194,6 +375,13 @@ impl FrameCache {
         self.error_frame = None;
     }
 
+    /// Shows a map frame, followed by the frame that was shown before it.
+    fn show_map(&mut self, map_view: MapView) {
+        let mut frames = vec![Frame::Map(map_view)];
+        frames.extend(self.last_frame.as_ref().map(Frame::without_messages));
+        self.add_new_frames(frames);
+    }
+
     fn has_more_frames(&self) -> bool {
         !self.remaining_frames.is_empty() || self.error_frame.is_some()
     }
@@ -243,3 +431,68 @@ impl FrameGetter<'_> {
         self.0.take_next_frame().unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Game, GameResult};
+    use crate::location::GenerationState;
+    use crate::run_config::RunConfig;
+
+    fn collect_frames(game: &mut Game) -> Vec<String> {
+        let mut frames = Vec::new();
+        while let GameResult::Frame(frame_getter) = game.next_result() {
+            frames.push(serde_json::to_string(&frame_getter.get()).unwrap());
+        }
+        frames
+    }
+
+    #[test]
+    fn json_export_round_trips() {
+        let mut game = super::setup_new(RunConfig::default(), Some(11)).unwrap();
+        let mut json = Vec::new();
+        crate::serialization::write_game_as_json(&game, &mut json).unwrap();
+        let mut loaded_game = super::load_json(json.as_slice()).unwrap();
+
+        assert_eq!(collect_frames(&mut game), collect_frames(&mut loaded_game));
+        for input in ["wait", "left"] {
+            if !game.ready_to_take_input() {
+                break;
+            }
+            let _ = game.handle_input(input);
+            let _ = loaded_game.handle_input(input);
+            assert_eq!(collect_frames(&mut game), collect_frames(&mut loaded_game));
+        }
+    }
+
+    #[test]
+    fn undo_restores_previous_turn() {
+        let mut game = super::setup_new_with(
+            GenerationState::single("goblin_forest".to_owned(), RunConfig::default()).unwrap(),
+            Some(5),
+        )
+        .unwrap();
+        game.enable_undo();
+        let initial_frames = collect_frames(&mut game);
+        assert!(!game.can_undo());
+
+        assert!(game.handle_input("wait").is_ok());
+        let frames_after_wait = collect_frames(&mut game);
+        assert!(game.undo());
+        assert_eq!(
+            collect_frames(&mut game),
+            initial_frames[initial_frames.len() - 1..]
+        );
+
+        assert!(game.handle_input("wait").is_ok());
+        assert_eq!(collect_frames(&mut game), frames_after_wait);
+    }
+
+    #[test]
+    fn same_seed_gives_same_run() {
+        let mut game_1 = super::setup_new(RunConfig::default(), Some(17)).unwrap();
+        let mut game_2 = super::setup_new(RunConfig::default(), Some(17)).unwrap();
+
+        assert_eq!(collect_frames(&mut game_1), collect_frames(&mut game_2));
+        assert_eq!(game_1.seed(), Some(17));
+    }
+}
@@ -1,9 +1,12 @@
 use crate::action::{self, Action};
 use crate::asset::{self, GameAssets};
-use crate::core::area::{self, FuelAmount, ShipState, ShipStatus};
+use crate::core::area::{self, FuelAmount, ShipState, ShipStatus, ShipUpgrade};
 use crate::core::behavior::{
-    Character, CrewLossMemory, Decision, Hostile, RepeatingAction, TalkedAboutEnoughFuel, Waiting,
+    Character, CrewLossMemory, Decision, Hostile, Order, RepeatingAction, TalkedAboutEnoughFuel,
+    Waiting,
 };
+use crate::core::effect;
+use crate::core::hazard;
 use crate::core::inventory::Held;
 use crate::core::item::ItemTypeId;
 use crate::core::name::{self, ArticleKind, Name, NameData, NameIdData, NameQuery};
@@ -12,51 +15,74 @@ use crate::core::status::{self, Health, Morale, Stamina, Trait};
 use crate::core::{CrewMember, OpenedChest};
 use crate::game_interface::{Phase, PhaseResult};
 use crate::location::{self, GenerationState, InitialSpawnData, PickResult};
+use crate::view::combat_log::CombatLog;
+use crate::view::event::GameEvent;
 use crate::view::text::{self, CombinableMsgType};
 use crate::view::{self, Frame, StatusCache};
 use crate::{StopType, ai, command, dialogue, serialization};
 use hecs::{CommandBuffer, Entity, Satisfies, World};
-use rand::rngs::ThreadRng;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// The random number generator used by the game.
+/// Its state is stored as part of the save file so that a run can be reproduced from its seed.
+pub type GameRng = ChaCha8Rng;
+
 #[derive(Serialize, Deserialize)]
 pub struct GameState {
     #[serde(with = "serialization::world")]
     pub world: World,
-    #[serde(skip)]
-    pub rng: ThreadRng,
+    #[serde(default = "GameRng::from_os_rng")]
+    pub rng: GameRng,
+    /// The seed that the run was started with.
+    /// Absent for runs that were started before the seed was stored.
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// The location that the ship last landed at.
+    #[serde(default)]
+    pub current_location: Option<String>,
     pub generation_state: GenerationState,
     pub ship_core: Entity,
     pub controlled: Entity,
     pub status_cache: StatusCache,
     pub has_introduced_controlled: bool,
+    /// Records of the rolls behind each attack. Absent if the combat log is not enabled.
+    #[serde(skip)]
+    pub combat_log: Option<CombatLog>,
 }
 
 pub fn setup(
     mut generation_state: GenerationState,
+    seed: u64,
     assets: &GameAssets,
 ) -> Result<GameState, String> {
+    let run_config = generation_state.run_config();
+    run_config.validate(&assets.species_data_map)?;
+    let mut crew_data = asset::CREW_DATA_FILE
+        .load()
+        .map_err(|error| error.to_string())?;
+    run_config.apply_to_crew_data(&mut crew_data);
+
+    let mut rng = GameRng::seed_from_u64(seed);
     let InitialSpawnData {
         world,
         controlled_character,
         ship_core,
-    } = location::spawn_starting_crew_and_ship(
-        asset::CREW_DATA_FILE
-            .load()
-            .map_err(|error| error.to_string())?,
-        &mut generation_state,
-        assets,
-    )?;
+    } = location::spawn_starting_crew_and_ship(crew_data, &mut generation_state, &mut rng, assets)?;
 
     Ok(GameState {
         world,
-        rng: rand::rng(),
+        rng,
+        seed: Some(seed),
+        current_location: None,
         generation_state,
         ship_core,
         controlled: controlled_character,
         status_cache: StatusCache::default(),
         has_introduced_controlled: false,
+        combat_log: None,
     })
 }
 
@@ -102,11 +128,20 @@ fn run_step(
                 state,
                 view_buffer.assets,
             )
-            .map_err(|message| Phase::LoadLocation(location).with_error(message))?;
+            .map_err(|message| Phase::LoadLocation(location.clone()).with_error(message))?;
+            view_buffer.add_event(GameEvent::LocationEntered {
+                location: location.clone(),
+            });
+            state.current_location = Some(location);
+            area::mark_explored_areas(&mut state.world);
             if !state.has_introduced_controlled {
-                view_buffer.messages.add(format!(
-                    "You're playing as the aftik {}.",
-                    NameData::find(&state.world, state.controlled, view_buffer.assets).definite()
+                let assets = view_buffer.assets;
+                view_buffer.messages.add(assets.messages.text(
+                    "game.introduce_controlled",
+                    &[(
+                        "character",
+                        &NameData::find(&state.world, state.controlled, assets).definite(),
+                    )],
                 ));
                 state.has_introduced_controlled = true;
             }
@@ -137,7 +172,11 @@ fn prepare_next_location(
     state: &mut GameState,
     view_buffer: &mut view::Buffer,
 ) -> Result<Step, Phase> {
-    match state.generation_state.next(&mut state.rng) {
+    let use_scanner = area::has_ship_upgrade(&state.world, ShipUpgrade::Scanner);
+    match state
+        .generation_state
+        .next(use_scanner, &mut state.rng, view_buffer.assets)
+    {
         PickResult::None => {
             view_buffer.push_ending_frame(&state.world, state.controlled, StopType::Win);
             Err(Phase::Stopped(StopType::Win))
@@ -185,9 +224,10 @@ fn tick_and_check(
             .map(|state| state.status)
             .ok()
     {
+        let assets = view_buffer.assets;
         view_buffer
             .messages
-            .add("The ship still needs one more fuel can to be refueled.");
+            .add(assets.messages.text("ship.still_needs_one_can", &[]));
     }
 
     dialogue::trigger_encounter_dialogue(state, view_buffer);
@@ -242,6 +282,10 @@ fn tick(
 
     action::tick(action_map, state, view_buffer);
 
+    hazard::apply_hazard_effects(&mut state.world, view_buffer, state.controlled);
+    effect::tick_status_effects(&mut state.world, view_buffer, state.controlled);
+    area::mark_explored_areas(&mut state.world);
+
     status::detect_low_health(&mut state.world, view_buffer, state.controlled);
     status::detect_low_stamina(&mut state.world, view_buffer, state.controlled);
 
@@ -259,14 +303,18 @@ fn tick(
         .map(|entity| NameIdData::find(&state.world, entity))
         .collect::<Vec<_>>();
     if !alive_recovering_entities.is_empty() {
-        view_buffer.messages.add(format!(
-            "{entities} regained their balance.",
-            entities = text::join_elements(name::names_with_counts(
-                alive_recovering_entities,
-                name::ArticleKind::The,
-                name::CountFormat::Text,
-                view_buffer.assets,
-            ))
+        let assets = view_buffer.assets;
+        view_buffer.messages.add(assets.messages.text(
+            "status.regained_balance",
+            &[(
+                "entities",
+                &text::join_elements(name::names_with_counts(
+                    alive_recovering_entities,
+                    name::ArticleKind::The,
+                    name::CountFormat::Text,
+                    assets,
+                )),
+            )],
         ))
     }
 
@@ -283,9 +331,13 @@ fn tick(
             continue;
         };
         buffer.despawn(item);
-        view_buffer.messages.add(format!(
-            "As {} holds the four leaf clover, it disappears in their hand. (Luck has increased by 2 points)",
-            NameData::find_by_ref(holder_ref, view_buffer.assets).definite(),
+        let assets = view_buffer.assets;
+        view_buffer.messages.add(assets.messages.text(
+            "item.four_leaf_clover.held",
+            &[(
+                "character",
+                &NameData::find_by_ref(holder_ref, assets).definite(),
+            )],
         ));
     }
     buffer.run_on(&mut state.world);
@@ -313,11 +365,11 @@ fn insert_command_action(
                 .get_area();
             for (entity, _, _) in state
                 .world
-                .query::<(Entity, &Pos, Satisfies<&Waiting>)>()
+                .query::<(Entity, &Pos, Satisfies<hecs::Or<&Waiting, &Order>>)>()
                 .with::<&CrewMember>()
                 .iter()
-                .filter(|&(entity, pos, is_waiting)| {
-                    pos.is_in(area) && (entity == state.controlled || !is_waiting)
+                .filter(|&(entity, pos, has_own_orders)| {
+                    pos.is_in(area) && (entity == state.controlled || !has_own_orders)
                 })
             {
                 action_map.insert(entity, action.clone());
@@ -336,10 +388,14 @@ fn handle_crew_deaths(state: &mut GameState, view_buffer: &mut view::Buffer) {
         .map(|(aftik, _)| aftik)
         .collect::<Vec<_>>();
 
+    let assets = view_buffer.assets;
     for &aftik in &dead_crew {
-        view_buffer.messages.add(format!(
-            "{} is dead.",
-            NameData::find(&state.world, aftik, view_buffer.assets).definite(),
+        view_buffer.messages.add(assets.messages.text(
+            "crew.dead",
+            &[(
+                "character",
+                &NameData::find(&state.world, aftik, assets).definite(),
+            )],
         ));
     }
 
@@ -442,6 +498,11 @@ fn handle_was_waiting(state: &mut GameState, view_buffer: &mut view::Buffer) {
                 .world
                 .get::<&Hostile>(entity)
                 .is_ok_and(|hostile| hostile.aggressive)
+                && !ai::is_holding_back(
+                    state.world.entity(entity).unwrap(),
+                    &state.world,
+                    view_buffer.assets,
+                )
             {
                 position::turn_towards(&state.world, entity, player_pos);
                 view_buffer.messages.add(
@@ -466,19 +527,25 @@ fn is_ship_launching(state: &GameState) -> bool {
 fn leave_location(state: &mut GameState, view_buffer: &mut view::Buffer) {
     deposit_items_to_ship(state);
 
+    let assets = view_buffer.assets;
     view_buffer
         .messages
-        .add("The ship leaves for the next planet.");
+        .add(assets.messages.text("ship.leaves", &[]));
 
-    for (_, query) in state
+    for (character, _, query) in state
         .world
-        .query::<(&Pos, NameQuery)>()
+        .query::<(Entity, &Pos, NameQuery)>()
         .with::<&CrewMember>()
         .iter()
-        .filter(|&(pos, _)| !area::is_in_ship(*pos, &state.world))
+        .filter(|&(_, pos, _)| !area::is_in_ship(*pos, &state.world))
     {
-        let name = NameData::from_query(query, view_buffer.assets).definite();
-        view_buffer.messages.add(format!("{name} was left behind."));
+        let name = NameData::from_query(query, assets).definite();
+        view_buffer.messages.add(
+            assets
+                .messages
+                .text("crew.left_behind", &[("character", &name)]),
+        );
+        view_buffer.add_event(GameEvent::LeftBehind { character });
     }
     for morale in state.world.query_mut::<&mut Morale>().with::<&CrewMember>() {
         morale.dampen(0.6);
@@ -493,15 +560,15 @@ fn leave_location(state: &mut GameState, view_buffer: &mut view::Buffer) {
         })
         .count();
     consume_rations_healing(state, view_buffer);
+    apply_medbay_healing(state, view_buffer);
 
     view_buffer.capture_view(state, false);
 
     location::despawn_all_except_ship(&mut state.world);
-    state
-        .world
-        .get::<&mut ShipState>(state.ship_core)
-        .unwrap()
-        .status = ShipStatus::NeedFuel(FuelAmount::TwoCans);
+    {
+        let mut ship_state = state.world.get::<&mut ShipState>(state.ship_core).unwrap();
+        ship_state.status = ShipStatus::NeedFuel(ship_state.fuel_needed_on_landing());
+    }
 
     let crew = state.world.get::<&CrewMember>(state.controlled).unwrap().0;
     let _ = state.world.remove_one::<TalkedAboutEnoughFuel>(crew);
@@ -593,19 +660,70 @@ fn consume_rations_healing(state: &mut GameState, view_buffer: &mut view::Buffer
     }
 }
 
+/// The fraction of max health that the medbay restores for each hurt crew member when leaving a location.
+const MEDBAY_HEAL_FRACTION: f32 = 0.5;
+
+fn apply_medbay_healing(state: &mut GameState, view_buffer: &mut view::Buffer) {
+    if !area::has_ship_upgrade(&state.world, ShipUpgrade::Medbay) {
+        return;
+    }
+    let hurt_crew = state
+        .world
+        .query::<(Entity, &Health)>()
+        .with::<&CrewMember>()
+        .iter()
+        .filter(|&(_, health)| health.is_alive() && health.is_hurt())
+        .map(|(entity, _)| entity)
+        .collect::<Vec<_>>();
+    if hurt_crew.is_empty() {
+        return;
+    }
+
+    for &character in &hurt_crew {
+        let entity_ref = state.world.entity(character).unwrap();
+        entity_ref
+            .get::<&mut Health>()
+            .unwrap()
+            .restore_fraction(MEDBAY_HEAL_FRACTION, entity_ref);
+    }
+    let assets = view_buffer.assets;
+    view_buffer.messages.add(
+        assets.messages.text(
+            "ship.medbay",
+            &[(
+                "characters",
+                &text::join_elements(
+                    hurt_crew
+                        .into_iter()
+                        .map(|character| NameData::find(&state.world, character, assets).definite())
+                        .collect(),
+                ),
+            )],
+        ),
+    );
+}
+
 fn build_eating_message(
     crew_eating_rations: Vec<(Entity, u16)>,
     world: &World,
     assets: &GameAssets,
 ) -> String {
     if let &[(entity, amount)] = &crew_eating_rations[..] {
-        format!(
-            "{the_character} ate {one_ration} to recover some health.",
-            the_character = NameData::find(world, entity, assets).definite(),
-            one_ration = assets
-                .noun_data_map
-                .lookup(&ItemTypeId::food_ration().noun_id())
-                .with_text_count(amount, ArticleKind::One),
+        assets.messages.text(
+            "crew.ate_rations",
+            &[
+                (
+                    "character",
+                    &NameData::find(world, entity, assets).definite(),
+                ),
+                (
+                    "rations",
+                    &assets
+                        .noun_data_map
+                        .lookup(&ItemTypeId::food_ration().noun_id())
+                        .with_text_count(amount, ArticleKind::One),
+                ),
+            ],
         )
     } else {
         let names = crew_eating_rations
@@ -616,18 +734,26 @@ fn build_eating_message(
             .iter()
             .map(|(_, amount)| amount)
             .sum::<u16>();
-        format!(
-            "{names} ate {amount} food rations to recover some health.",
-            names = text::join_elements(names)
+        assets.messages.text(
+            "crew.ate_rations_together",
+            &[
+                ("characters", &text::join_elements(names)),
+                ("amount", &amount.to_string()),
+            ],
         )
     }
 }
 
 fn change_character(state: &mut GameState, character: Entity, view_buffer: &mut view::Buffer) {
     state.controlled = character;
-
-    view_buffer.messages.add(format!(
-        "You're now playing as the aftik {}.",
-        NameData::find(&state.world, character, view_buffer.assets).definite()
+    view_buffer.add_event(GameEvent::ControlledCharacterChanged { character });
+
+    let assets = view_buffer.assets;
+    view_buffer.messages.add(assets.messages.text(
+        "game.change_controlled",
+        &[(
+            "character",
+            &NameData::find(&state.world, character, assets).definite(),
+        )],
     ));
 }
@@ -7,7 +7,12 @@ mod dialogue;
 pub mod game_interface;
 mod game_loop;
 pub mod location;
+pub mod replay;
+pub mod run_config;
+#[cfg(test)]
+mod scenario;
 pub mod serialization;
+pub mod simulation;
 pub mod view;
 
 use serde::{Deserialize, Serialize};
@@ -1,8 +1,9 @@
 pub mod generate;
 
+use self::generate::LocationBuildData;
 use self::generate::creature;
 use self::generate::door::{self, DoorInfo};
-use crate::asset::location::{DoorPairData, DoorType, LOCATION_DIR};
+use crate::asset::location::{DoorPairData, DoorType, LOCATION_DIR, LocationData};
 use crate::asset::profile::CharacterProfile;
 use crate::asset::{AssetFile, CrewData, GameAssets};
 use crate::core::area::{self, FuelAmount, ShipRoom, ShipState, ShipStatus};
@@ -13,15 +14,15 @@ use crate::core::position::{self, Direction, Pos};
 use crate::core::status::Morale;
 use crate::core::store::Points;
 use crate::core::{CrewMember, Door, DoorKind, inventory};
-use crate::game_loop::GameState;
+use crate::game_loop::{GameRng, GameState};
+use crate::run_config::RunConfig;
 use crate::view::text::{self, Messages};
 use crate::{asset, serialization};
 use hecs::{CommandBuffer, Entity, Satisfies, World};
-use rand::Rng;
-use rand::rngs::ThreadRng;
-use rand::seq::index;
+use indexmap::IndexMap;
+use rand::seq::{IndexedRandom, index};
+use rand::{Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 
 #[derive(Eq, PartialEq, Serialize, Deserialize)]
 enum TrackedState {
@@ -34,30 +35,52 @@ pub struct GenerationState {
     locations: Locations,
     state: TrackedState,
     character_names: Vec<String>,
-    aftik_color_names: HashMap<SpeciesColorId, Vec<String>>,
+    aftik_color_names: IndexMap<SpeciesColorId, Vec<String>>,
+    /// Absent in saves from before run configurations were added, which then get the default configuration.
+    #[serde(default)]
+    run_config: RunConfig,
 }
 
 impl GenerationState {
-    pub fn load_new(locations_before_fortuna: i32) -> Result<Self, asset::Error> {
-        Self::new(LOCATIONS_FILE.load()?, locations_before_fortuna)
+    pub fn load_new(run_config: RunConfig) -> Result<Self, asset::Error> {
+        let locations_before_fortuna = run_config.locations_before_fortuna;
+        Self::new(LOCATIONS_FILE.load()?, locations_before_fortuna, run_config)
     }
 
-    pub fn single(location: String) -> Result<Self, asset::Error> {
-        Self::new(Locations::single(location), 1)
+    /// Sets up generation of only the given location.
+    /// The number of locations in the run configuration is ignored.
+    pub fn single(location: String, run_config: RunConfig) -> Result<Self, asset::Error> {
+        Self::new(Locations::single(location), 1, run_config)
     }
 
-    fn new(locations: Locations, locations_before_fortuna: i32) -> Result<Self, asset::Error> {
+    fn new(
+        locations: Locations,
+        locations_before_fortuna: i32,
+        run_config: RunConfig,
+    ) -> Result<Self, asset::Error> {
         Ok(Self {
             locations,
             state: TrackedState::BeforeFortuna {
                 remaining_locations_count: locations_before_fortuna,
             },
             character_names: asset::CHARACTER_NAMES_FILE.load()?,
-            aftik_color_names: asset::AFTIK_COLOR_NAMES_FILE.load()?,
+            aftik_color_names: asset::AFTIK_COLOR_NAMES_FILE.load_index_map()?,
+            run_config,
         })
     }
 
-    pub fn next(&mut self, rng: &mut impl Rng) -> PickResult {
+    pub fn run_config(&self) -> &RunConfig {
+        &self.run_config
+    }
+
+    /// Picks the next location, or a choice between location categories.
+    /// With the ship scanner, each alternative of a choice is scanned ahead of time.
+    pub fn next(
+        &mut self,
+        use_scanner: bool,
+        rng: &mut impl Rng,
+        assets: &GameAssets,
+    ) -> PickResult {
         match &mut self.state {
             TrackedState::AtFortuna => PickResult::None,
             TrackedState::BeforeFortuna {
@@ -69,7 +92,7 @@ impl GenerationState {
                 }
 
                 *remaining_locations_count -= 1;
-                self.locations.next(rng)
+                self.locations.next(use_scanner, rng, assets)
             }
         }
     }
@@ -80,11 +103,15 @@ impl GenerationState {
         input: &str,
         rng: &mut impl Rng,
     ) -> Result<String, String> {
-        let category_index = choice
+        let alternative = choice
             .try_choose(input)
             .ok_or_else(|| format!("Unexpected input: \"{input}\""))?;
 
-        Ok(self.locations.pick_from_category(category_index, rng))
+        Ok(self.locations.pick_from_category(
+            alternative.index,
+            alternative.scan.as_ref().map(|scan| scan.location.as_str()),
+            rng,
+        ))
     }
 
     pub fn is_at_fortuna(&self) -> bool {
@@ -129,13 +156,13 @@ impl Locations {
         }
     }
 
-    fn next(&mut self, rng: &mut impl Rng) -> PickResult {
+    fn next(&mut self, use_scanner: bool, rng: &mut impl Rng, assets: &GameAssets) -> PickResult {
         if self.categories.is_empty() {
             return PickResult::None;
         }
 
         if self.categories.len() == 1 {
-            return PickResult::Location(self.pick_from_category(0, rng));
+            return PickResult::Location(self.pick_from_category(0, None, rng));
         }
 
         let alternatives = index::sample(rng, self.categories.len(), 2)
@@ -144,17 +171,39 @@ impl Locations {
                 index,
                 name: self.categories[index].name.clone(),
                 description: self.categories[index].description.clone(),
+                scan: use_scanner.then(|| {
+                    let location = self.categories[index]
+                        .location_names
+                        .choose(rng)
+                        .unwrap()
+                        .clone();
+                    let details = generate::scan_location(&location, assets);
+                    ScanReading { location, details }
+                }),
             })
             .collect::<Vec<_>>();
 
-        PickResult::Choice(Choice(alternatives))
+        PickResult::Choice(Choice::new(alternatives, assets))
     }
 
-    fn pick_from_category(&mut self, category_index: usize, rng: &mut impl Rng) -> String {
+    /// Picks and removes a location from the category.
+    /// The scanned location is picked if given, and otherwise a random one.
+    fn pick_from_category(
+        &mut self,
+        category_index: usize,
+        scanned_location: Option<&str>,
+        rng: &mut impl Rng,
+    ) -> String {
         let category = self.categories.get_mut(category_index).unwrap();
-        let chosen_location = category
-            .location_names
-            .swap_remove(rng.random_range(0..category.location_names.len()));
+        let location_index = scanned_location
+            .and_then(|scanned_location| {
+                category
+                    .location_names
+                    .iter()
+                    .position(|location| location == scanned_location)
+            })
+            .unwrap_or_else(|| rng.random_range(0..category.location_names.len()));
+        let chosen_location = category.location_names.swap_remove(location_index);
         if category.location_names.is_empty() {
             self.categories.swap_remove(category_index);
         }
@@ -183,29 +232,88 @@ struct Alternative {
     index: usize,
     name: String,
     description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    scan: Option<ScanReading>,
+}
+
+impl Alternative {
+    fn title_line(&self) -> String {
+        format!("{}: {}", text::capitalize(&self.name), self.description)
+    }
 }
 
+/// The location that the ship scanner picked up for an alternative, along with the scanned details.
 #[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct Choice(Vec<Alternative>);
+struct ScanReading {
+    location: String,
+    details: String,
+}
+
+/// A choice between location categories, along with the text that presents it.
+/// The text is resolved from the message catalog when the choice is made,
+/// since the frontends that show the choice do not have access to the assets.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(from = "ChoiceData")]
+pub struct Choice {
+    alternatives: Vec<Alternative>,
+    text_lines: Vec<String>,
+}
+
+/// Accepts choices from saves made before the presentation text was stored with the choice.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ChoiceData {
+    WithText {
+        alternatives: Vec<Alternative>,
+        text_lines: Vec<String>,
+    },
+    AlternativesOnly(Vec<Alternative>),
+}
+
+impl From<ChoiceData> for Choice {
+    fn from(value: ChoiceData) -> Self {
+        match value {
+            ChoiceData::WithText {
+                alternatives,
+                text_lines,
+            } => Self {
+                alternatives,
+                text_lines,
+            },
+            ChoiceData::AlternativesOnly(alternatives) => Self {
+                text_lines: alternatives.iter().map(Alternative::title_line).collect(),
+                alternatives,
+            },
+        }
+    }
+}
 
 impl Choice {
-    pub fn presentation_text_lines(&self) -> Vec<String> {
-        let Choice(alternatives) = self;
-        let mut text_lines = Vec::new();
-        text_lines.push("On the next planet, there are two destination targets:".to_owned());
-        for alternative in alternatives {
-            text_lines.push(format!(
-                "{}: {}",
-                text::capitalize(&alternative.name),
-                alternative.description
-            ));
+    fn new(alternatives: Vec<Alternative>, assets: &GameAssets) -> Self {
+        let mut text_lines = vec![assets.messages.text("location.choice.header", &[])];
+        for alternative in &alternatives {
+            text_lines.push(alternative.title_line());
+            if let Some(scan) = &alternative.scan {
+                text_lines.push(
+                    assets
+                        .messages
+                        .text("scan.readings", &[("details", &scan.details)]),
+                );
+            }
+        }
+        text_lines.push(assets.messages.text("location.choice.footer", &[]));
+        Self {
+            alternatives,
+            text_lines,
         }
-        text_lines.push("Pick the location to travel to next.".to_owned());
-        text_lines
+    }
+
+    pub fn presentation_text_lines(&self) -> Vec<String> {
+        self.text_lines.clone()
     }
 
     pub fn alternatives(&self) -> Vec<String> {
-        self.0
+        self.alternatives
             .iter()
             .map(|alternative| alternative.name.clone())
             .collect()
@@ -213,11 +321,10 @@ impl Choice {
 }
 
 impl Choice {
-    fn try_choose(&self, input: &str) -> Option<usize> {
-        self.0
+    fn try_choose(&self, input: &str) -> Option<&Alternative> {
+        self.alternatives
             .iter()
             .find(|alternative| alternative.name.eq_ignore_ascii_case(input))
-            .map(|alternative| alternative.index)
     }
 }
 
@@ -237,6 +344,7 @@ pub struct InitialSpawnData {
 pub(crate) fn spawn_starting_crew_and_ship(
     crew_data: CrewData,
     generation_state: &mut GenerationState,
+    rng: &mut GameRng,
     assets: &GameAssets,
 ) -> Result<InitialSpawnData, String> {
     let ship_data = LOCATION_DIR
@@ -248,7 +356,8 @@ pub(crate) fn spawn_starting_crew_and_ship(
         character_names: generation_state.character_names.clone(),
         aftik_color_names: generation_state.aftik_color_names.clone(),
         assets,
-        rng: rand::rng(),
+        rng: GameRng::from_rng(rng),
+        run_config: generation_state.run_config.clone(),
     };
     let build_data = generate::build_location(ship_data, &mut gen_context)?;
     let LocationGenContext {
@@ -257,6 +366,7 @@ pub(crate) fn spawn_starting_crew_and_ship(
         aftik_color_names,
         assets: _,
         mut rng,
+        run_config: _,
     } = gen_context;
     generation_state.character_names = character_names;
     generation_state.aftik_color_names = aftik_color_names;
@@ -281,6 +391,7 @@ pub(crate) fn spawn_starting_crew_and_ship(
         exit_pos: build_data.entry_pos,
         item_pos: food_deposit_pos,
         dialogue_pos: [ship_dialogue_pos1, ship_dialogue_pos2],
+        upgrades: Vec::new(),
     },));
     let crew = world.spawn((Points(crew_data.points),));
 
@@ -357,14 +468,40 @@ pub(crate) fn setup_location_into_game(
     messages: &mut Messages,
     state: &mut GameState,
     assets: &GameAssets,
+) -> Result<bool, String> {
+    let is_society = land_at_location(state, assets, |gen_context| {
+        generate::load_and_build_location(location_name, gen_context)
+            .map_err(|message| format!("Error loading location {location_name}: {message}"))
+    })?;
+
+    let message_id = if state.generation_state.is_at_fortuna() {
+        "location.arrive.fortuna"
+    } else {
+        "location.arrive"
+    };
+    messages.add(assets.messages.text(message_id, &[]));
+    Ok(is_society)
+}
+
+/// Builds the given location data and deploys the crew at it, in the same way as when the ship lands at a location.
+pub(crate) fn setup_location_data_into_game(
+    location_data: LocationData,
+    state: &mut GameState,
+    assets: &GameAssets,
+) -> Result<bool, String> {
+    land_at_location(state, assets, |gen_context| {
+        generate::build_location(location_data, gen_context)
+    })
+}
+
+fn land_at_location(
+    state: &mut GameState,
+    assets: &GameAssets,
+    build: impl FnOnce(&mut LocationGenContext<'_>) -> Result<LocationBuildData, String>,
 ) -> Result<bool, String> {
     let mut gen_context = LocationGenContext::clone_from(state, assets);
 
-    let build_data = LOCATION_DIR
-        .load(location_name)
-        .map_err(|error| error.to_string())
-        .and_then(|location_data| generate::build_location(location_data, &mut gen_context))
-        .map_err(|message| format!("Error loading location {location_name}: {message}"))?;
+    let build_data = build(&mut gen_context)?;
 
     gen_context.apply_to_game_state(state);
 
@@ -398,33 +535,28 @@ pub(crate) fn setup_location_into_game(
 
     deploy_crew_and_passengers_at_new_location(build_data.entry_pos, build_data.is_society, state);
 
-    if state.generation_state.is_at_fortuna() {
-        messages.add(
-            "The ship arrives at the location of the fortuna chest, and the crew exit the ship.",
-        )
-    } else {
-        messages.add("The ship arrives at a new location, and the crew exit the ship.");
-    }
     Ok(build_data.is_society)
 }
 
 pub struct LocationGenContext<'a> {
     world: World,
     character_names: Vec<String>,
-    aftik_color_names: HashMap<SpeciesColorId, Vec<String>>,
+    aftik_color_names: IndexMap<SpeciesColorId, Vec<String>>,
     assets: &'a GameAssets,
-    rng: ThreadRng,
+    rng: GameRng,
+    run_config: RunConfig,
 }
 
 impl<'a> LocationGenContext<'a> {
-    fn clone_from(state: &GameState, assets: &'a GameAssets) -> Self {
+    fn clone_from(state: &mut GameState, assets: &'a GameAssets) -> Self {
         Self {
             world: serialization::world::serialize_clone(&state.world)
                 .expect("Unexpected error when cloning world"),
             character_names: state.generation_state.character_names.clone(),
             aftik_color_names: state.generation_state.aftik_color_names.clone(),
             assets,
-            rng: rand::rng(),
+            rng: GameRng::from_rng(&mut state.rng),
+            run_config: state.generation_state.run_config.clone(),
         }
     }
 
@@ -434,7 +566,8 @@ impl<'a> LocationGenContext<'a> {
             character_names: Default::default(),
             aftik_color_names: Default::default(),
             assets,
-            rng: rand::rng(),
+            rng: GameRng::from_os_rng(),
+            run_config: RunConfig::default(),
         }
     }
 
@@ -495,6 +628,9 @@ pub fn despawn_all_except_ship(world: &mut World) {
     {
         buffer.insert_one(entity, Keep);
     }
+    for item in area::stored_items(world) {
+        buffer.insert_one(item, Keep);
+    }
     for (entity, _) in world
         .query::<(Entity, &Pos)>()
         .iter()
@@ -514,6 +650,9 @@ pub fn despawn_all_except_ship(world: &mut World) {
         if let Some(item) = inventory::get_wielded(world, entity) {
             buffer.insert_one(item, Keep);
         }
+        if let Some(item) = inventory::get_worn(world, entity) {
+            buffer.insert_one(item, Keep);
+        }
         for item in inventory::get_inventory(world, entity) {
             buffer.insert_one(item, Keep);
         }
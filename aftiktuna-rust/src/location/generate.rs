@@ -1,21 +1,24 @@
 pub(super) mod creature;
 pub(super) mod door;
+mod procedural;
 
 use super::LocationGenContext;
 use crate::asset::location::{
-    AreaData, ContainerData, DoorPairMap, FURNISH_DIR, FurnishTemplate, ItemOrLoot, LocationData,
-    SymbolData, SymbolLookup, SymbolMap,
+    AreaData, ContainerData, DoorPairMap, FURNISH_DIR, FurnishTemplate, ItemOrLoot, LOCATION_DIR,
+    LOCATION_GENERATOR_DIR, LocationData, SymbolData, SymbolLookup, SymbolMap,
 };
 use crate::asset::{self, GameAssets, loot};
-use crate::core::area::{Area, ShipControls};
+use crate::core::area::{self, Area, ShipControls};
 use crate::core::behavior::GivesHuntRewardData;
 use crate::core::display::ModelId;
+use crate::core::hazard::AreaHazards;
 use crate::core::inventory::{Container, Held};
 use crate::core::name::{NameIdData, NameQuery, NounId};
 use crate::core::position::{Coord, Pos};
 use crate::core::{FortunaChest, Tag, name};
 use crate::view::text;
 use hecs::{Entity, World};
+use rand::Rng;
 use rand::seq::IndexedRandom;
 
 pub struct LocationBuildData {
@@ -26,6 +29,84 @@ pub struct LocationBuildData {
     pub is_society: bool,
 }
 
+/// Loads and builds the location with the given name.
+/// If there is a location generator file with the name, the location is assembled from its rules,
+/// and otherwise the location is loaded from the location file with the name.
+pub fn load_and_build_location(
+    location_name: &str,
+    gen_context: &mut LocationGenContext<'_>,
+) -> Result<LocationBuildData, String> {
+    let location_data = if LOCATION_GENERATOR_DIR.file_path(location_name).exists() {
+        let generator_data = LOCATION_GENERATOR_DIR
+            .load(location_name)
+            .map_err(|error| error.to_string())?;
+        procedural::generate(&generator_data, &mut gen_context.rng)?
+    } else {
+        LOCATION_DIR
+            .load(location_name)
+            .map_err(|error| error.to_string())?
+    };
+    build_location(location_data, gen_context)
+}
+
+/// Describes what the ship scanner can tell about the location with the given name,
+/// such as the number of areas and creatures, and any darkness or hazards.
+pub(super) fn scan_location(location_name: &str, assets: &GameAssets) -> String {
+    if LOCATION_GENERATOR_DIR.file_path(location_name).exists() {
+        return assets.messages.text("scan.generated", &[]);
+    }
+    let (Ok(location_data), Ok(base_symbols)) = (
+        LOCATION_DIR.load(location_name),
+        asset::location::BASE_SYMBOLS_FILE.load(),
+    ) else {
+        return assets.messages.text("scan.no_readings", &[]);
+    };
+
+    let mut creature_count = 0;
+    let mut has_darkness = false;
+    let mut hazard_kinds = Vec::new();
+    for area_data in &location_data.areas {
+        let symbols = SymbolLookup::new(&base_symbols, &area_data.symbols);
+        creature_count += area_data
+            .objects
+            .iter()
+            .flat_map(|objects| objects.chars())
+            .filter(|&symbol| matches!(symbols.lookup(symbol), Some(SymbolData::Creature(_))))
+            .count();
+        has_darkness |= area_data.darkness >= area::DARKNESS_THRESHOLD;
+        for hazard in &area_data.hazards {
+            if !hazard_kinds.contains(&hazard.kind) {
+                hazard_kinds.push(hazard.kind);
+            }
+        }
+    }
+
+    let mut details = vec![
+        assets.messages.text(
+            "scan.areas",
+            &[("count", &location_data.areas.len().to_string())],
+        ),
+        assets
+            .messages
+            .text("scan.creatures", &[("count", &creature_count.to_string())]),
+    ];
+    if has_darkness {
+        details.push(assets.messages.text("scan.darkness", &[]));
+    }
+    if !hazard_kinds.is_empty() {
+        let hazard_names = hazard_kinds
+            .iter()
+            .map(|kind| kind.name(assets))
+            .collect::<Vec<_>>();
+        details.push(
+            assets
+                .messages
+                .text("scan.hazards", &[("hazards", &hazard_names.join(", "))]),
+        );
+    }
+    text::capitalize(details.join(", "))
+}
+
 pub fn build_location(
     location_data: LocationData,
     gen_context: &mut LocationGenContext<'_>,
@@ -47,6 +128,12 @@ pub fn build_location(
     }
 
     builder.door_pair_builder.verify_all_doors_placed()?;
+    door::verify_keys_exist(
+        &builder.gen_context.world,
+        &spawned_areas,
+        &builder.entry_positions,
+        builder.gen_context.assets,
+    )?;
 
     resolve_references(&builder.gen_context.world, builder.gen_context.assets);
 
@@ -75,10 +162,28 @@ fn build_area(
         background_offset: area_data.background_offset.unwrap_or(0),
         extra_background_layers: area_data.extra_background_layers,
         darkness: area_data.darkness,
+        pos_in_overview: area_data.pos_in_overview,
     },));
     if let Some(tag) = area_data.tag {
         builder.gen_context.world.insert_one(area, tag).unwrap();
     }
+    if !area_data.hazards.is_empty() {
+        if let Some(hazard) = area_data.hazards.iter().find(|hazard| {
+            hazard
+                .coord
+                .is_some_and(|coord| coord as usize >= area_data.objects.len())
+        }) {
+            return Err(format!(
+                "Hazard \"{}\" is placed outside the area",
+                hazard.kind.name(builder.gen_context.assets)
+            ));
+        }
+        builder
+            .gen_context
+            .world
+            .insert_one(area, AreaHazards(area_data.hazards))
+            .unwrap();
+    }
 
     let symbols = SymbolLookup::new(base_symbols, &area_data.symbols);
 
@@ -152,11 +257,13 @@ fn place_symbol(
             item.spawn(&mut builder.gen_context.world, pos);
         }
         SymbolData::Loot { table } => {
-            let item_type = builder
-                .loot_table_cache
-                .get_or_load(table)?
-                .pick_loot_item(&mut builder.gen_context.rng);
-            item_type.spawn(&mut builder.gen_context.world, pos);
+            for _ in 0..builder.roll_loot_count() {
+                let item_type = builder
+                    .loot_table_cache
+                    .get_or_load(table)?
+                    .pick_loot_item(&mut builder.gen_context.rng);
+                item_type.spawn(&mut builder.gen_context.world, pos);
+            }
         }
         SymbolData::Door(door_data) => door::place(door_data, pos, builder)?,
         SymbolData::Inanimate { model, direction } => {
@@ -229,6 +336,16 @@ impl<'a, 'b> Builder<'a, 'b> {
     fn add_entry_pos(&mut self, pos: Pos) {
         self.entry_positions.push(pos);
     }
+
+    /// The number of items to spawn from a loot table for a single loot entry.
+    /// This is one item at normal difficulty, with fewer items on average at higher difficulties.
+    fn roll_loot_count(&mut self) -> u32 {
+        let expected_count = 1. / self.gen_context.run_config.difficulty;
+        let whole_count = expected_count.trunc();
+        let remainder = expected_count - whole_count;
+        let extra_count = remainder > 0. && self.gen_context.rng.random_bool(f64::from(remainder));
+        whole_count as u32 + u32::from(extra_count)
+    }
 }
 
 fn place_fortuna_chest(world: &mut World, pos: Pos) {
@@ -259,17 +376,26 @@ fn generate_item_or_loot(
     container: Entity,
     builder: &mut Builder,
 ) -> Result<(), String> {
-    let item_type = match item_or_loot {
-        ItemOrLoot::Item { item } => item,
-        ItemOrLoot::Loot { table } => builder
-            .loot_table_cache
-            .get_or_load(table)?
-            .pick_loot_item(&mut builder.gen_context.rng),
-    };
-    item_type.spawn(
-        &mut builder.gen_context.world,
-        Held::in_inventory(container),
-    );
+    match item_or_loot {
+        ItemOrLoot::Item { item } => {
+            item.spawn(
+                &mut builder.gen_context.world,
+                Held::in_inventory(container),
+            );
+        }
+        ItemOrLoot::Loot { table } => {
+            for _ in 0..builder.roll_loot_count() {
+                let item_type = builder
+                    .loot_table_cache
+                    .get_or_load(table)?
+                    .pick_loot_item(&mut builder.gen_context.rng);
+                item_type.spawn(
+                    &mut builder.gen_context.world,
+                    Held::in_inventory(container),
+                );
+            }
+        }
+    }
     Ok(())
 }
 
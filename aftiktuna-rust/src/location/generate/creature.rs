@@ -58,6 +58,7 @@ pub(super) fn place_creature(
         aggressive,
         wandering,
         tag,
+        held_item,
         direction,
     } = spawn_data;
     let species_data = gen_context
@@ -97,6 +98,7 @@ pub(super) fn place_creature(
         attribute.adjust_stats(&mut stats);
         builder.add(attribute);
     }
+    gen_context.run_config.adjust_creature_stats(&mut stats);
 
     builder.add_bundle((pos, direction, health, Stamina::with_max(&stats), stats));
 
@@ -123,7 +125,13 @@ pub(super) fn place_creature(
         builder.add(Recruitable::default());
     }
 
-    gen_context.world.spawn(builder.build());
+    let creature = gen_context.world.spawn(builder.build());
+    if let Some(item_type) = held_item {
+        item_type.spawn(
+            &mut gen_context.world,
+            inventory::Held::in_inventory(creature),
+        );
+    }
     Ok(())
 }
 
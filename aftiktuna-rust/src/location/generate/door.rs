@@ -1,11 +1,14 @@
 use super::Builder;
+use crate::asset::GameAssets;
 use crate::asset::location::{DoorPairData, DoorSpawnData};
 use crate::core::display::ModelId;
+use crate::core::inventory::Held;
+use crate::core::item::ItemTypeId;
 use crate::core::name::{Adjective, NounId};
 use crate::core::position::Pos;
-use crate::core::{Door, DoorKind, IsCut};
+use crate::core::{BlockType, Door, DoorKind, IsCut, LockKey};
 use hecs::{Entity, World};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Clone)]
 pub(crate) struct DoorInfo {
@@ -22,10 +25,14 @@ pub(crate) fn place_pair(
     door2: DoorInfo,
     pair_data: &DoorPairData,
 ) -> (Entity, Entity) {
-    let door_pair = match pair_data.block_type {
-        Some(block_type) => world.spawn((block_type,)),
-        None => world.spawn(()),
-    };
+    let mut pair_builder = hecs::EntityBuilder::new();
+    if let Some(block_type) = pair_data.block_type {
+        pair_builder.add(block_type);
+    }
+    if let Some(key) = &pair_data.key {
+        pair_builder.add(LockKey(key.clone()));
+    }
+    let door_pair = world.spawn(pair_builder.build());
     let dest1 = door2.pos;
     let dest2 = door1.pos;
     (
@@ -89,6 +96,9 @@ impl DoorPairsBuilder {
             .0
             .get_mut(pair_id)
             .ok_or_else(|| format!("Unknown door id \"{pair_id}\""))?;
+        if data.block_type == Some(BlockType::Locked) && data.key.is_none() {
+            return Err(format!("Locked door pair \"{pair_id}\" has no key"));
+        }
 
         *status = match status {
             DoorPairStatus::None => DoorPairStatus::One(door_info),
@@ -114,6 +124,67 @@ impl DoorPairsBuilder {
     }
 }
 
+/// Checks that the location placed a key for each of its locked door pairs, and that the key
+/// can be reached from every entry position without going through the door pair that it unlocks.
+/// Keys held by the crew or stored in the ship are not counted.
+pub(super) fn verify_keys_exist(
+    world: &World,
+    spawned_areas: &[Entity],
+    entry_positions: &[Pos],
+    assets: &GameAssets,
+) -> Result<(), String> {
+    for (door_pair, key) in &mut world.query::<(Entity, &LockKey)>() {
+        let key_areas = world
+            .query::<(Entity, &ItemTypeId)>()
+            .iter()
+            .filter(|(_, item_type)| item_type.fits_lock(key, assets))
+            .filter_map(|(item, _)| item_area(world, item))
+            .filter(|area| spawned_areas.contains(area))
+            .collect::<Vec<_>>();
+        if key_areas.is_empty() {
+            return Err(format!("No key was placed for the lock key \"{}\"", key.0));
+        }
+        let is_key_reachable = entry_positions.iter().all(|entry_pos| {
+            let reachable_areas = find_reachable_areas(world, entry_pos.get_area(), door_pair);
+            key_areas.iter().any(|area| reachable_areas.contains(area))
+        });
+        if !is_key_reachable {
+            return Err(format!(
+                "The key for the lock key \"{}\" can only be reached through the door that it unlocks",
+                key.0
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// The area of an item, or of its holder if it is held by something, such as a container.
+fn item_area(world: &World, item: Entity) -> Option<Entity> {
+    let holder = world.get::<&Held>(item).map_or(item, |held| held.holder);
+    world.get::<&Pos>(holder).ok().map(|pos| pos.get_area())
+}
+
+/// Finds the areas that can be reached from the start area without using the excluded door pair.
+fn find_reachable_areas(
+    world: &World,
+    start_area: Entity,
+    excluded_pair: Entity,
+) -> HashSet<Entity> {
+    let mut reachable_areas = HashSet::from([start_area]);
+    let mut areas_to_visit = vec![start_area];
+    while let Some(area) = areas_to_visit.pop() {
+        for (pos, door) in &mut world.query::<(&Pos, &Door)>() {
+            if pos.is_in(area)
+                && door.door_pair != excluded_pair
+                && reachable_areas.insert(door.destination.get_area())
+            {
+                areas_to_visit.push(door.destination.get_area());
+            }
+        }
+    }
+    reachable_areas
+}
+
 pub(super) fn place(
     spawn_data: &DoorSpawnData,
     pos: Pos,
@@ -0,0 +1,434 @@
+//! Assembly of locations from the rules of a location generator file.
+//! The result is ordinary location data, which is then built the same way as a location from a location file.
+
+use crate::asset::location::{
+    AreaData, Budget, CountRange, DoorAdjective, DoorPairData, DoorPairMap, DoorSpawnData,
+    DoorType, FURNISH_DIR, LocationData, LocationGeneratorData, SymbolData, SymbolMap,
+};
+use rand::Rng;
+use rand::seq::IndexedRandom;
+
+/// Doors in the same area are told apart by their adjective, which limits the number of doors per area.
+const MAX_DOORS_PER_AREA: usize = 3;
+/// Horizontal space kept between neighbouring areas in the overview.
+const OVERVIEW_PADDING: usize = 2;
+
+pub fn generate(
+    generator_data: &LocationGeneratorData,
+    rng: &mut impl Rng,
+) -> Result<LocationData, String> {
+    let CountRange { min, max } = generator_data.area_count;
+    if min == 0 || min > max {
+        return Err(format!("Invalid area count range {min}-{max}"));
+    }
+    let area_count = usize::from(rng.random_range(min..=max));
+
+    let mut areas = Vec::with_capacity(area_count);
+    for _ in 0..area_count {
+        areas.push(pick_area(generator_data, rng)?);
+    }
+    areas[0].add_symbol(SymbolData::LocationEntry, rng)?;
+
+    let connections = pick_connections(&areas, generator_data.connections.extra_connections, rng)?;
+    let mut door_pairs = DoorPairMap::new();
+    let mut open_connections = Vec::new();
+    for (index, &(area1, area2)) in connections.iter().enumerate() {
+        let pair_id = format!("connection{index}");
+        let door_type = generator_data
+            .connections
+            .door_types
+            .choose_weighted(rng, |entry| entry.weight)
+            .map_err(|error| format!("Unable to pick door type: {error}"))?
+            .door_type;
+        areas[area1].doors.push((pair_id.clone(), door_type));
+        areas[area2]
+            .doors
+            .push((pair_id.clone(), opposite_door_type(door_type)));
+
+        let block_type = if area1 == 0 || area2 == 0 {
+            None
+        } else {
+            generator_data
+                .connections
+                .block_chances
+                .iter()
+                .find(|block_chance| rng.random_bool(f64::from(block_chance.chance)))
+                .map(|block_chance| block_chance.block_type)
+        };
+        if block_type.is_none() {
+            open_connections.push((area1, area2));
+        }
+        door_pairs.insert(
+            pair_id,
+            DoorPairData {
+                block_type,
+                ..DoorPairData::default()
+            },
+        );
+    }
+
+    for area in &mut areas {
+        area.place_doors(rng)?;
+    }
+    let inner_areas = if area_count > 1 { 1..area_count } else { 0..1 };
+    // Fixed symbols tend to be needed to progress, so they are kept out of reach of blocked doors.
+    let open_areas = reachable_areas(&open_connections);
+    let fixed_symbol_areas = if open_areas.len() > 1 {
+        &open_areas[1..]
+    } else {
+        &open_areas[..]
+    };
+    for symbol_data in &generator_data.fixed_symbols {
+        let area = &mut areas[*fixed_symbol_areas.choose(rng).unwrap()];
+        let coord = rng.random_range(0..area.size());
+        area.add_symbol_at(coord, symbol_data.clone());
+    }
+    spend_budget(&generator_data.creatures, &mut areas, inner_areas, rng)?;
+    spend_budget(&generator_data.loot, &mut areas, 0..area_count, rng)?;
+
+    let area_sizes = areas.iter().map(AreaBuilder::size).collect::<Vec<_>>();
+    let overview_positions = overview_positions(&area_sizes, &connections);
+    Ok(LocationData {
+        is_society: generator_data.is_society,
+        variants: Vec::new(),
+        areas: areas
+            .into_iter()
+            .zip(overview_positions)
+            .map(|(area, pos_in_overview)| AreaData {
+                pos_in_overview,
+                ..area.data
+            })
+            .collect(),
+        door_pairs,
+    })
+}
+
+struct AreaBuilder {
+    data: AreaData,
+    /// Coordinates reserved for the entry point and doors, so that these are not placed on top of each other.
+    reserved_coords: Vec<usize>,
+    doors: Vec<(String, DoorType)>,
+}
+
+impl AreaBuilder {
+    fn size(&self) -> usize {
+        self.data.objects.len()
+    }
+
+    fn door_capacity(&self) -> usize {
+        self.size()
+            .saturating_sub(self.reserved_coords.len())
+            .min(MAX_DOORS_PER_AREA)
+    }
+
+    fn add_symbol_at(&mut self, coord: usize, symbol_data: SymbolData) {
+        // Symbols from the private use area of unicode can not collide with the base symbols.
+        let symbol = char::from_u32(0xE000 + self.data.symbols.len() as u32).unwrap();
+        self.data.symbols.insert(symbol, symbol_data);
+        self.data.objects[coord].push(symbol);
+    }
+
+    fn free_coords(&self) -> Vec<usize> {
+        (0..self.size())
+            .filter(|coord| !self.reserved_coords.contains(coord))
+            .collect()
+    }
+
+    /// Adds the symbol at a random coordinate that is not reserved, and then reserves that coordinate.
+    fn add_symbol(&mut self, symbol_data: SymbolData, rng: &mut impl Rng) -> Result<usize, String> {
+        let free_coords = self.free_coords();
+        let &coord = free_coords
+            .choose(rng)
+            .ok_or_else(|| format!("No room left in area \"{}\"", self.data.name))?;
+        self.add_symbol_at(coord, symbol_data);
+        self.reserved_coords.push(coord);
+        Ok(coord)
+    }
+
+    fn place_doors(&mut self, rng: &mut impl Rng) -> Result<(), String> {
+        let mut door_coords = Vec::with_capacity(self.doors.len());
+        for (pair_id, door_type) in std::mem::take(&mut self.doors) {
+            let coord = self.add_symbol(
+                SymbolData::Door(DoorSpawnData {
+                    pair_id,
+                    door_type,
+                    model: None,
+                    adjective: None,
+                }),
+                rng,
+            )?;
+            door_coords.push((coord, self.data.symbols.len() - 1));
+        }
+
+        door_coords.sort_unstable();
+        let adjectives: &[DoorAdjective] = match door_coords.len() {
+            0 | 1 => &[],
+            2 => &[DoorAdjective::Left, DoorAdjective::Right],
+            _ => DoorAdjective::variants(),
+        };
+        for (&(_, symbol_index), &adjective) in door_coords.iter().zip(adjectives) {
+            if let Some((_, SymbolData::Door(door_data))) =
+                self.data.symbols.get_index_mut(symbol_index)
+            {
+                door_data.adjective = Some(adjective);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn pick_area(
+    generator_data: &LocationGeneratorData,
+    rng: &mut impl Rng,
+) -> Result<AreaBuilder, String> {
+    let area_type = generator_data
+        .area_types
+        .choose_weighted(rng, |area_type| area_type.weight)
+        .map_err(|error| format!("Unable to pick area type: {error}"))?;
+    let area_size = area_type
+        .sizes
+        .choose(rng)
+        .ok_or_else(|| format!("Area type \"{}\" has no sizes", area_type.name))?;
+    let size = usize::from(area_size.size);
+    if size == 0 {
+        return Err(format!(
+            "Area type \"{}\" has an empty size",
+            area_type.name
+        ));
+    }
+
+    let mut area = AreaBuilder {
+        data: AreaData {
+            name: area_type.name.clone(),
+            pos_in_overview: (0, 0),
+            tag: None,
+            background: area_size.background.clone(),
+            background_offset: None,
+            extra_background_layers: Vec::new(),
+            darkness: area_type.darkness,
+            hazards: Vec::new(),
+            objects: vec![String::new(); size],
+            variant_objects: Default::default(),
+            symbols: SymbolMap::new(),
+        },
+        reserved_coords: Vec::new(),
+        doors: Vec::new(),
+    };
+
+    if let Some(template) = area_type.furnish.choose(rng) {
+        let template_length = FURNISH_DIR
+            .load(template)
+            .map_err(|error| error.to_string())?
+            .iter()
+            .map(|template_data| template_data.objects.len())
+            .max()
+            .unwrap_or(0);
+        if template_length > size {
+            return Err(format!(
+                "Furnish template \"{template}\" does not fit in area \"{}\" of size {size}",
+                area_type.name
+            ));
+        }
+        let coord = rng.random_range(0..=size - template_length);
+        area.add_symbol_at(
+            coord,
+            SymbolData::Furnish {
+                template: template.clone(),
+            },
+        );
+    }
+    Ok(area)
+}
+
+/// Picks pairs of areas to connect with doors.
+/// Each area is first connected to one of the areas before it, so that all areas can be reached,
+/// and then the extra connections are added between areas that are not yet connected.
+fn pick_connections(
+    areas: &[AreaBuilder],
+    extra_connections: u16,
+    rng: &mut impl Rng,
+) -> Result<Vec<(usize, usize)>, String> {
+    let mut connections: Vec<(usize, usize)> = Vec::new();
+    let mut door_counts = vec![0; areas.len()];
+    let has_room =
+        |door_counts: &[usize], area: usize| door_counts[area] < areas[area].door_capacity();
+
+    for area in 1..areas.len() {
+        let candidates = (0..area)
+            .filter(|&other| has_room(&door_counts, other))
+            .collect::<Vec<_>>();
+        let &other = candidates
+            .choose(rng)
+            .ok_or("No area has room for another door")?;
+        if !has_room(&door_counts, area) {
+            return Err(format!(
+                "Area \"{}\" has no room for a door",
+                areas[area].data.name
+            ));
+        }
+        connections.push((other, area));
+        door_counts[other] += 1;
+        door_counts[area] += 1;
+    }
+
+    for _ in 0..extra_connections {
+        let candidates = (0..areas.len())
+            .flat_map(|area1| ((area1 + 1)..areas.len()).map(move |area2| (area1, area2)))
+            .filter(|&(area1, area2)| {
+                has_room(&door_counts, area1)
+                    && has_room(&door_counts, area2)
+                    && !connections.contains(&(area1, area2))
+            })
+            .collect::<Vec<_>>();
+        let Some(&(area1, area2)) = candidates.choose(rng) else {
+            break;
+        };
+        connections.push((area1, area2));
+        door_counts[area1] += 1;
+        door_counts[area2] += 1;
+    }
+    Ok(connections)
+}
+
+fn opposite_door_type(door_type: DoorType) -> DoorType {
+    match door_type {
+        DoorType::UpwardStairs => DoorType::DownwardStairs,
+        DoorType::DownwardStairs => DoorType::UpwardStairs,
+        door_type => door_type,
+    }
+}
+
+fn spend_budget(
+    budget: &Budget,
+    areas: &mut [AreaBuilder],
+    area_range: std::ops::Range<usize>,
+    rng: &mut impl Rng,
+) -> Result<(), String> {
+    let mut points = budget.points;
+    loop {
+        let affordable_entries = budget
+            .entries
+            .iter()
+            .filter(|entry| entry.cost > 0 && entry.cost <= points)
+            .collect::<Vec<_>>();
+        let Ok(entry) = affordable_entries.choose_weighted(rng, |entry| entry.weight) else {
+            return Ok(());
+        };
+        points -= entry.cost;
+
+        // Creatures and loot are kept off the doors and the entry point.
+        let candidates = area_range
+            .clone()
+            .filter_map(|area| {
+                let free_coords = areas[area].free_coords();
+                (!free_coords.is_empty()).then_some((area, free_coords))
+            })
+            .collect::<Vec<_>>();
+        let Some((area, free_coords)) = candidates.choose(rng) else {
+            return Ok(());
+        };
+        let &coord = free_coords.choose(rng).unwrap();
+        areas[*area].add_symbol_at(coord, entry.symbol.clone());
+    }
+}
+
+/// The areas that can be reached from the entry area through the given connections, starting with the entry area.
+fn reachable_areas(connections: &[(usize, usize)]) -> Vec<usize> {
+    let mut reached = vec![0];
+    let mut index = 0;
+    while let Some(&area) = reached.get(index) {
+        for &(area1, area2) in connections {
+            let other = if area1 == area {
+                area2
+            } else if area2 == area {
+                area1
+            } else {
+                continue;
+            };
+            if !reached.contains(&other) {
+                reached.push(other);
+            }
+        }
+        index += 1;
+    }
+    reached
+}
+
+/// Lays out the areas in the overview by their distance from the entry area.
+/// Columns are spaced by the widest area, so that areas at the same depth do not overlap.
+fn overview_positions(area_sizes: &[usize], connections: &[(usize, usize)]) -> Vec<(i32, i32)> {
+    let area_count = area_sizes.len();
+    let column_width = area_sizes.iter().copied().max().unwrap_or(0) + OVERVIEW_PADDING;
+    let mut depths = vec![0; area_count];
+    // Each area is connected to an earlier area first, so the depth of that area is already known.
+    for area in 1..area_count {
+        if let Some(&(parent, _)) = connections.iter().find(|&&(_, child)| child == area) {
+            depths[area] = depths[parent] + 1;
+        }
+    }
+    let mut areas_at_depth = vec![0; area_count];
+    depths
+        .into_iter()
+        .map(|depth| {
+            let column = areas_at_depth[depth];
+            areas_at_depth[depth] += 1;
+            ((column * column_width) as i32, depth as i32 * 4)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::asset::location::{LOCATION_GENERATOR_DIR, SymbolData};
+    use crate::game_loop::GameRng;
+    use rand::SeedableRng;
+    use std::collections::HashMap;
+
+    #[test]
+    fn generated_facility_connects_all_door_pairs() {
+        let generator_data = LOCATION_GENERATOR_DIR.load("generated_facility").unwrap();
+        for seed in 0..20 {
+            let mut rng = GameRng::seed_from_u64(seed);
+            let location_data = super::generate(&generator_data, &mut rng).unwrap();
+
+            let area_count = location_data.areas.len() as u16;
+            assert!(generator_data.area_count.min <= area_count);
+            assert!(area_count <= generator_data.area_count.max);
+
+            let mut door_counts = HashMap::<&str, usize>::new();
+            for area in &location_data.areas {
+                for symbol in area.objects.iter().flat_map(|objects| objects.chars()) {
+                    if let Some(SymbolData::Door(door_data)) = area.symbols.get(&symbol) {
+                        *door_counts.entry(&door_data.pair_id).or_default() += 1;
+                    }
+                }
+            }
+            assert_eq!(door_counts.len(), location_data.door_pairs.len());
+            assert!(door_counts.values().all(|&count| count == 2));
+        }
+    }
+
+    #[test]
+    fn generated_facility_overview_has_no_overlapping_areas() {
+        let generator_data = LOCATION_GENERATOR_DIR.load("generated_facility").unwrap();
+        for seed in 0..20 {
+            let mut rng = GameRng::seed_from_u64(seed);
+            let location_data = super::generate(&generator_data, &mut rng).unwrap();
+
+            let spans = location_data
+                .areas
+                .iter()
+                .map(|area| {
+                    let (x, y) = area.pos_in_overview;
+                    let start = x - area.objects.len() as i32 / 2;
+                    (y, start, start + area.objects.len() as i32)
+                })
+                .collect::<Vec<_>>();
+            for (index, &(y1, start1, end1)) in spans.iter().enumerate() {
+                for &(y2, start2, end2) in &spans[index + 1..] {
+                    assert!(y1 != y2 || end1 <= start2 || end2 <= start1);
+                }
+            }
+        }
+    }
+}
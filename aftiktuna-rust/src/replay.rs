@@ -0,0 +1,206 @@
+//! Recording of the inputs of a run, so that the run can be replayed and verified later.
+
+use crate::game_interface::{self, Game, GameResult};
+use crate::location::GenerationState;
+use crate::run_config::RunConfig;
+use crate::view::Frame;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io;
+use std::io::BufReader;
+use std::path::Path;
+
+/// Describes how the recorded run was set up.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum Start {
+    /// A regular new game, using the locations from the locations file.
+    NewGame,
+    /// A game with only the given location, as used when testing a location.
+    SingleLocation(String),
+}
+
+impl Start {
+    pub(crate) fn generation_state(
+        &self,
+        run_config: RunConfig,
+    ) -> Result<GenerationState, String> {
+        match self {
+            Start::NewGame => GenerationState::load_new(run_config),
+            Start::SingleLocation(location) => {
+                GenerationState::single(location.to_owned(), run_config)
+            }
+        }
+        .map_err(|error| error.to_string())
+    }
+}
+
+/// The recorded inputs of a run together with the frames that were produced from them.
+#[derive(Serialize, Deserialize)]
+pub struct Replay {
+    pub start: Start,
+    /// Absent in replays from before run configurations were added, which then use the default configuration.
+    #[serde(default)]
+    pub run_config: RunConfig,
+    pub seed: u64,
+    initial_frames: Vec<Frame>,
+    steps: Vec<RecordedInput>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RecordedInput {
+    input: String,
+    frames: Vec<Frame>,
+}
+
+impl Replay {
+    pub(crate) fn new(start: Start, run_config: RunConfig, seed: u64) -> Self {
+        Self {
+            start,
+            run_config,
+            seed,
+            initial_frames: Vec::new(),
+            steps: Vec::new(),
+        }
+    }
+
+    pub(crate) fn add_input(&mut self, input: &str) {
+        self.steps.push(RecordedInput {
+            input: input.to_owned(),
+            frames: Vec::new(),
+        });
+    }
+
+    pub(crate) fn add_frames(&mut self, frames: impl IntoIterator<Item = Frame>) {
+        match self.steps.last_mut() {
+            Some(step) => step.frames.extend(frames),
+            None => self.initial_frames.extend(frames),
+        }
+    }
+
+    pub fn input_count(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Runs the recorded inputs on a new game and checks that the same frames are produced.
+    pub fn verify(&self) -> Result<(), String> {
+        let mut game = game_interface::setup_new_with(
+            self.start.generation_state(self.run_config.clone())?,
+            Some(self.seed),
+        )?;
+
+        compare_frames(
+            &self.initial_frames,
+            collect_frames(&mut game),
+            "the start of the run",
+        )?;
+        for (index, step) in self.steps.iter().enumerate() {
+            let input_description = format!("input {} (\"{}\")", index + 1, step.input);
+            if !game.ready_to_take_input() {
+                return Err(format!("The game did not take {input_description}"));
+            }
+            game.handle_input(&step.input).map_err(|info| {
+                format!(
+                    "The game did not accept {input_description}: {}",
+                    info.into_text().join(" ")
+                )
+            })?;
+            compare_frames(&step.frames, collect_frames(&mut game), &input_description)?;
+        }
+        Ok(())
+    }
+}
+
+fn collect_frames(game: &mut Game) -> Vec<Frame> {
+    let mut frames = Vec::new();
+    while let GameResult::Frame(frame_getter) = game.next_result() {
+        frames.push(frame_getter.get());
+    }
+    frames
+}
+
+fn compare_frames(expected: &[Frame], actual: Vec<Frame>, context: &str) -> Result<(), String> {
+    for (index, (expected, actual)) in expected.iter().zip(&actual).enumerate() {
+        if serde_json::to_value(expected).ok() != serde_json::to_value(actual).ok() {
+            return Err(format!(
+                "Frame {} after {context} differs.\nExpected:\n{}\nGot:\n{}",
+                index + 1,
+                expected.get_messages().join("\n"),
+                actual.get_messages().join("\n"),
+            ));
+        }
+    }
+    if expected.len() != actual.len() {
+        return Err(format!(
+            "Expected {} frames after {context}, but got {}.",
+            expected.len(),
+            actual.len(),
+        ));
+    }
+    Ok(())
+}
+
+pub enum ReplayError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    NotRecording,
+}
+
+impl From<io::Error> for ReplayError {
+    fn from(value: io::Error) -> Self {
+        ReplayError::Io(value)
+    }
+}
+
+impl From<serde_json::Error> for ReplayError {
+    fn from(value: serde_json::Error) -> Self {
+        ReplayError::Json(value)
+    }
+}
+
+impl Display for ReplayError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplayError::Io(error) => Display::fmt(error, f),
+            ReplayError::Json(error) => Display::fmt(error, f),
+            ReplayError::NotRecording => write!(f, "The game is not being recorded."),
+        }
+    }
+}
+
+pub fn write_replay_file(game: &Game, path: impl AsRef<Path>) -> Result<(), ReplayError> {
+    let replay = game.replay().ok_or(ReplayError::NotRecording)?;
+    serde_json::to_writer(File::create(path)?, replay)?;
+    Ok(())
+}
+
+pub fn load_replay_file(path: impl AsRef<Path>) -> Result<Replay, ReplayError> {
+    let file = File::open(path)?;
+    Ok(serde_json::from_reader(BufReader::new(file))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Start;
+    use crate::game_interface;
+    use crate::run_config::RunConfig;
+
+    #[test]
+    fn recorded_run_verifies() {
+        let mut game = game_interface::setup_recorded(
+            Start::SingleLocation("goblin_forest".to_owned()),
+            RunConfig::default(),
+            Some(5),
+        )
+        .unwrap();
+        for input in ["status", "wait", "wait", "control mint", "wait"] {
+            super::collect_frames(&mut game);
+            let _ = game.handle_input(input);
+        }
+        super::collect_frames(&mut game);
+
+        let replay = game.replay().unwrap();
+        assert!(replay.input_count() > 0);
+        assert_eq!(replay.verify(), Ok(()));
+    }
+}
@@ -0,0 +1,155 @@
+//! Settings that are chosen when starting a new run, and that then stay the same for the whole run.
+
+use crate::asset::profile::ProfileOrRandom;
+use crate::asset::species::{SPECIES_FILE, SpeciesData, SpeciesDataMap, SpeciesKind};
+use crate::asset::{self, CrewData};
+use crate::core::status::Stats;
+use crate::core::{CREW_SIZE_LIMIT, SpeciesId};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RunConfig {
+    pub locations_before_fortuna: i32,
+    /// Replaces the points from the starting crew file when set.
+    pub starting_points: Option<i32>,
+    /// Replaces the crew from the starting crew file with random characters of these species when set.
+    pub crew_species: Option<Vec<SpeciesId>>,
+    /// Scales the stats of creatures, with loot becoming scarcer as the difficulty goes up.
+    /// A difficulty of 1 leaves both unchanged.
+    pub difficulty: f32,
+}
+
+impl Default for RunConfig {
+    fn default() -> Self {
+        Self {
+            locations_before_fortuna: 3,
+            starting_points: None,
+            crew_species: None,
+            difficulty: 1.,
+        }
+    }
+}
+
+impl RunConfig {
+    pub const MIN_DIFFICULTY: f32 = 0.5;
+    pub const MAX_DIFFICULTY: f32 = 2.;
+
+    pub(crate) fn validate(&self, species_map: &SpeciesDataMap) -> Result<(), String> {
+        if self.locations_before_fortuna < 1 {
+            return Err("There must be at least one location before Fortuna".to_owned());
+        }
+        if !(Self::MIN_DIFFICULTY..=Self::MAX_DIFFICULTY).contains(&self.difficulty) {
+            return Err(format!(
+                "The difficulty must be between {} and {}",
+                Self::MIN_DIFFICULTY,
+                Self::MAX_DIFFICULTY
+            ));
+        }
+        if let Some(points) = self.starting_points
+            && points < 0
+        {
+            return Err("The starting points must not be negative".to_owned());
+        }
+        if let Some(crew_species) = &self.crew_species {
+            if crew_species.is_empty() || crew_species.len() > CREW_SIZE_LIMIT {
+                return Err(format!(
+                    "The crew must have between 1 and {CREW_SIZE_LIMIT} members"
+                ));
+            }
+            for species in crew_species {
+                if !matches!(
+                    species_map.get(species),
+                    Some(SpeciesData {
+                        kind: SpeciesKind::CharacterSpecies,
+                        ..
+                    })
+                ) {
+                    return Err(format!("\"{species}\" is not a character species"));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn apply_to_crew_data(&self, crew_data: &mut CrewData) {
+        if let Some(points) = self.starting_points {
+            crew_data.points = points;
+        }
+        if let Some(crew_species) = &self.crew_species {
+            crew_data.crew = crew_species
+                .iter()
+                .map(|species| ProfileOrRandom::Random {
+                    species: species.clone(),
+                    stats_bonus: 0,
+                })
+                .collect();
+        }
+    }
+
+    pub(crate) fn adjust_creature_stats(&self, stats: &mut Stats) {
+        if self.difficulty == 1. {
+            return;
+        }
+        for stat in [
+            &mut stats.strength,
+            &mut stats.endurance,
+            &mut stats.agility,
+        ] {
+            *stat = ((f32::from(*stat) * self.difficulty).round() as i16).clamp(1, 10);
+        }
+    }
+}
+
+/// The species that may be picked for the starting crew.
+pub fn selectable_crew_species() -> Result<Vec<SpeciesId>, asset::Error> {
+    let mut species = SPECIES_FILE.load()?.into_keys().collect::<Vec<_>>();
+    species.sort_by_key(ToString::to_string);
+    Ok(species)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RunConfig;
+    use crate::core::status::Stats;
+
+    #[test]
+    fn difficulty_scales_creature_stats() {
+        let config = RunConfig {
+            difficulty: 1.5,
+            ..RunConfig::default()
+        };
+        let mut stats = Stats::new(4, 2, 1, 3);
+        config.adjust_creature_stats(&mut stats);
+        assert_eq!(
+            (stats.strength, stats.endurance, stats.agility, stats.luck),
+            (6, 3, 2, 3)
+        );
+
+        let mut stats = Stats::new(4, 2, 1, 3);
+        RunConfig::default().adjust_creature_stats(&mut stats);
+        assert_eq!(
+            (stats.strength, stats.endurance, stats.agility, stats.luck),
+            (4, 2, 1, 3)
+        );
+    }
+
+    #[test]
+    fn scaled_creature_stats_stay_within_bounds() {
+        let config = RunConfig {
+            difficulty: 3.,
+            ..RunConfig::default()
+        };
+        let mut stats = Stats::new(8, 2, 1, 3);
+        config.adjust_creature_stats(&mut stats);
+        assert_eq!((stats.strength, stats.endurance, stats.agility), (10, 6, 3));
+
+        let config = RunConfig {
+            difficulty: 0.1,
+            ..RunConfig::default()
+        };
+        let mut stats = Stats::new(8, 2, 1, 3);
+        config.adjust_creature_stats(&mut stats);
+        assert_eq!((stats.strength, stats.endurance, stats.agility), (1, 1, 1));
+    }
+}
@@ -0,0 +1,680 @@
+//! Support for testing gameplay through scripted scenarios.
+//! A scenario loads a single location, runs a script of commands,
+//! and then lets the test check the produced frames and the state of the world.
+
+use crate::core::area::{Area, ShipState, ShipStatus};
+use crate::core::behavior::BehaviorProfile;
+use crate::core::inventory::{self, Held};
+use crate::core::item::ItemTypeId;
+use crate::core::position::Pos;
+use crate::core::{BlockType, Door};
+use crate::game_interface::{self, Game, GameResult};
+use crate::game_loop::GameState;
+use crate::location::{self, GenerationState};
+use crate::run_config::RunConfig;
+use crate::view::Frame;
+use crate::view::event::GameEvent;
+use crate::view::text::Messages;
+use hecs::{Entity, World};
+
+pub(crate) struct Scenario {
+    game: Game,
+    frames: Vec<Frame>,
+}
+
+impl Scenario {
+    pub fn load(location: &str) -> Self {
+        Self::load_with_seed(location, 0)
+    }
+
+    pub fn load_with_seed(location: &str, seed: u64) -> Self {
+        let locations = GenerationState::single(location.to_owned(), RunConfig::default()).unwrap();
+        let mut game = game_interface::setup_new_with(locations, Some(seed)).unwrap();
+        let frames = collect_frames(&mut game);
+        Self { game, frames }
+    }
+
+    /// Replaces the behavior profile of a species, for testing profiles that no species uses yet.
+    pub fn set_behavior_profile(&mut self, species: &str, profile: BehaviorProfile) -> &mut Self {
+        self.game
+            .assets_mut()
+            .species_data_map
+            .get_mut(&species.into())
+            .unwrap()
+            .behavior = profile;
+        self
+    }
+
+    /// Gives the character a new item of the given item type.
+    pub fn give_item(&mut self, character: Entity, item_type: &str) -> &mut Self {
+        let (state, _) = self.game.state_and_assets_mut();
+        ItemTypeId::from(item_type).spawn(&mut state.world, Held::in_inventory(character));
+        self
+    }
+
+    /// Tries to build the location and deploy the crew at it, in the same way as when the ship lands.
+    pub fn try_land_at(&mut self, location: &str) -> Result<(), String> {
+        let (state, assets) = self.game.state_and_assets_mut();
+        location::setup_location_into_game(location, &mut Messages::default(), state, assets)
+            .map(|_| ())
+    }
+
+    /// Runs the commands in order, and panics if any of them is not accepted by the game.
+    pub fn run(&mut self, commands: &[&str]) -> &mut Self {
+        for command in commands {
+            if let Err(response) = self.try_run(command) {
+                panic!(
+                    "The command \"{command}\" was not accepted: {}",
+                    response.join(" ")
+                );
+            }
+        }
+        self
+    }
+
+    /// Runs a single command, and returns the response if it was not accepted by the game.
+    pub fn try_run(&mut self, command: &str) -> Result<(), Vec<String>> {
+        assert!(
+            self.game.ready_to_take_input(),
+            "The game is not ready to take the command \"{command}\""
+        );
+        self.game
+            .handle_input(command)
+            .map_err(crate::CommandInfo::into_text)?;
+        self.frames = collect_frames(&mut self.game);
+        Ok(())
+    }
+
+    /// The frames produced by the latest command.
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    pub fn messages(&self) -> Vec<String> {
+        self.frames().iter().flat_map(Frame::get_messages).collect()
+    }
+
+    pub fn events(&self) -> Vec<GameEvent> {
+        self.frames()
+            .iter()
+            .flat_map(|frame| frame.get_events().iter().cloned())
+            .collect()
+    }
+
+    /// The names of the objects shown in the latest area view.
+    pub fn visible_names(&self) -> Vec<String> {
+        self.frames()
+            .iter()
+            .rev()
+            .find_map(|frame| match frame {
+                Frame::AreaView { render_data, .. } => Some(render_data),
+                _ => None,
+            })
+            .map(|render_data| {
+                render_data
+                    .objects
+                    .iter()
+                    .filter_map(|object| object.name_data.as_ref())
+                    .map(|name_data| name_data.name.clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn assert_message(&self, text: &str) {
+        let messages = self.messages();
+        assert!(
+            messages.iter().any(|message| message.contains(text)),
+            "Expected a message containing \"{text}\", but got:\n{}",
+            messages.join("\n")
+        );
+    }
+
+    pub fn state(&self) -> &GameState {
+        &self.game.serialized_state.state
+    }
+
+    pub fn world(&self) -> &World {
+        &self.state().world
+    }
+
+    pub fn controlled(&self) -> Entity {
+        self.state().controlled
+    }
+
+    /// Checks if the character holds an item of the given item type, either in their inventory or wielded.
+    pub fn is_holding(&self, character: Entity, item_type: &str) -> bool {
+        inventory::is_holding(
+            |item_type_id| item_type_id.to_string() == item_type,
+            self.world(),
+            character,
+        )
+    }
+
+    pub fn area_label(&self, entity: Entity) -> String {
+        let area = self.world().get::<&Pos>(entity).unwrap().get_area();
+        self.world().get::<&Area>(area).unwrap().label.clone()
+    }
+
+    pub fn ship_status(&self) -> ShipStatus {
+        self.world()
+            .get::<&ShipState>(self.state().ship_core)
+            .unwrap()
+            .status
+    }
+
+    /// Checks if any door in the area of the given entity is blocked.
+    pub fn has_blocked_door_in_area(&self, entity: Entity) -> bool {
+        let world = self.world();
+        let area = world.get::<&Pos>(entity).unwrap().get_area();
+        world
+            .query::<(&Pos, &Door)>()
+            .iter()
+            .any(|(pos, door)| pos.is_in(area) && world.get::<&BlockType>(door.door_pair).is_ok())
+    }
+}
+
+fn collect_frames(game: &mut Game) -> Vec<Frame> {
+    let mut frames = Vec::new();
+    while let GameResult::Frame(frame_getter) = game.next_result() {
+        frames.push(frame_getter.get());
+    }
+    frames
+}
+
+mod tests {
+    use super::Scenario;
+    use crate::core::area::{self, FuelAmount, ShipStatus, ShipUpgrade};
+    use crate::core::behavior::{BehaviorProfile, Hostile, Order, Waiting};
+    use crate::core::combat::HitType;
+    use crate::core::effect::{self, StatusEffectKind};
+    use crate::core::inventory::{self, Held};
+    use crate::core::item::ItemTypeId;
+    use crate::core::name::Name;
+    use crate::core::position::{OccupiesSpace, Pos};
+    use crate::core::status::{self, Health};
+    use crate::core::{CrewMember, Tag};
+    use crate::view::Frame;
+    use crate::view::event::GameEvent;
+    use hecs::Entity;
+
+    #[test]
+    fn take_item() {
+        let mut scenario = Scenario::load("test/misc");
+        let controlled = scenario.controlled();
+        assert!(!scenario.is_holding(controlled, "crowbar"));
+
+        scenario.run(&["take crowbar"]);
+        assert!(scenario.is_holding(controlled, "crowbar"));
+    }
+
+    #[test]
+    fn force_door_needs_tool() {
+        let mut scenario = Scenario::load("test/misc");
+        let controlled = scenario.controlled();
+        scenario.run(&["force left door"]);
+        assert!(scenario.has_blocked_door_in_area(controlled));
+        scenario.assert_message("needs some sort of tool to force the door open");
+
+        scenario.run(&["take crowbar", "force left door"]);
+        assert!(!scenario.has_blocked_door_in_area(controlled));
+
+        scenario.run(&["enter left door"]);
+        assert_eq!(scenario.area_label(controlled), "Side Room");
+    }
+
+    #[test]
+    fn actions_emit_events() {
+        let mut scenario = Scenario::load("test/misc");
+        let controlled = scenario.controlled();
+        scenario.run(&["take crowbar"]);
+        assert!(scenario.events().iter().any(|event| matches!(
+            event,
+            GameEvent::ItemTaken { character, item_type, .. }
+                if *character == controlled && item_type.to_string() == "crowbar"
+        )));
+
+        scenario.run(&["force left door"]);
+        assert!(scenario.events().iter().any(|event| matches!(
+            event,
+            GameEvent::DoorForced { character, .. } if *character == controlled
+        )));
+
+        scenario.run(&["enter left door"]);
+        assert!(scenario.events().iter().any(|event| matches!(
+            event,
+            GameEvent::DoorEntered { character, .. } if *character == controlled
+        )));
+    }
+
+    #[test]
+    fn locked_door_opens_with_key() {
+        let mut scenario = Scenario::load("test/locked_door");
+        let controlled = scenario.controlled();
+        scenario.run(&["enter door"]);
+        scenario.assert_message("as it is locked");
+        assert!(scenario.has_blocked_door_in_area(controlled));
+
+        scenario.run(&[
+            "enter doorway",
+            "take keycard",
+            "enter doorway",
+            "enter door",
+        ]);
+        scenario.assert_message("unlocked the door");
+        assert!(scenario.events().iter().any(|event| matches!(
+            event,
+            GameEvent::DoorUnlocked { character, .. } if *character == controlled
+        )));
+        assert_eq!(scenario.area_label(controlled), "Vault");
+    }
+
+    #[test]
+    fn location_must_place_a_reachable_key_for_its_locked_doors() {
+        let mut scenario = Scenario::load("test/misc");
+        let controlled = scenario.controlled();
+        scenario.give_item(controlled, "keycard");
+
+        let error = scenario
+            .try_land_at("test/locked_door_without_key")
+            .unwrap_err();
+        assert!(error.contains("No key was placed"), "{error}");
+
+        let error = scenario
+            .try_land_at("test/key_behind_locked_door")
+            .unwrap_err();
+        assert!(
+            error.contains("through the door that it unlocks"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn gas_mask_protects_from_toxic_gas() {
+        let health_of = |scenario: &Scenario, entity| {
+            scenario
+                .world()
+                .get::<&Health>(entity)
+                .unwrap()
+                .as_fraction()
+        };
+
+        let mut scenario = Scenario::load("test/hazards");
+        let controlled = scenario.controlled();
+        let initial_health = health_of(&scenario, controlled);
+        scenario.run(&["enter door"]);
+        scenario.assert_message("choked on the toxic gas");
+        assert!(health_of(&scenario, controlled) < initial_health);
+
+        let mut scenario = Scenario::load("test/hazards");
+        let controlled = scenario.controlled();
+        let initial_health = health_of(&scenario, controlled);
+        scenario.run(&["take gas mask", "enter door", "wait"]);
+        assert!(
+            scenario
+                .messages()
+                .iter()
+                .all(|message| !message.contains("toxic gas"))
+        );
+        assert_eq!(health_of(&scenario, controlled), initial_health);
+    }
+
+    #[test]
+    fn creature_killed_by_hazard_is_cleared_out() {
+        let mut scenario = Scenario::load("test/hazard_death");
+        scenario.run(&["wait"]);
+        let goblin = scenario
+            .world()
+            .query::<(Entity, &Tag)>()
+            .iter()
+            .find(|(_, tag)| tag.0 == "victim")
+            .map(|(entity, _)| entity)
+            .unwrap();
+        assert!(!status::is_alive(goblin, scenario.world()));
+        assert!(!scenario.world().satisfies::<&Hostile>(goblin));
+        assert!(!scenario.world().satisfies::<&OccupiesSpace>(goblin));
+        assert!(
+            scenario
+                .events()
+                .contains(&GameEvent::Died { entity: goblin })
+        );
+        assert!(
+            scenario
+                .messages()
+                .iter()
+                .all(|message| !message.contains("fire"))
+        );
+    }
+
+    #[test]
+    fn light_source_lets_crew_see_in_darkness() {
+        let mut scenario = Scenario::load("test/darkness");
+        scenario.run(&["enter door"]);
+        assert!(
+            scenario
+                .visible_names()
+                .contains(&"Unknown item".to_owned())
+        );
+        let response = scenario.try_run("check knife").unwrap_err();
+        assert!(response[0].contains("too dark"));
+
+        scenario.run(&["enter door", "take flashlight", "enter door"]);
+        assert!(scenario.visible_names().contains(&"Knife".to_owned()));
+        let response = scenario.try_run("check knife").unwrap_err();
+        assert_eq!(response[0], "Knife:");
+    }
+
+    #[test]
+    fn ranged_weapon_fires_from_a_distance_using_ammo() {
+        let mut scenario = Scenario::load("test/ranged");
+        let controlled = scenario.controlled();
+        scenario.run(&[
+            "take blaster",
+            "take energy cell",
+            "take energy cell",
+            "wield blaster",
+            "enter door",
+        ]);
+        let coord_before_attack = scenario
+            .world()
+            .get::<&Pos>(controlled)
+            .unwrap()
+            .get_coord();
+
+        scenario.run(&["attack goblin"]);
+        scenario.assert_message("fires their blaster at the goblin");
+        assert_eq!(
+            scenario
+                .world()
+                .get::<&Pos>(controlled)
+                .unwrap()
+                .get_coord(),
+            coord_before_attack
+        );
+        let energy_cells = scenario
+            .world()
+            .query::<(&ItemTypeId, &Held)>()
+            .iter()
+            .filter(|(item_type, held)| {
+                held.held_by(controlled) && item_type.to_string() == "energy_cell"
+            })
+            .count();
+        assert_eq!(energy_cells, 1);
+    }
+
+    #[test]
+    fn wear_and_take_off_armor() {
+        let mut scenario = Scenario::load("test/armor");
+        let controlled = scenario.controlled();
+        scenario.run(&["wear armor vest"]);
+        scenario.assert_message("picked up and put on the armor vest");
+        assert!(inventory::get_worn(scenario.world(), controlled).is_some());
+        scenario.assert_message("Wearing Armor vest");
+        let response = scenario.try_run("wield armor vest").unwrap_err();
+        assert!(response[0].contains("no armor vest"));
+
+        scenario.run(&["take off armor vest"]);
+        assert!(inventory::get_worn(scenario.world(), controlled).is_none());
+        assert!(scenario.is_holding(controlled, "armor_vest"));
+        scenario.assert_message("Wearing Nothing");
+    }
+
+    #[test]
+    fn worn_armor_is_kept_when_launching() {
+        let mut scenario = Scenario::load("test/launch");
+        let controlled = scenario.controlled();
+        scenario.run(&[
+            "wear armor vest",
+            "take fuel can",
+            "take fuel can",
+            "go to ship",
+            "launch ship",
+        ]);
+        assert!(
+            scenario
+                .world()
+                .query::<&ItemTypeId>()
+                .iter()
+                .all(|item_type| item_type.to_string() != "crowbar")
+        );
+        let armor = inventory::get_worn(scenario.world(), controlled).unwrap();
+        assert_eq!(
+            scenario
+                .world()
+                .get::<&ItemTypeId>(armor)
+                .unwrap()
+                .to_string(),
+            "armor_vest"
+        );
+    }
+
+    #[test]
+    fn stimulant_applies_adrenaline() {
+        let mut scenario = Scenario::load("test/effects");
+        let controlled = scenario.controlled();
+        scenario.run(&["take stimulant", "use stimulant"]);
+        scenario.assert_message("feels a rush of adrenaline");
+        assert!(!scenario.is_holding(controlled, "stimulant"));
+        assert!(effect::has_effect(
+            scenario.world().entity(controlled).unwrap(),
+            StatusEffectKind::Adrenaline
+        ));
+
+        scenario.run(&["status"]);
+        scenario.assert_message("Status effects: Adrenaline");
+    }
+
+    #[test]
+    fn creatures_follow_their_behavior_profiles() {
+        let mut scenario = Scenario::load("test/behavior");
+        let controlled = scenario.controlled();
+        scenario.run(&["wait"]);
+        assert!(
+            !scenario
+                .world()
+                .get::<&Health>(controlled)
+                .unwrap()
+                .is_hurt()
+        );
+        let goblin = scenario
+            .world()
+            .query::<(Entity, &Tag)>()
+            .iter()
+            .find(|(_, tag)| tag.0 == "scavenger")
+            .map(|(entity, _)| entity)
+            .unwrap();
+        assert!(scenario.is_holding(goblin, "crowbar"));
+    }
+
+    #[test]
+    fn territorial_creature_attacks_only_inside_its_area() {
+        let mut scenario = Scenario::load("test/territorial");
+        scenario.set_behavior_profile(
+            "goblin",
+            BehaviorProfile::Territorial {
+                area_tag: Tag("den".to_owned()),
+            },
+        );
+        let goblin = scenario
+            .world()
+            .query::<(Entity, &Tag)>()
+            .iter()
+            .find(|(_, tag)| tag.0 == "territorial")
+            .map(|(entity, _)| entity)
+            .unwrap();
+        let is_attacked_by_goblin = |scenario: &Scenario| {
+            scenario.events().iter().any(
+                |event| matches!(event, GameEvent::Attack { attacker, .. } if *attacker == goblin),
+            )
+        };
+
+        scenario.run(&["wait"]);
+        assert!(!is_attacked_by_goblin(&scenario));
+        assert_eq!(scenario.area_label(goblin), "Den");
+
+        scenario.run(&["enter path"]);
+        assert!(is_attacked_by_goblin(&scenario));
+    }
+
+    #[test]
+    fn combat_log_records_attack_rolls() {
+        let mut scenario = Scenario::load("test/ranged");
+        let response = scenario.try_run("combat log").unwrap_err();
+        assert!(response[0].contains("not enabled"));
+
+        scenario.game.enable_combat_log();
+        scenario.run(&[
+            "take blaster",
+            "take energy cell",
+            "wield blaster",
+            "enter door",
+        ]);
+        scenario.run(&["attack goblin"]);
+        let combat_log = scenario.game.combat_log().unwrap();
+        let record = combat_log
+            .records()
+            .iter()
+            .find(|record| record.target == "the goblin")
+            .unwrap();
+        assert!((1..=20).contains(&record.hit_roll.roll));
+        assert_eq!(record.damage.is_some(), record.hit != HitType::Dodge);
+
+        let mut csv = Vec::new();
+        combat_log.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(csv.lines().count(), combat_log.records().len() + 1);
+        assert!(csv.contains(",the goblin,"));
+
+        let response = scenario.try_run("combat log").unwrap_err();
+        assert!(response[0].starts_with("Combat log (last"));
+        assert!(response.iter().any(|line| line.contains("-> the goblin (")));
+        assert!(response.iter().any(|line| line.starts_with("  Hit roll")));
+    }
+
+    #[test]
+    fn map_shows_only_explored_areas() {
+        let mut scenario = Scenario::load("test/darkness");
+        scenario.run(&["map"]);
+        assert!(matches!(scenario.frames()[0], Frame::Map(_)));
+        scenario.assert_message("Room (ship)");
+        assert!(
+            !scenario
+                .messages()
+                .iter()
+                .any(|message| message.contains("Dark Room"))
+        );
+
+        scenario.run(&["enter door", "map"]);
+        scenario.assert_message("leads to Dark Room");
+        scenario.assert_message("Dark Room - ");
+    }
+
+    #[test]
+    fn store_and_retrieve_item_in_ship_cargo() {
+        let mut scenario = Scenario::load("test/misc");
+        let controlled = scenario.controlled();
+        scenario.run(&["take crowbar"]);
+        let response = scenario.try_run("store crowbar in ship").unwrap_err();
+        assert!(response[0].contains("must be in the ship"));
+
+        scenario.run(&["go to ship", "store crowbar in ship"]);
+        assert!(!scenario.is_holding(controlled, "crowbar"));
+        assert_eq!(area::stored_items(scenario.world()).len(), 1);
+        let response = scenario.try_run("cargo").unwrap_err();
+        assert_eq!(response[0], "Cargo hold (1/12):");
+        assert!(response[1].contains("crowbar"));
+
+        scenario.run(&["retrieve crowbar"]);
+        assert!(scenario.is_holding(controlled, "crowbar"));
+        assert!(area::stored_items(scenario.world()).is_empty());
+    }
+
+    #[test]
+    fn install_ship_upgrade_with_crew_points() {
+        let mut scenario = Scenario::load("test/misc");
+        scenario.run(&["go to ship", "install scanner"]);
+        assert!(area::has_ship_upgrade(
+            scenario.world(),
+            ShipUpgrade::Scanner
+        ));
+        scenario.assert_message("installed the scanner");
+
+        let response = scenario.try_run("install scanner").unwrap_err();
+        assert!(response[0].contains("already has the scanner"));
+        let response = scenario.try_run("install fuel recycler").unwrap_err();
+        assert!(response[0].contains("cannot afford"));
+    }
+
+    #[test]
+    fn refuel_ship_with_fuel_from_location() {
+        let mut scenario = Scenario::load("test/misc");
+        let controlled = scenario.controlled();
+        scenario.run(&[
+            "take crowbar",
+            "force left door",
+            "enter left door",
+            "take fuel can",
+            "take fuel can",
+            "go to ship",
+        ]);
+        assert_eq!(scenario.area_label(controlled), "Ship");
+        assert_eq!(
+            scenario.ship_status(),
+            ShipStatus::NeedFuel(FuelAmount::TwoCans)
+        );
+
+        scenario.run(&["refuel ship"]);
+        assert_eq!(scenario.ship_status(), ShipStatus::Refueled);
+        assert!(!scenario.is_holding(controlled, "fuel_can"));
+    }
+
+    #[test]
+    fn crew_member_follows_orders_until_done() {
+        let mut scenario = Scenario::load("test/orders");
+        let controlled = scenario.controlled();
+        let (crew_member, name) = scenario
+            .world()
+            .query::<(Entity, &Name)>()
+            .with::<&CrewMember>()
+            .iter()
+            .find(|&(entity, _)| entity != controlled)
+            .map(|(entity, name)| (entity, name.name.clone()))
+            .unwrap();
+        let goblin = scenario
+            .world()
+            .query::<Entity>()
+            .with::<&Hostile>()
+            .iter()
+            .next()
+            .unwrap();
+
+        let response = scenario
+            .try_run(&format!("tell {name} to guard {name}"))
+            .unwrap_err();
+        assert!(response[0].contains("guard themselves"));
+
+        scenario.run(&[format!("tell {name} to attack goblin").as_str()]);
+        for _ in 0..30 {
+            if !status::is_alive(goblin, scenario.world()) {
+                break;
+            }
+            scenario.run(&["wait"]);
+        }
+        assert!(!status::is_alive(goblin, scenario.world()));
+        scenario.run(&["wait"]);
+        assert!(!scenario.world().satisfies::<&Order>(crew_member));
+
+        scenario.run(&[format!("tell {name} to retreat to ship").as_str()]);
+        for _ in 0..5 {
+            scenario.run(&["wait"]);
+        }
+        let crew_pos = *scenario.world().get::<&Pos>(crew_member).unwrap();
+        assert!(area::is_in_ship(crew_pos, scenario.world()));
+        assert!(
+            scenario
+                .world()
+                .get::<&Waiting>(crew_member)
+                .is_ok_and(|waiting| waiting.at_ship)
+        );
+    }
+}
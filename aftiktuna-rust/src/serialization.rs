@@ -1,29 +1,51 @@
 use crate::asset::{self, GameAssets};
-use crate::core::SpeciesId;
 use crate::core::display::CreatureVariantSet;
+use crate::core::name::Name;
+use crate::core::store::Points;
+use crate::core::{CrewMember, SpeciesId};
 use crate::game_interface::{Game, SerializedState};
 use crate::game_loop::GameState;
 use hecs::World;
 use rmp_serde::{decode, encode};
+use serde::de::IgnoredAny;
 use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
 use std::collections::HashSet;
 use std::fmt::{Display, Formatter};
-use std::fs::File;
+use std::fs::{self, File};
 use std::io;
-use std::io::{Read, Write};
-
-pub const SAVE_FILE_NAME: &str = "SAVE_FILE";
+use std::io::{BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub mod migration;
+
+pub const SAVE_DIRECTORY: &str = "saves";
+pub const AUTOSAVE_SLOT: &str = "autosave";
+/// The single save file that was used before save slots were added.
+const LEGACY_SAVE_FILE: &str = "SAVE_FILE";
+const LEGACY_SAVE_SLOT: &str = "old_save";
+const SAVE_FILE_EXTENSION: &str = "save";
 const MAJOR_VERSION: u16 = 6;
-const MINOR_VERSION: u16 = 0;
+const MINOR_VERSION: u16 = 15;
+/// The first version where the save file has a metadata header.
+const METADATA_VERSION: (u16, u16) = (6, 2);
 
 fn verify_version(major: u16, minor: u16) -> Result<(), LoadError> {
-    if major != MAJOR_VERSION || minor > MINOR_VERSION {
-        Err(LoadError::UnsupportedVersion(major, minor))
-    } else {
+    let is_current = major == MAJOR_VERSION && minor <= MINOR_VERSION;
+    let can_migrate =
+        major < MAJOR_VERSION && major >= migration::oldest_supported_major(migration::MIGRATIONS);
+    if is_current || can_migrate {
         Ok(())
+    } else {
+        Err(LoadError::UnsupportedVersion(major, minor))
     }
 }
 
+fn has_metadata(major: u16, minor: u16) -> bool {
+    (major, minor) >= METADATA_VERSION
+}
+
 macro_rules! world_serialization {
     ($($comp:ty, $id:ident);* $(;)?) => {
         use hecs::serialize::column;
@@ -150,7 +172,8 @@ macro_rules! world_serialization {
 
 pub mod world {
     use crate::core::{
-        self, area, behavior, display, inventory, item, name, position, status, store,
+        self, area, behavior, display, effect, hazard, inventory, item, name, position, status,
+        store,
     };
     use std::error::Error;
 
@@ -159,6 +182,8 @@ pub mod world {
         area::ShipState, ShipState;
         area::ShipRoom, ShipRoom;
         area::ShipControls, ShipControls;
+        area::Explored, Explored;
+        area::StoredInShip, StoredInShip;
         position::Pos, Pos;
         position::Direction, Direction;
         position::Large, Large;
@@ -182,6 +207,7 @@ pub mod world {
         status::SeenWithLowHealth, LowHealth;
         status::SeenWithLowStamina, LowStamina;
         status::IsStunned, IsStunned;
+        effect::StatusEffects, StatusEffects;
 
         core::CrewMember, CrewMember;
         behavior::Character, Character;
@@ -192,6 +218,7 @@ pub mod world {
         behavior::BackgroundId, BackgroundId;
         behavior::RepeatingAction, RepeatingAction;
         behavior::Waiting, Waiting;
+        behavior::Order, Order;
         behavior::CrewLossMemory, CrewLossMemory;
         behavior::Decision, Decision;
 
@@ -213,6 +240,8 @@ pub mod world {
         core::Door, Door;
         core::IsCut, IsCut;
         core::BlockType, BlockType;
+        core::LockKey, LockKey;
+        hazard::AreaHazards, AreaHazards;
 
         inventory::Held, Held;
         inventory::Container, Container;
@@ -264,16 +293,19 @@ macro_rules! from {
 pub enum SaveError {
     IO(io::Error),
     Encode(encode::Error),
+    Json(serde_json::Error),
 }
 
 from!(io::Error => SaveError, SaveError::IO);
 from!(encode::Error => SaveError, SaveError::Encode);
+from!(serde_json::Error => SaveError, SaveError::Json);
 
 impl Display for SaveError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             SaveError::IO(error) => Display::fmt(error, f),
             SaveError::Encode(error) => Display::fmt(error, f),
+            SaveError::Json(error) => Display::fmt(error, f),
         }
     }
 }
@@ -281,12 +313,16 @@ impl Display for SaveError {
 #[derive(Debug)]
 pub enum LoadError {
     UnsupportedVersion(u16, u16),
+    Migration(migration::MigrationError),
     Decode(decode::Error),
+    Json(serde_json::Error),
     Io(io::Error),
     Asset(asset::Error),
 }
 
+from!(migration::MigrationError => LoadError, LoadError::Migration);
 from!(decode::Error => LoadError, LoadError::Decode);
+from!(serde_json::Error => LoadError, LoadError::Json);
 from!(io::Error => LoadError, LoadError::Io);
 from!(asset::Error => LoadError, LoadError::Asset);
 
@@ -297,24 +333,190 @@ impl Display for LoadError {
                 f,
                 "Unsupported save file format '{major}.{minor}'. Current format version is '{MAJOR_VERSION}.{MINOR_VERSION}'."
             ),
+            LoadError::Migration(error) => Display::fmt(error, f),
             LoadError::Decode(error) => Display::fmt(error, f),
+            LoadError::Json(error) => Display::fmt(error, f),
             LoadError::Io(error) => Display::fmt(error, f),
             LoadError::Asset(error) => Display::fmt(error, f),
         }
     }
 }
 
-pub fn write_game_to_save_file(game: &Game) -> Result<(), SaveError> {
-    serialize_game(&game.serialized_state, File::create(SAVE_FILE_NAME)?)
+/// A short summary of a saved game, stored at the start of the save file
+/// so that save slots can be listed without loading the whole game.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SaveMetadata {
+    pub location: Option<String>,
+    pub crew_names: Vec<String>,
+    pub points: i32,
+    pub locations_before_fortuna: i32,
+    /// Seconds since the unix epoch at the time of saving.
+    pub timestamp: u64,
+}
+
+impl SaveMetadata {
+    fn from_state(state: &GameState) -> Self {
+        let world = &state.world;
+        let crew = world
+            .get::<&CrewMember>(state.controlled)
+            .map(|crew_member| crew_member.0)
+            .ok();
+        let crew_names = world
+            .query::<(&Name, &CrewMember)>()
+            .iter()
+            .filter(|(_, (_, crew_member))| Some(crew_member.0) == crew)
+            .map(|(_, (name, _))| name.name.clone())
+            .collect();
+        let points = crew
+            .and_then(|crew| world.get::<&Points>(crew).ok().map(|points| points.0))
+            .unwrap_or_default();
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        Self {
+            location: state.current_location.clone(),
+            crew_names,
+            points,
+            locations_before_fortuna: state.generation_state.locations_before_fortuna(),
+            timestamp,
+        }
+    }
+
+    pub fn time_since_saved(&self) -> Duration {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH + Duration::from_secs(self.timestamp))
+            .unwrap_or_default()
+    }
+
+    pub fn summary(&self) -> String {
+        let location = self.location.as_deref().unwrap_or("No location yet");
+        let crew = self.crew_names.join(", ");
+        let minutes = self.time_since_saved().as_secs() / 60;
+        let time = match minutes {
+            0 => "just now".to_owned(),
+            1..60 => format!("{minutes} min ago"),
+            60..1440 => format!("{} h ago", minutes / 60),
+            _ => format!("{} days ago", minutes / 1440),
+        };
+        format!(
+            "{location} | Crew: {crew} | {points}p | {remaining} locations before Fortuna | Saved {time}",
+            points = self.points,
+            remaining = self.locations_before_fortuna,
+        )
+    }
+}
+
+pub struct SaveSlot {
+    pub name: String,
+    /// The metadata of the save, if it could be read.
+    pub metadata: Option<SaveMetadata>,
+}
+
+pub fn save_slot_path(slot: &str) -> PathBuf {
+    [SAVE_DIRECTORY, slot]
+        .iter()
+        .collect::<PathBuf>()
+        .with_extension(SAVE_FILE_EXTENSION)
+}
+
+pub fn is_valid_slot_name(slot: &str) -> bool {
+    !slot.is_empty()
+        && slot
+            .chars()
+            .all(|char| char.is_ascii_alphanumeric() || char == '_' || char == '-')
+}
+
+/// Lists the existing save slots, with the most recently saved slot first.
+/// Only the metadata header of each save file is read.
+pub fn list_save_slots() -> io::Result<Vec<SaveSlot>> {
+    import_legacy_save_file()?;
+    let entries = match fs::read_dir(SAVE_DIRECTORY) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(error),
+    };
+    let mut slots = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path
+            .extension()
+            .is_none_or(|extension| extension != SAVE_FILE_EXTENSION)
+        {
+            continue;
+        }
+        let Some(name) = path.file_stem().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        let metadata = File::open(&path)
+            .ok()
+            .and_then(|file| read_metadata(BufReader::new(file)).ok().flatten());
+        slots.push(SaveSlot {
+            name: name.to_owned(),
+            metadata,
+        });
+    }
+    slots.sort_by_key(|slot| Reverse(slot.metadata.as_ref().map(|metadata| metadata.timestamp)));
+    Ok(slots)
+}
+
+/// Moves a save file from before save slots into its own slot,
+/// so that it is listed and loaded like any other save.
+fn import_legacy_save_file() -> io::Result<()> {
+    let legacy_path = Path::new(LEGACY_SAVE_FILE);
+    if !legacy_path.is_file() {
+        return Ok(());
+    }
+    fs::create_dir_all(SAVE_DIRECTORY)?;
+    let slot = if save_slot_path(LEGACY_SAVE_SLOT).exists() {
+        new_slot_name()
+    } else {
+        LEGACY_SAVE_SLOT.to_owned()
+    };
+    fs::rename(legacy_path, save_slot_path(&slot))
+}
+
+/// Picks a name for a new save slot that is not used by an existing save.
+pub fn new_slot_name() -> String {
+    (1..)
+        .map(|number| format!("save_{number}"))
+        .find(|slot| !save_slot_path(slot).exists())
+        .unwrap()
+}
+
+pub fn delete_save_slot(slot: &str) -> io::Result<()> {
+    fs::remove_file(save_slot_path(slot))
+}
+
+/// Deletes the saves of a run that has ended, which are its own slot and the autosave.
+pub fn delete_saves_of_finished_run(slot: &str) {
+    let _ = delete_save_slot(slot);
+    let _ = delete_save_slot(AUTOSAVE_SLOT);
+}
+
+pub fn write_game_to_save_slot(game: &Game, slot: &str) -> Result<(), SaveError> {
+    fs::create_dir_all(SAVE_DIRECTORY)?;
+    serialize_game(&game.serialized_state, File::create(save_slot_path(slot))?)
 }
 
 fn serialize_game(state: &SerializedState, writer: impl Write) -> Result<(), SaveError> {
     let mut serializer = rmp_serde::Serializer::new(writer).with_struct_map();
     (MAJOR_VERSION, MINOR_VERSION).serialize(&mut serializer)?;
+    SaveMetadata::from_state(&state.state).serialize(&mut serializer)?;
     state.serialize(&mut serializer)?;
     Ok(())
 }
 
+fn read_metadata(reader: impl Read) -> Result<Option<SaveMetadata>, LoadError> {
+    let mut deserializer = rmp_serde::Deserializer::new(reader);
+    let (major, minor) = <(u16, u16)>::deserialize(&mut deserializer)?;
+    verify_version(major, minor)?;
+    if !has_metadata(major, minor) {
+        return Ok(None);
+    }
+    Ok(Some(SaveMetadata::deserialize(&mut deserializer)?))
+}
+
 pub(crate) fn load_game(
     reader: impl Read,
     assets: &GameAssets,
@@ -322,7 +524,64 @@ pub(crate) fn load_game(
     let mut deserializer = rmp_serde::Deserializer::new(reader);
     let (major, minor) = <(u16, u16)>::deserialize(&mut deserializer)?;
     verify_version(major, minor)?;
-    let mut state = SerializedState::deserialize(&mut deserializer)?;
+    if has_metadata(major, minor) {
+        IgnoredAny::deserialize(&mut deserializer)?;
+    }
+    let mut state = if major == MAJOR_VERSION {
+        SerializedState::deserialize(&mut deserializer)?
+    } else {
+        let mut save = rmpv::Value::deserialize(&mut deserializer)?;
+        migration::migrate(&mut save, major, migration::MIGRATIONS)?;
+        migration::decode_migrated(&save)?
+    };
+    inject_update_data(&mut state.state, assets);
+    Ok(state)
+}
+
+/// Serializes the game into an in-memory snapshot, which can later be restored to undo turns.
+pub(crate) fn write_snapshot(state: &SerializedState) -> Result<Vec<u8>, encode::Error> {
+    rmp_serde::to_vec_named(state)
+}
+
+pub(crate) fn read_snapshot(snapshot: &[u8]) -> Result<SerializedState, decode::Error> {
+    rmp_serde::from_slice(snapshot)
+}
+
+#[derive(Serialize)]
+struct JsonSaveRef<'a> {
+    version: (u16, u16),
+    game: &'a SerializedState,
+}
+
+#[derive(Deserialize)]
+struct JsonSave {
+    version: (u16, u16),
+    game: SerializedState,
+}
+
+/// Writes the game as pretty-printed json, for inspecting, editing or comparing saves.
+pub fn write_game_as_json(game: &Game, writer: impl Write) -> Result<(), SaveError> {
+    let save = JsonSaveRef {
+        version: (MAJOR_VERSION, MINOR_VERSION),
+        game: &game.serialized_state,
+    };
+    serde_json::to_writer_pretty(writer, &save)?;
+    Ok(())
+}
+
+/// Reads a game written by [write_game_as_json].
+/// Unlike regular save files, json saves from older major versions are not migrated.
+pub(crate) fn load_game_from_json(
+    reader: impl Read,
+    assets: &GameAssets,
+) -> Result<SerializedState, LoadError> {
+    let JsonSave {
+        version: (major, minor),
+        game: mut state,
+    } = serde_json::from_reader(reader)?;
+    if major != MAJOR_VERSION || minor > MINOR_VERSION {
+        return Err(LoadError::UnsupportedVersion(major, minor));
+    }
     inject_update_data(&mut state.state, assets);
     Ok(state)
 }
@@ -379,3 +638,24 @@ pub fn check_world_components(world: &World) {
         println!("Has non-serialized components: {non_serialized_components:?}");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::asset::GameAssets;
+    use crate::game_interface;
+    use crate::run_config::RunConfig;
+
+    #[test]
+    fn metadata_is_read_without_the_game() {
+        let game = game_interface::setup_new(RunConfig::default(), Some(3)).unwrap();
+        let mut data = Vec::new();
+        super::serialize_game(&game.serialized_state, &mut data).unwrap();
+
+        let metadata = super::read_metadata(data.as_slice()).unwrap().unwrap();
+        assert_eq!(metadata.crew_names.len(), 2);
+        assert_eq!(metadata.locations_before_fortuna, 2);
+
+        let state = super::load_game(data.as_slice(), &GameAssets::load().unwrap()).unwrap();
+        assert_eq!(state.state.seed, Some(3));
+    }
+}
@@ -0,0 +1,306 @@
+//! Upgrading of save files from older major format versions.
+//!
+//! A save from an older major version is read as a generic MessagePack value,
+//! and is then passed through one migration per major version until it matches the current format.
+//! The functions in this module are helpers for writing such migrations.
+
+use super::{LoadError, MAJOR_VERSION};
+use crate::game_interface::SerializedState;
+use rmp_serde::decode;
+use rmpv::Value;
+use std::fmt::{Display, Formatter};
+
+/// Upgrades save data from one major version to the next.
+pub struct Migration {
+    /// The major version that this migration upgrades from.
+    pub from_major: u16,
+    pub description: &'static str,
+    pub apply: fn(&mut Value) -> Result<(), String>,
+}
+
+/// All available migrations.
+/// When the major version is bumped, a migration from the previous major version should be added here.
+pub(super) const MIGRATIONS: &[Migration] = &[];
+
+#[derive(Debug)]
+pub struct MigrationError {
+    pub from_major: u16,
+    pub description: &'static str,
+    pub message: String,
+}
+
+impl Display for MigrationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Failed to upgrade save file from format version {from} to {to} ({description}): {message}",
+            from = self.from_major,
+            to = self.from_major + 1,
+            description = self.description,
+            message = self.message,
+        )
+    }
+}
+
+/// The oldest major version that can be upgraded to the current version through a chain of migrations.
+pub(super) fn oldest_supported_major(migrations: &[Migration]) -> u16 {
+    let mut major = MAJOR_VERSION;
+    while major > 0
+        && migrations
+            .iter()
+            .any(|migration| migration.from_major == major - 1)
+    {
+        major -= 1;
+    }
+    major
+}
+
+/// Applies migrations in order to upgrade the save data from the given major version to the current one.
+pub(super) fn migrate(
+    save: &mut Value,
+    from_major: u16,
+    migrations: &[Migration],
+) -> Result<(), MigrationError> {
+    for major in from_major..MAJOR_VERSION {
+        let migration = migrations
+            .iter()
+            .find(|migration| migration.from_major == major)
+            .ok_or_else(|| MigrationError {
+                from_major: major,
+                description: "missing migration",
+                message: "No migration exists for this version".to_owned(),
+            })?;
+        (migration.apply)(save).map_err(|message| MigrationError {
+            from_major: major,
+            description: migration.description,
+            message,
+        })?;
+    }
+    Ok(())
+}
+
+pub(super) fn decode_migrated(save: &Value) -> Result<SerializedState, LoadError> {
+    let data =
+        rmp_serde::to_vec(save).map_err(|error| decode::Error::Uncategorized(error.to_string()))?;
+    Ok(rmp_serde::from_slice(&data)?)
+}
+
+pub fn field_mut<'a>(value: &'a mut Value, name: &str) -> Result<&'a mut Value, String> {
+    let Value::Map(entries) = value else {
+        return Err(format!("Expected a map with the field \"{name}\""));
+    };
+    entries
+        .iter_mut()
+        .find(|(key, _)| key.as_str() == Some(name))
+        .map(|(_, value)| value)
+        .ok_or_else(|| format!("Missing field \"{name}\""))
+}
+
+pub fn rename_field(value: &mut Value, old_name: &str, new_name: &str) -> Result<(), String> {
+    let Value::Map(entries) = value else {
+        return Err(format!("Expected a map with the field \"{old_name}\""));
+    };
+    let (key, _) = entries
+        .iter_mut()
+        .find(|(key, _)| key.as_str() == Some(old_name))
+        .ok_or_else(|| format!("Missing field \"{old_name}\""))?;
+    *key = Value::from(new_name);
+    Ok(())
+}
+
+/// Inserts the field with the given value, unless the field is already present.
+pub fn insert_field(value: &mut Value, name: &str, field_value: Value) -> Result<(), String> {
+    let Value::Map(entries) = value else {
+        return Err(format!(
+            "Expected a map to insert the field \"{name}\" into"
+        ));
+    };
+    if !entries.iter().any(|(key, _)| key.as_str() == Some(name)) {
+        entries.push((Value::from(name), field_value));
+    }
+    Ok(())
+}
+
+pub fn remove_field(value: &mut Value, name: &str) -> Result<(), String> {
+    let Value::Map(entries) = value else {
+        return Err(format!("Expected a map with the field \"{name}\""));
+    };
+    entries.retain(|(key, _)| key.as_str() != Some(name));
+    Ok(())
+}
+
+pub fn game_state_mut(save: &mut Value) -> Result<&mut Value, String> {
+    field_mut(save, "state")
+}
+
+pub fn generation_state_mut(save: &mut Value) -> Result<&mut Value, String> {
+    field_mut(game_state_mut(save)?, "generation_state")
+}
+
+struct ArchetypeParts<'a> {
+    component_ids: &'a mut Vec<Value>,
+    entity_count: u64,
+    columns: &'a mut Vec<Value>,
+}
+
+fn archetypes_mut(save: &mut Value) -> Result<Vec<ArchetypeParts<'_>>, String> {
+    let Value::Array(archetypes) = field_mut(game_state_mut(save)?, "world")? else {
+        return Err("Expected the world to be a list of archetypes".to_owned());
+    };
+    archetypes
+        .iter_mut()
+        .map(|archetype| {
+            let Value::Array(parts) = archetype else {
+                return Err("Expected an archetype to be a list".to_owned());
+            };
+            let [component_ids, entity_count, _entities, columns] = parts.as_mut_slice() else {
+                return Err("Expected an archetype to have four parts".to_owned());
+            };
+            let (Value::Array(component_ids), Some(entity_count), Value::Array(columns)) =
+                (component_ids, entity_count.as_u64(), columns)
+            else {
+                return Err("Unexpected archetype layout".to_owned());
+            };
+            Ok(ArchetypeParts {
+                component_ids,
+                entity_count,
+                columns,
+            })
+        })
+        .collect()
+}
+
+pub fn rename_component(save: &mut Value, old_id: &str, new_id: &str) -> Result<(), String> {
+    for archetype in archetypes_mut(save)? {
+        for component_id in archetype.component_ids {
+            if component_id.as_str() == Some(old_id) {
+                *component_id = Value::from(new_id);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Adds a component to all entities that have the other given component.
+pub fn add_component_to_entities_with(
+    save: &mut Value,
+    existing_id: &str,
+    new_id: &str,
+    new_component: impl Fn() -> Value,
+) -> Result<(), String> {
+    for archetype in archetypes_mut(save)? {
+        let has_existing = archetype
+            .component_ids
+            .iter()
+            .any(|id| id.as_str() == Some(existing_id));
+        let has_new = archetype
+            .component_ids
+            .iter()
+            .any(|id| id.as_str() == Some(new_id));
+        if has_existing && !has_new {
+            archetype.component_ids.push(Value::from(new_id));
+            archetype.columns.push(Value::Array(
+                (0..archetype.entity_count)
+                    .map(|_| new_component())
+                    .collect(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+pub fn remove_component(save: &mut Value, id: &str) -> Result<(), String> {
+    for archetype in archetypes_mut(save)? {
+        if let Some(index) = archetype
+            .component_ids
+            .iter()
+            .position(|component_id| component_id.as_str() == Some(id))
+        {
+            archetype.component_ids.remove(index);
+            archetype.columns.remove(index);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Migration;
+    use crate::game_interface;
+    use crate::run_config::RunConfig;
+    use rmpv::Value;
+
+    fn current_save_value() -> Value {
+        let game = game_interface::setup_new(RunConfig::default(), Some(5)).unwrap();
+        let data = rmp_serde::encode::to_vec_named(&game.serialized_state).unwrap();
+        rmp_serde::from_slice(&data).unwrap()
+    }
+
+    fn downgrade(save: &mut Value) -> Result<(), String> {
+        super::rename_component(save, "Points", "CrewPoints")?;
+        super::remove_component(save, "Traits")?;
+        super::rename_field(
+            super::generation_state_mut(save)?,
+            "character_names",
+            "names",
+        )
+    }
+
+    fn upgrade(save: &mut Value) -> Result<(), String> {
+        super::rename_component(save, "CrewPoints", "Points")?;
+        super::add_component_to_entities_with(
+            save,
+            "Stats",
+            "Traits",
+            || Value::Array(Vec::new()),
+        )?;
+        super::rename_field(
+            super::generation_state_mut(save)?,
+            "names",
+            "character_names",
+        )
+    }
+
+    #[test]
+    fn migrated_save_can_be_decoded() {
+        let mut save = current_save_value();
+        downgrade(&mut save).unwrap();
+        assert!(super::decode_migrated(&save).is_err());
+
+        let migrations = [Migration {
+            from_major: super::MAJOR_VERSION - 1,
+            description: "test upgrade",
+            apply: upgrade,
+        }];
+        super::migrate(&mut save, super::MAJOR_VERSION - 1, &migrations).unwrap();
+        assert!(super::decode_migrated(&save).is_ok());
+    }
+
+    #[test]
+    fn failed_migration_is_reported() {
+        let mut save = current_save_value();
+        let migrations = [
+            Migration {
+                from_major: super::MAJOR_VERSION - 2,
+                description: "first upgrade",
+                apply: |_| Ok(()),
+            },
+            Migration {
+                from_major: super::MAJOR_VERSION - 1,
+                description: "second upgrade",
+                apply: |save| {
+                    super::rename_component(save, "Unknown", "Other")
+                        .and(Err("test failure".to_owned()))
+                },
+            },
+        ];
+        assert_eq!(
+            super::oldest_supported_major(&migrations),
+            super::MAJOR_VERSION - 2
+        );
+
+        let error = super::migrate(&mut save, super::MAJOR_VERSION - 2, &migrations).unwrap_err();
+        assert_eq!(error.from_major, super::MAJOR_VERSION - 1);
+        assert_eq!(error.description, "second upgrade");
+    }
+}
@@ -0,0 +1,597 @@
+//! Headless simulation of fights and whole runs, for looking into the balance of the game.
+//! Simulations go through the regular game loop, with the controlled character being driven by a simple autopilot
+//! in place of player input, so that the outcomes follow the same combat rules, creature stats and loot as a played run.
+
+use crate::StopType;
+use crate::action::item::SearchAction;
+use crate::action::{Action, AnswerYesNoDecisionAction, ForceDoorAction};
+use crate::ai;
+use crate::asset::GameAssets;
+use crate::asset::location::LocationData;
+use crate::command::Target;
+use crate::core::area::{self, ShipControls, ShipState, ShipStatus};
+use crate::core::behavior::{Decision, Hostile};
+use crate::core::inventory::{self, Container, Held};
+use crate::core::item::{ItemTypeId, Price};
+use crate::core::name::NameData;
+use crate::core::position::Pos;
+use crate::core::status::{self, Stamina};
+use crate::core::store::Points;
+use crate::core::{BlockType, CrewMember, Door, FortunaChest, SpeciesId};
+use crate::game_interface::Phase;
+use crate::game_loop::{self, GameState, Step};
+use crate::location::{self, GenerationState};
+use crate::run_config::RunConfig;
+use hecs::{Entity, World};
+use std::collections::{HashMap, HashSet};
+
+/// The number of times that the autopilot tries to get through a blocked door before giving up on it.
+const DOOR_ATTEMPT_LIMIT: u8 = 3;
+
+pub struct FightConfig {
+    pub crew: Vec<SpeciesId>,
+    /// A weapon given to each crew member before the fight.
+    pub weapon: Option<ItemTypeId>,
+    /// Armor worn by each crew member during the fight.
+    pub armor: Option<ItemTypeId>,
+    pub creatures: Vec<SpeciesId>,
+    pub difficulty: f32,
+    pub turn_limit: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FightResult {
+    CrewWon,
+    CrewLost,
+    TimedOut,
+}
+
+#[derive(Debug, Clone)]
+pub struct FightOutcome {
+    pub result: FightResult,
+    /// The number of turns in which the crew got to pick an action.
+    pub turns: u32,
+    pub crew_deaths: usize,
+}
+
+/// Simulates a fight between the crew and the given creatures, with all creatures aggressive from the start.
+pub fn simulate_fight(
+    config: &FightConfig,
+    seed: u64,
+    assets: &GameAssets,
+) -> Result<FightOutcome, String> {
+    for item_type in config.weapon.iter().chain(&config.armor) {
+        if !assets.item_type_map.contains_key(item_type) {
+            return Err(format!("Unknown item type \"{item_type}\""));
+        }
+    }
+    let run_config = RunConfig {
+        crew_species: Some(config.crew.clone()),
+        difficulty: config.difficulty,
+        ..RunConfig::default()
+    };
+    let generation_state = GenerationState::single("arena".to_owned(), run_config)
+        .map_err(|error| error.to_string())?;
+    let mut state = game_loop::setup(generation_state, seed, assets)?;
+    location::setup_location_data_into_game(arena_data(&config.creatures)?, &mut state, assets)?;
+    area::mark_explored_areas(&mut state.world);
+    equip_crew(config, &mut state.world);
+
+    let arena = state
+        .world
+        .get::<&Pos>(state.controlled)
+        .unwrap()
+        .get_area();
+    let crew_count = count_crew(&state.world);
+    let mut autopilot = Autopilot::default();
+    let mut step = Step::PrepareTick;
+    let mut turns = 0;
+    let result = loop {
+        match run_until_input(step, &mut state, assets)? {
+            Phase::CommandInput => {}
+            Phase::Stopped(StopType::Lose) => break FightResult::CrewLost,
+            phase => return Err(format!("Unexpected phase during a fight: {phase:?}")),
+        }
+        if !is_any_hostile_alive_in(arena, &state.world) {
+            break FightResult::CrewWon;
+        }
+        if turns >= config.turn_limit {
+            break FightResult::TimedOut;
+        }
+        turns += 1;
+        let action = autopilot
+            .pick_action(&mut state, assets)
+            .unwrap_or((Action::Wait, Target::Controlled));
+        step = Step::Tick(Some(action));
+    };
+
+    Ok(FightOutcome {
+        result,
+        turns,
+        crew_deaths: crew_count - count_crew(&state.world),
+    })
+}
+
+fn arena_data(creatures: &[SpeciesId]) -> Result<LocationData, String> {
+    if creatures.is_empty() {
+        return Err("There must be at least one creature to fight".to_owned());
+    }
+    let mut objects = vec![String::from("v"), String::new(), String::new()];
+    let mut symbols = serde_json::Map::new();
+    for (index, creature) in creatures.iter().enumerate() {
+        // Uppercase symbols keep clear of the lowercase location entry symbol.
+        let symbol = char::from_digit(index as u32, 36)
+            .ok_or_else(|| "Too many creatures for the arena".to_owned())?
+            .to_ascii_uppercase();
+        objects.push(symbol.to_string());
+        symbols.insert(
+            symbol.to_string(),
+            serde_json::json!({ "type": "creature", "creature": creature, "aggressive": true }),
+        );
+    }
+    serde_json::from_value(serde_json::json!({
+        "areas": [{ "name": "Arena", "objects": objects, "symbols": symbols }],
+        "door_pairs": {},
+    }))
+    .map_err(|error| format!("Failed to set up the arena: {error}"))
+}
+
+fn equip_crew(config: &FightConfig, world: &mut World) {
+    let crew = world
+        .query::<Entity>()
+        .with::<&CrewMember>()
+        .iter()
+        .collect::<Vec<_>>();
+    for crew_member in crew {
+        if let Some(weapon) = &config.weapon {
+            inventory::unwield_if_needed(world, crew_member);
+            weapon.spawn(world, Held::in_hand(crew_member));
+        }
+        if let Some(armor) = &config.armor {
+            inventory::take_off_armor_if_needed(world, crew_member);
+            armor.spawn(world, Held::worn(crew_member));
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunResult {
+    Won,
+    Lost,
+    /// The autopilot found no way forward, or ran out of turns at a location.
+    Stranded,
+}
+
+#[derive(Debug, Clone)]
+pub struct LocationOutcome {
+    pub location: String,
+    /// The number of turns in which the crew got to pick an action.
+    pub turns: u32,
+    pub crew_at_arrival: usize,
+    /// Crew members that died or were left behind at the location.
+    pub crew_lost: usize,
+    pub points_earned: i32,
+    /// The change in the sell value of the items that the crew carries or has stored in the ship.
+    pub loot_value: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct RunOutcome {
+    pub result: RunResult,
+    pub locations: Vec<LocationOutcome>,
+}
+
+/// Simulates a whole run, where the autopilot always picks the first alternative of a location choice.
+pub fn simulate_run(
+    run_config: RunConfig,
+    seed: u64,
+    turn_limit_per_location: u32,
+    assets: &GameAssets,
+) -> Result<RunOutcome, String> {
+    let generation_state =
+        GenerationState::load_new(run_config).map_err(|error| error.to_string())?;
+    let mut state = game_loop::setup(generation_state, seed, assets)?;
+
+    let mut autopilot = Autopilot::default();
+    let mut locations = Vec::new();
+    let mut current: Option<LocationTracker> = None;
+    let mut step = Step::PrepareNextLocation;
+    let result = loop {
+        let phase = run_until_input(step, &mut state, assets)?;
+
+        if let Some(location) = &state.current_location
+            && current
+                .as_ref()
+                .is_none_or(|tracker| tracker.location != *location)
+        {
+            let tracker = LocationTracker::start(location.clone(), &state, assets);
+            if let Some(finished) = current.replace(tracker) {
+                locations.push(finished.finish(&state, assets));
+            }
+        }
+
+        step = match phase {
+            Phase::ChooseLocation(choice) => {
+                let alternative = choice.alternatives().into_iter().next().unwrap_or_default();
+                let location = state.generation_state.try_make_choice(
+                    &choice,
+                    &alternative,
+                    &mut state.rng,
+                )?;
+                Step::LoadLocation(location)
+            }
+            Phase::CommandInput => {
+                let Some(tracker) = &mut current else {
+                    return Err("Expected to be at a location when taking input".to_owned());
+                };
+                if tracker.turns >= turn_limit_per_location {
+                    break RunResult::Stranded;
+                }
+                tracker.turns += 1;
+                let Some(action) = autopilot.pick_action(&mut state, assets) else {
+                    break RunResult::Stranded;
+                };
+                Step::Tick(Some(action))
+            }
+            Phase::Stopped(StopType::Win) => break RunResult::Won,
+            Phase::Stopped(StopType::Lose) => break RunResult::Lost,
+            phase => return Err(format!("Unexpected phase during a run: {phase:?}")),
+        };
+    };
+    if let Some(tracker) = current {
+        locations.push(tracker.finish(&state, assets));
+    }
+
+    Ok(RunOutcome { result, locations })
+}
+
+struct LocationTracker {
+    location: String,
+    turns: u32,
+    crew_at_arrival: usize,
+    points_at_arrival: i32,
+    item_value_at_arrival: i32,
+}
+
+impl LocationTracker {
+    fn start(location: String, state: &GameState, assets: &GameAssets) -> Self {
+        Self {
+            location,
+            turns: 0,
+            crew_at_arrival: count_crew(&state.world),
+            points_at_arrival: crew_points(&state.world),
+            item_value_at_arrival: crew_item_value(&state.world, assets),
+        }
+    }
+
+    fn finish(self, state: &GameState, assets: &GameAssets) -> LocationOutcome {
+        LocationOutcome {
+            location: self.location,
+            turns: self.turns,
+            crew_at_arrival: self.crew_at_arrival,
+            crew_lost: self
+                .crew_at_arrival
+                .saturating_sub(count_crew(&state.world)),
+            points_earned: crew_points(&state.world) - self.points_at_arrival,
+            loot_value: crew_item_value(&state.world, assets) - self.item_value_at_arrival,
+        }
+    }
+}
+
+fn run_until_input(
+    step: Step,
+    state: &mut GameState,
+    assets: &GameAssets,
+) -> Result<Phase, String> {
+    let (phase_result, _) = game_loop::run(step, state, assets);
+    match phase_result.load_error {
+        Some(error) => Err(error),
+        None => Ok(phase_result.next_phase),
+    }
+}
+
+fn count_crew(world: &World) -> usize {
+    world.query::<&Pos>().with::<&CrewMember>().iter().count()
+}
+
+fn crew_points(world: &World) -> i32 {
+    world
+        .query::<&Points>()
+        .iter()
+        .next()
+        .map_or(0, |points| points.0)
+}
+
+fn crew_item_value(world: &World, assets: &GameAssets) -> i32 {
+    let stored_items = area::stored_items(world);
+    world
+        .query::<(Entity, &ItemTypeId, Option<&Held>, Option<&Pos>)>()
+        .iter()
+        .filter(|&(item, _, held, pos)| {
+            held.is_some_and(|held| world.satisfies::<&CrewMember>(held.holder))
+                || pos.is_some_and(|pos| area::is_in_ship(*pos, world))
+                || stored_items.contains(&item)
+        })
+        .filter_map(|(_, item_type, _, _)| assets.item_type_map.get(item_type)?.price.as_ref())
+        .map(Price::sell_price)
+        .sum()
+}
+
+fn is_any_hostile_alive_in(area: Entity, world: &World) -> bool {
+    world
+        .query::<(Entity, &Pos)>()
+        .with::<&Hostile>()
+        .iter()
+        .any(|(entity, pos)| pos.is_in(area) && status::is_alive(entity, world))
+}
+
+/// Picks actions for the controlled character in the place of a player.
+/// It fights any creature in sight, collects loot, explores the whole location,
+/// and then launches the ship if it has found enough fuel.
+#[derive(Default)]
+struct Autopilot {
+    handled_items: HashSet<Entity>,
+    searched_containers: HashSet<Entity>,
+    door_attempts: HashMap<Entity, u8>,
+}
+
+impl Autopilot {
+    fn pick_action(
+        &mut self,
+        state: &mut GameState,
+        assets: &GameAssets,
+    ) -> Option<(Action, Target)> {
+        let world = &state.world;
+        let controlled = state.controlled;
+        if world.satisfies::<&Decision>(controlled) {
+            let action = AnswerYesNoDecisionAction {
+                decision_outcome: false,
+            };
+            return Some((action.into(), Target::Controlled));
+        }
+        let area = world.get::<&Pos>(controlled).ok()?.get_area();
+
+        let foes = world
+            .query::<(Entity, &Pos)>()
+            .with::<&Hostile>()
+            .iter()
+            .filter(|&(foe, pos)| pos.is_in(area) && status::is_alive(foe, world))
+            .map(|(foe, _)| foe)
+            .collect::<Vec<_>>();
+        if !foes.is_empty() {
+            let attack_kind = ai::pick_attack_kind(
+                world.entity(controlled).ok()?,
+                world,
+                &mut state.rng,
+                assets,
+            );
+            return Some((Action::Attack(foes, attack_kind), Target::Controlled));
+        }
+
+        if let Some((chest, _)) = world
+            .query::<(Entity, &Pos)>()
+            .with::<&FortunaChest>()
+            .iter()
+            .find(|(_, pos)| pos.is_in(area))
+        {
+            return Some((Action::OpenChest(chest), Target::Controlled));
+        }
+
+        if !area::is_ship(area, world)
+            && let Some(action) = self.pick_loot_action(area, world, assets)
+        {
+            return Some((action, Target::Controlled));
+        }
+
+        if world
+            .query::<(&Stamina, &Pos)>()
+            .with::<&CrewMember>()
+            .iter()
+            .any(|(stamina, pos)| pos.is_in(area) && stamina.need_rest())
+        {
+            return Some((Action::Rest(true), Target::Controlled));
+        }
+
+        if let Some(path) = self.find_path(area, world, |destination| {
+            !area::is_ship(destination, world) && !world.satisfies::<&area::Explored>(destination)
+        }) {
+            return Some(self.enter_door_action(path, world, controlled));
+        }
+
+        if !can_launch(state) {
+            return None;
+        }
+        let has_ship_controls = |area: Entity| {
+            world
+                .query::<&Pos>()
+                .with::<&ShipControls>()
+                .iter()
+                .any(|pos| pos.is_in(area))
+        };
+        if has_ship_controls(area) {
+            return Some((Action::Launch, Target::Controlled));
+        }
+        let path = self.find_path(area, world, has_ship_controls)?;
+        Some(self.enter_door_action(path, world, controlled))
+    }
+
+    fn pick_loot_action(
+        &mut self,
+        area: Entity,
+        world: &World,
+        assets: &GameAssets,
+    ) -> Option<Action> {
+        if let Some((container, _)) = world
+            .query::<(Entity, &Pos)>()
+            .with::<&Container>()
+            .iter()
+            .find(|&(container, pos)| {
+                pos.is_in(area) && !self.searched_containers.contains(&container)
+            })
+        {
+            self.searched_containers.insert(container);
+            return Some(SearchAction { container }.into());
+        }
+
+        let (item, _) = world
+            .query::<(Entity, &Pos)>()
+            .with::<&ItemTypeId>()
+            .iter()
+            .find(|&(item, pos)| pos.is_in(area) && !self.handled_items.contains(&item))?;
+        self.handled_items.insert(item);
+        Some(Action::TakeItem(item, NameData::find(world, item, assets)))
+    }
+
+    /// Finds the first door on the shortest path to an area that matches the predicate,
+    /// avoiding blocked doors that the crew has already failed to get through.
+    fn find_path(
+        &self,
+        area: Entity,
+        world: &World,
+        predicate: impl Fn(Entity) -> bool,
+    ) -> Option<Entity> {
+        let is_passable = |door: &Door| {
+            !world.satisfies::<&BlockType>(door.door_pair)
+                || self
+                    .door_attempts
+                    .get(&door.door_pair)
+                    .is_none_or(|&attempts| attempts < DOOR_ATTEMPT_LIMIT)
+        };
+        let mut entries = world
+            .query::<(Entity, &Pos, &Door)>()
+            .iter()
+            .filter(|&(_, pos, door)| pos.is_in(area) && is_passable(door))
+            .map(|(path, _, door)| (path, door.destination.get_area()))
+            .collect::<Vec<_>>();
+        let mut checked_areas = HashSet::from([area]);
+
+        while !entries.is_empty() {
+            let mut new_entries = Vec::new();
+            for (path, entry_area) in entries {
+                if checked_areas.insert(entry_area) {
+                    if predicate(entry_area) {
+                        return Some(path);
+                    }
+                    new_entries.extend(
+                        world
+                            .query::<(&Pos, &Door)>()
+                            .iter()
+                            .filter(|&(pos, door)| pos.is_in(entry_area) && is_passable(door))
+                            .map(|(_, door)| (path, door.destination.get_area())),
+                    );
+                }
+            }
+            entries = new_entries;
+        }
+        None
+    }
+
+    fn enter_door_action(
+        &mut self,
+        door: Entity,
+        world: &World,
+        controlled: Entity,
+    ) -> (Action, Target) {
+        let Some(door_pair) = world.get::<&Door>(door).ok().map(|door| door.door_pair) else {
+            return (Action::EnterDoor(door), Target::Crew);
+        };
+        let Ok(block_type) = world
+            .get::<&BlockType>(door_pair)
+            .map(|block_type| *block_type)
+        else {
+            return (Action::EnterDoor(door), Target::Crew);
+        };
+        *self.door_attempts.entry(door_pair).or_default() += 1;
+        if block_type
+            .usable_tools()
+            .into_iter()
+            .any(|tool| inventory::is_holding_tool(world, controlled, tool))
+        {
+            let action = ForceDoorAction {
+                door,
+                assisting: None,
+            };
+            (action.into(), Target::Controlled)
+        } else {
+            (Action::EnterDoor(door), Target::Crew)
+        }
+    }
+}
+
+/// Checks if the ship has enough fuel to launch, either already or with the fuel cans held by the controlled character.
+/// Launching is never possible at the location of the fortuna chest.
+fn can_launch(state: &GameState) -> bool {
+    if state.generation_state.is_at_fortuna() {
+        return false;
+    }
+    let Ok(status) = state
+        .world
+        .get::<&ShipState>(state.ship_core)
+        .map(|ship_state| ship_state.status)
+    else {
+        return false;
+    };
+    match status {
+        ShipStatus::Refueled | ShipStatus::Launching => true,
+        ShipStatus::NeedFuel(_) => {
+            area::fuel_needed_to_launch(&state.world).is_some_and(|needed| {
+                let held_cans = inventory::get_held(&state.world, state.controlled)
+                    .into_iter()
+                    .filter(|&item| {
+                        state
+                            .world
+                            .get::<&ItemTypeId>(item)
+                            .is_ok_and(|item_type| item_type.is_fuel_can())
+                    })
+                    .count();
+                held_cans >= needed
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FightConfig, FightResult};
+    use crate::asset::GameAssets;
+    use crate::core::SpeciesId;
+    use crate::run_config::RunConfig;
+
+    fn fight_config(weapon: &str) -> FightConfig {
+        FightConfig {
+            crew: vec![SpeciesId::from("aftik"); 3],
+            weapon: Some(weapon.into()),
+            armor: Some("armor_vest".into()),
+            creatures: vec![SpeciesId::from("goblin")],
+            difficulty: 1.,
+            turn_limit: 200,
+        }
+    }
+
+    #[test]
+    fn armed_crew_wins_fight_against_goblin() {
+        let assets = GameAssets::load().unwrap();
+        let outcome = super::simulate_fight(&fight_config("sword"), 0, &assets).unwrap();
+        assert_eq!(outcome.result, FightResult::CrewWon);
+        assert!(outcome.turns >= 1);
+
+        assert!(super::simulate_fight(&fight_config("not_an_item"), 0, &assets).is_err());
+    }
+
+    #[test]
+    fn simulated_run_records_visited_locations() {
+        let assets = GameAssets::load().unwrap();
+        let run_config = RunConfig {
+            locations_before_fortuna: 1,
+            ..RunConfig::default()
+        };
+        let outcome = super::simulate_run(run_config, 0, 200, &assets).unwrap();
+        assert!(!outcome.locations.is_empty());
+        assert!(
+            outcome
+                .locations
+                .iter()
+                .all(|location| location.crew_lost <= location.crew_at_arrival)
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use self::area::RenderData;
+use self::event::GameEvent;
 pub use self::status::FullStatus;
 pub(crate) use self::status::get_full_status;
 use self::text::{IntoMessage, Messages};
@@ -18,6 +19,9 @@ use std::mem::take;
 use std::ops::Deref;
 
 pub mod area;
+pub mod combat_log;
+pub mod event;
+pub mod map;
 mod status;
 pub mod text;
 
@@ -116,6 +120,7 @@ mod store {
             messages: buffer
                 .pop_messages(world, character, cache)
                 .into_text(buffer.assets),
+            events: Vec::new(),
         }
     }
 }
@@ -126,6 +131,7 @@ pub type StatusCache = status::Cache;
 
 pub(crate) struct Buffer<'a> {
     pub messages: Messages,
+    events: Vec<GameEvent>,
     captured_frames: Vec<Frame>,
     unseen_view: bool,
     pub assets: &'a GameAssets,
@@ -135,6 +141,7 @@ impl<'a> Buffer<'a> {
     pub fn new(assets: &'a GameAssets) -> Self {
         Self {
             messages: Messages::default(),
+            events: Vec::new(),
             captured_frames: Vec::new(),
             unseen_view: false,
             assets,
@@ -165,7 +172,7 @@ impl<'a> Buffer<'a> {
                 && matches!(self.captured_frames.last(), Some(Frame::Dialogue { .. })))
             || frame.has_messages()
         {
-            self.captured_frames.push(frame);
+            self.push_frame(frame);
         }
         self.unseen_view = false;
     }
@@ -193,6 +200,11 @@ impl<'a> Buffer<'a> {
         self.flush_hint(state);
     }
 
+    /// Records an event, which is attached to the next captured frame.
+    pub fn add_event(&mut self, event: GameEvent) {
+        self.events.push(event);
+    }
+
     pub fn flush_hint(&mut self, state: &mut GameState) {
         if self.messages.len() >= 4 {
             self.capture_view(state, false);
@@ -218,12 +230,18 @@ impl<'a> Buffer<'a> {
         }
     }
 
-    pub fn push_frame(&mut self, frame: Frame) {
+    pub fn push_frame(&mut self, mut frame: Frame) {
+        if let Some(events) = frame.events_mut() {
+            events.append(&mut self.events);
+        }
         self.captured_frames.push(frame);
     }
 
     pub fn push_ending_frame(&mut self, _world: &World, _character: Entity, stop_type: StopType) {
-        self.push_frame(Frame::Ending { stop_type })
+        self.push_frame(Frame::Ending {
+            stop_type,
+            events: Vec::new(),
+        })
     }
 
     pub fn push_dialogue(
@@ -236,10 +254,21 @@ impl<'a> Buffer<'a> {
         self.push_frame(Frame::Dialogue {
             messages: vec![format!("\"{}\"", message.as_ref())],
             data: DialogueFrameData::build(target, expression, world),
+            events: Vec::new(),
         })
     }
 
-    pub fn into_frames(self) -> Vec<Frame> {
+    pub fn into_frames(mut self) -> Vec<Frame> {
+        // Events that happened after the last captured frame still belong to this run,
+        // so they are attached to the latest frame that can hold them.
+        if let Some(events) = self
+            .captured_frames
+            .iter_mut()
+            .rev()
+            .find_map(Frame::events_mut)
+        {
+            events.append(&mut self.events);
+        }
         self.captured_frames
     }
 }
@@ -250,19 +279,28 @@ pub enum Frame {
     AreaView {
         messages: Vec<String>,
         render_data: RenderData,
+        #[serde(default)]
+        events: Vec<GameEvent>,
     },
     Dialogue {
         messages: Vec<String>,
         data: DialogueFrameData,
+        #[serde(default)]
+        events: Vec<GameEvent>,
     },
     StoreView {
         view: StoreView,
         messages: Vec<String>,
+        #[serde(default)]
+        events: Vec<GameEvent>,
     },
     LocationChoice(Choice),
+    Map(map::MapView),
     Error(String),
     Ending {
         stop_type: StopType,
+        #[serde(default)]
+        events: Vec<GameEvent>,
     },
 }
 
@@ -274,6 +312,7 @@ impl Frame {
             | Frame::StoreView { messages, .. } => !messages.is_empty(),
             Frame::Introduction
             | Frame::LocationChoice(_)
+            | Frame::Map(_)
             | Frame::Error(_)
             | Frame::Ending { .. } => true,
         }
@@ -286,10 +325,53 @@ impl Frame {
             Frame::Dialogue { messages, .. } => messages.clone(),
             Frame::StoreView { messages, .. } => messages.clone(),
             Frame::LocationChoice(choice) => choice.presentation_text_lines(),
+            Frame::Map(map) => map.text_lines(),
             Frame::Error(message) => vec![message.to_owned()],
             Frame::Ending { stop_type, .. } => vec![stop_type_message(*stop_type)],
         }
     }
+
+    /// The events that happened since the previous frame.
+    pub fn get_events(&self) -> &[GameEvent] {
+        match self {
+            Frame::AreaView { events, .. }
+            | Frame::Dialogue { events, .. }
+            | Frame::StoreView { events, .. }
+            | Frame::Ending { events, .. } => events,
+            Frame::Introduction | Frame::LocationChoice(_) | Frame::Map(_) | Frame::Error(_) => &[],
+        }
+    }
+
+    /// A copy of this frame without its messages and events, for showing the frame again.
+    pub(crate) fn without_messages(&self) -> Frame {
+        let mut frame = self.clone();
+        match &mut frame {
+            Frame::AreaView { messages, .. }
+            | Frame::Dialogue { messages, .. }
+            | Frame::StoreView { messages, .. } => messages.clear(),
+            Frame::Introduction
+            | Frame::LocationChoice(_)
+            | Frame::Map(_)
+            | Frame::Error(_)
+            | Frame::Ending { .. } => {}
+        }
+        if let Some(events) = frame.events_mut() {
+            events.clear();
+        }
+        frame
+    }
+
+    fn events_mut(&mut self) -> Option<&mut Vec<GameEvent>> {
+        match self {
+            Frame::AreaView { events, .. }
+            | Frame::Dialogue { events, .. }
+            | Frame::StoreView { events, .. }
+            | Frame::Ending { events, .. } => Some(events),
+            Frame::Introduction | Frame::LocationChoice(_) | Frame::Map(_) | Frame::Error(_) => {
+                None
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -348,5 +430,6 @@ fn area_view_frame(buffer: &mut Buffer, state: &mut GameState) -> Frame {
             .pop_messages(&state.world, state.controlled, &mut state.status_cache)
             .into_text(buffer.assets),
         render_data: area::prepare_render_data(state, buffer.assets),
+        events: Vec::new(),
     }
 }
@@ -2,15 +2,16 @@ use crate::asset::GameAssets;
 use crate::asset::background::ParallaxLayer;
 use crate::command::suggestion;
 use crate::command::suggestion::InteractionType;
-use crate::core::area::{Area, BackgroundId};
+use crate::core::area::{self, Area, BackgroundId};
 use crate::core::display::{CreatureVariantSet, DialogueExpression, ModelId, SpeciesColorId};
+use crate::core::hazard::{AreaHazards, Hazard};
 use crate::core::inventory::{self, Held};
 use crate::core::item::ItemTypeId;
 use crate::core::name::{NameData, NameWithAttribute};
 use crate::core::position::{Coord, Direction, Pos};
 use crate::core::status::{self, Health, Morale};
 use crate::core::store::Shopkeeper;
-use crate::core::{BlockType, Door, IsCut, SpeciesId};
+use crate::core::{BlockType, CrewMember, Door, IsCut, SpeciesId};
 use crate::deref_clone;
 use crate::game_loop::GameState;
 use crate::view::text;
@@ -25,6 +26,11 @@ pub struct RenderData {
     pub background_offset: i32,
     pub extra_background_layers: Vec<ParallaxLayer<String>>,
     pub darkness: f32,
+    #[serde(default)]
+    pub hazards: Vec<Hazard>,
+    /// The names of the hazards, in the same order as `hazards`.
+    #[serde(default)]
+    pub hazard_names: Vec<String>,
     pub character_coord: Coord,
     pub inventory: Vec<ItemProfile>,
     pub objects: Vec<ObjectRenderData>,
@@ -50,6 +56,13 @@ pub struct ObjectNameData {
 }
 
 impl ObjectNameData {
+    fn unknown(name: &str) -> Self {
+        Self {
+            modified_name: name.to_owned(),
+            name: name.to_owned(),
+        }
+    }
+
     fn build(entity_ref: EntityRef, world: &World, assets: &GameAssets) -> Option<Self> {
         let name = NameWithAttribute::lookup_option_by_ref(entity_ref, assets)?.base();
         Some(Self {
@@ -113,6 +126,10 @@ pub struct ItemProfile {
     pub is_wielded: bool,
     #[serde(default)]
     pub is_usable: bool,
+    #[serde(default)]
+    pub is_wearable: bool,
+    #[serde(default)]
+    pub is_worn: bool,
 }
 
 impl ItemProfile {
@@ -128,6 +145,11 @@ impl ItemProfile {
                 .get::<&ItemTypeId>()
                 .and_then(|id| assets.item_type_map.get(&id))
                 .is_some_and(|data| data.usage.is_some()),
+            is_wearable: item
+                .get::<&ItemTypeId>()
+                .and_then(|id| assets.item_type_map.get(&id))
+                .is_some_and(|data| data.armor.is_some()),
+            is_worn: item.get::<&Held>().is_some_and(|held| held.is_worn()),
         }
     }
 
@@ -139,38 +161,68 @@ impl ItemProfile {
 pub(super) fn prepare_render_data(state: &GameState, assets: &GameAssets) -> RenderData {
     let character_pos = state.world.get::<&Pos>(state.controlled).unwrap();
     let area = state.world.get::<&Area>(character_pos.get_area()).unwrap();
+    let is_dark = area::is_dark(character_pos.get_area(), &state.world, assets);
 
     let objects: Vec<ObjectRenderData> = state
         .world
         .query::<(Entity, &Pos)>()
         .iter()
         .filter(|&(_, pos)| pos.is_in(character_pos.get_area()))
-        .map(|(entity, pos)| build_object_data(state, entity, pos, assets))
+        .map(|(entity, pos)| build_object_data(state, entity, pos, is_dark, assets))
         .collect();
 
     let inventory = inventory::get_held(&state.world, state.controlled)
         .into_iter()
         .map(|item| ItemProfile::create(state.world.entity(item).unwrap(), assets))
         .collect();
+    let hazards = state
+        .world
+        .get::<&AreaHazards>(character_pos.get_area())
+        .map(|hazards| hazards.0.clone())
+        .unwrap_or_default();
+    let hazard_names = hazards
+        .iter()
+        .map(|hazard| hazard.kind.name(assets))
+        .collect();
     RenderData {
         area_size: area.size,
         background: area.background.clone(),
         background_offset: area.background_offset,
         extra_background_layers: area.extra_background_layers.clone(),
         darkness: area.darkness,
+        hazards,
+        hazard_names,
         character_coord: character_pos.get_coord(),
         inventory,
         objects,
     }
 }
 
+/// Items and creatures outside the crew can not be made out in a dark area.
+/// Returns the id of the message to show in place of the name.
+fn unknown_name_in_darkness(entity_ref: EntityRef) -> Option<&'static str> {
+    if entity_ref.has::<ItemTypeId>() {
+        Some("darkness.unknown_item")
+    } else if entity_ref.has::<Health>() && !entity_ref.has::<CrewMember>() {
+        Some("darkness.unknown_creature")
+    } else {
+        None
+    }
+}
+
 fn build_object_data(
     state: &GameState,
     entity: Entity,
     pos: &Pos,
+    is_dark: bool,
     assets: &GameAssets,
 ) -> ObjectRenderData {
     let entity_ref = state.world.entity(entity).unwrap();
+    let unknown_name = if is_dark {
+        unknown_name_in_darkness(entity_ref)
+    } else {
+        None
+    };
     let morale = entity_ref
         .get::<&Morale>()
         .as_deref()
@@ -218,9 +270,18 @@ fn build_object_data(
             hasher.finish()
         },
         is_controlled: entity == state.controlled,
-        name_data: ObjectNameData::build(entity_ref, &state.world, assets),
+        name_data: match unknown_name {
+            Some(message_id) => Some(ObjectNameData::unknown(
+                &assets.messages.text(message_id, &[]),
+            )),
+            None => ObjectNameData::build(entity_ref, &state.world, assets),
+        },
         wielded_item: find_wielded_item_texture(&state.world, entity),
-        interactions: suggestion::interactions_for(entity, state, assets),
+        interactions: if unknown_name.is_some() {
+            Vec::new()
+        } else {
+            suggestion::interactions_for(entity, state, assets)
+        },
         properties,
     }
 }
@@ -0,0 +1,224 @@
+use crate::core::combat::{AttackKind, HitType};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// The file that the combat log is exported to.
+pub const EXPORT_PATH: &str = "combat_log.csv";
+
+/// The values that went into the d20 roll for whether an attack hits.
+#[derive(Clone, Debug)]
+pub struct HitRoll {
+    pub roll: i16,
+    pub difficulty: i16,
+    pub target_luck: i16,
+    /// The dodge bonus from the agility of the target, scaled by its remaining stamina.
+    pub target_dodge: f32,
+    pub attacker_agility: i16,
+    pub attacker_luck: i16,
+    pub attack_kind_modifier: i16,
+    /// Penalties from darkness and from the distance of a ranged attack.
+    pub penalty: i16,
+}
+
+/// The factors that the damage of a hit was calculated from.
+#[derive(Clone, Debug)]
+pub struct DamageBreakdown {
+    pub weapon_damage: f32,
+    pub strength_factor: f32,
+    /// The factor from `Morale::damage_factor` of the attacker.
+    pub morale_factor: f32,
+    /// The factor from the hit type and the attack kind.
+    pub hit_factor: f32,
+    /// The damage after the armor of the target has been applied.
+    pub dealt: f32,
+}
+
+impl DamageBreakdown {
+    pub fn before_armor(&self) -> f32 {
+        self.weapon_damage * self.strength_factor * self.morale_factor * self.hit_factor
+    }
+}
+
+/// The values that went into the d20 roll for whether a hit stuns the target.
+#[derive(Clone, Debug)]
+pub struct StunRoll {
+    pub roll: i16,
+    pub difficulty: i16,
+}
+
+impl StunRoll {
+    pub fn is_success(&self) -> bool {
+        self.roll >= self.difficulty
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AttackRecord {
+    pub attacker: String,
+    pub target: String,
+    pub kind: AttackKind,
+    pub hit_roll: HitRoll,
+    pub hit: HitType,
+    pub damage: Option<DamageBreakdown>,
+    pub stun_roll: Option<StunRoll>,
+    pub killed: bool,
+}
+
+impl AttackRecord {
+    pub fn lines(&self) -> Vec<String> {
+        let HitRoll {
+            roll,
+            difficulty,
+            target_luck,
+            target_dodge,
+            attacker_agility,
+            attacker_luck,
+            attack_kind_modifier,
+            penalty,
+        } = self.hit_roll;
+        let mut lines = vec![
+            format!(
+                "{} -> {} ({} attack): {}",
+                self.attacker,
+                self.target,
+                kind_name(self.kind),
+                self.outcome_text(),
+            ),
+            format!(
+                "  Hit roll {roll} against {difficulty} (target luck {target_luck}, target dodge {target_dodge:.1}, attacker agility {attacker_agility}, attacker luck {attacker_luck}, attack kind {attack_kind_modifier:+}, penalty {penalty:+})"
+            ),
+        ];
+        if let Some(damage) = &self.damage {
+            lines.push(format!(
+                "  Damage {:.2} (weapon {:.2} x strength {:.2} x morale {:.2} x hit {:.2} = {:.2} before armor)",
+                damage.dealt,
+                damage.weapon_damage,
+                damage.strength_factor,
+                damage.morale_factor,
+                damage.hit_factor,
+                damage.before_armor(),
+            ));
+        }
+        if let Some(stun_roll) = &self.stun_roll {
+            lines.push(format!(
+                "  Stun roll {} against {}",
+                stun_roll.roll, stun_roll.difficulty
+            ));
+        }
+        lines
+    }
+
+    fn outcome_text(&self) -> &'static str {
+        if self.killed {
+            return "killed";
+        }
+        if self.stun_roll.as_ref().is_some_and(StunRoll::is_success) {
+            return "stunned";
+        }
+        match self.hit {
+            HitType::DirectHit => "direct hit",
+            HitType::GrazingHit => "grazing hit",
+            HitType::Dodge => "dodged",
+        }
+    }
+
+    fn write_csv_row(&self, writer: &mut impl Write) -> io::Result<()> {
+        let hit_roll = &self.hit_roll;
+        let damage = self.damage.as_ref();
+        let optional = |value: Option<String>| value.unwrap_or_default();
+        writeln!(
+            writer,
+            "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+            csv_field(&self.attacker),
+            csv_field(&self.target),
+            kind_name(self.kind),
+            hit_roll.roll,
+            hit_roll.difficulty,
+            hit_roll.target_luck,
+            hit_roll.target_dodge,
+            hit_roll.attacker_agility,
+            hit_roll.attacker_luck,
+            hit_roll.attack_kind_modifier,
+            hit_roll.penalty,
+            match self.hit {
+                HitType::DirectHit => "direct_hit",
+                HitType::GrazingHit => "grazing_hit",
+                HitType::Dodge => "dodge",
+            },
+            optional(damage.map(|damage| damage.weapon_damage.to_string())),
+            optional(damage.map(|damage| damage.strength_factor.to_string())),
+            optional(damage.map(|damage| damage.morale_factor.to_string())),
+            optional(damage.map(|damage| damage.hit_factor.to_string())),
+            optional(damage.map(|damage| damage.before_armor().to_string())),
+            optional(damage.map(|damage| damage.dealt.to_string())),
+            optional(self.stun_roll.as_ref().map(|roll| roll.roll.to_string())),
+            optional(
+                self.stun_roll
+                    .as_ref()
+                    .map(|roll| roll.difficulty.to_string())
+            ),
+            self.killed,
+        )
+    }
+}
+
+const CSV_HEADER: &str = "attacker,target,attack_kind,hit_roll,hit_difficulty,target_luck,target_dodge,attacker_agility,attacker_luck,attack_kind_modifier,hit_penalty,hit_type,weapon_damage,strength_factor,morale_factor,hit_factor,damage_before_armor,damage_dealt,stun_roll,stun_difficulty,killed";
+
+fn kind_name(kind: AttackKind) -> &'static str {
+    match kind {
+        AttackKind::Light => "light",
+        AttackKind::Rash => "rash",
+        AttackKind::Charged => "charged",
+    }
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_owned()
+    }
+}
+
+/// A record of the rolls and modifiers behind each attack, for looking into combat balance.
+/// Only kept when enabled through [crate::game_interface::Game::enable_combat_log].
+#[derive(Clone, Debug, Default)]
+pub struct CombatLog {
+    records: Vec<AttackRecord>,
+}
+
+impl CombatLog {
+    pub fn add(&mut self, record: AttackRecord) {
+        #[cfg(feature = "debug_logging")]
+        for line in record.lines() {
+            eprintln!("{line}");
+        }
+        self.records.push(record);
+    }
+
+    pub fn records(&self) -> &[AttackRecord] {
+        &self.records
+    }
+
+    /// Text lines for the given number of most recent attacks.
+    pub fn recent_lines(&self, count: usize) -> Vec<String> {
+        self.records[self.records.len().saturating_sub(count)..]
+            .iter()
+            .flat_map(AttackRecord::lines)
+            .collect()
+    }
+
+    pub fn write_csv(&self, writer: impl Write) -> io::Result<()> {
+        let mut writer = BufWriter::new(writer);
+        writeln!(writer, "{CSV_HEADER}")?;
+        for record in &self.records {
+            record.write_csv_row(&mut writer)?;
+        }
+        writer.flush()
+    }
+
+    pub fn export(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.write_csv(File::create(path)?)
+    }
+}
@@ -0,0 +1,106 @@
+//! Typed events that describe what happened during a turn.
+//! Events are recorded alongside the text messages and attached to frames,
+//! so that frontends and tools can react to the game without having to parse text.
+//! Unlike messages, events are recorded regardless of where they happen,
+//! so a frontend that only cares about what the player sees should filter them by the entities involved.
+
+use crate::core::area::{ShipStatus, ShipUpgrade};
+use crate::core::combat::{AttackKind, HitType};
+use crate::core::effect::StatusEffectKind;
+use crate::core::item::ItemTypeId;
+use hecs::Entity;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum GameEvent {
+    Attack {
+        attacker: Entity,
+        target: Entity,
+        kind: AttackKind,
+        hit: HitType,
+        damage: f32,
+    },
+    Stunned {
+        entity: Entity,
+    },
+    Died {
+        entity: Entity,
+    },
+    StatusEffectApplied {
+        entity: Entity,
+        effect: StatusEffectKind,
+    },
+    StatusEffectEnded {
+        entity: Entity,
+        effect: StatusEffectKind,
+    },
+    ItemTaken {
+        character: Entity,
+        item: Entity,
+        item_type: ItemTypeId,
+    },
+    ItemWielded {
+        character: Entity,
+        item: Entity,
+    },
+    ItemWorn {
+        character: Entity,
+        item: Entity,
+    },
+    ItemTakenOff {
+        character: Entity,
+        item: Entity,
+    },
+    ItemGiven {
+        giver: Entity,
+        receiver: Entity,
+        item: Entity,
+    },
+    ItemStoredInShip {
+        character: Entity,
+        item: Entity,
+    },
+    ItemRetrievedFromShip {
+        character: Entity,
+        item: Entity,
+    },
+    /// The item has been consumed by the use, so the item entity no longer exists.
+    ItemUsed {
+        character: Entity,
+        item: Entity,
+        item_type: ItemTypeId,
+    },
+    DoorEntered {
+        character: Entity,
+        door: Entity,
+        destination_area: Entity,
+    },
+    DoorForced {
+        character: Entity,
+        door: Entity,
+    },
+    DoorUnlocked {
+        character: Entity,
+        door: Entity,
+    },
+    JoinedCrew {
+        character: Entity,
+    },
+    ShipStatusChanged {
+        character: Entity,
+        status: ShipStatus,
+    },
+    ShipUpgradeInstalled {
+        character: Entity,
+        upgrade: ShipUpgrade,
+    },
+    LocationEntered {
+        location: String,
+    },
+    LeftBehind {
+        character: Entity,
+    },
+    ControlledCharacterChanged {
+        character: Entity,
+    },
+}
@@ -0,0 +1,172 @@
+use crate::asset::GameAssets;
+use crate::core::area::{self, Area, Explored};
+use crate::core::name::NameData;
+use crate::core::position::{Coord, Pos};
+use crate::core::{CrewMember, Door};
+use crate::game_loop::GameState;
+use crate::view::text;
+use hecs::{Entity, World};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// A map of the areas in the current location that have been explored by the crew.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MapView {
+    pub areas: Vec<MapArea>,
+    /// Door connections between explored areas, as pairs of map positions.
+    pub connections: Vec<((i32, i32), (i32, i32))>,
+    pub crew_in_ship: Vec<String>,
+    /// The map as text, resolved from the message catalog when the map is prepared.
+    #[serde(default)]
+    pub text: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MapArea {
+    pub label: String,
+    /// The map position of the leftmost coordinate of the area.
+    pub start: (i32, i32),
+    pub size: Coord,
+    pub crew: Vec<String>,
+    pub has_ship: bool,
+    pub has_controlled: bool,
+    /// The labels of the explored areas that this area has known doors to.
+    pub neighbours: Vec<String>,
+}
+
+impl MapView {
+    pub fn text_lines(&self) -> Vec<String> {
+        self.text.clone()
+    }
+}
+
+fn build_text(areas: &[MapArea], crew_in_ship: &[String], assets: &GameAssets) -> Vec<String> {
+    if areas.is_empty() {
+        return vec![assets.messages.text("map.unexplored", &[])];
+    }
+    let mut lines = vec![assets.messages.text("map.header", &[])];
+    for area in areas {
+        let mut line = area.label.clone();
+        if area.has_ship {
+            line = assets
+                .messages
+                .text("map.area.with_ship", &[("area", &line)]);
+        }
+        if !area.crew.is_empty() {
+            line = assets.messages.text(
+                "map.area.with_crew",
+                &[("area", &line), ("crew", &area.crew.join(", "))],
+            );
+        }
+        if !area.neighbours.is_empty() {
+            line = assets.messages.text(
+                "map.area.with_neighbours",
+                &[("area", &line), ("neighbours", &area.neighbours.join(", "))],
+            );
+        }
+        lines.push(line);
+    }
+    if !crew_in_ship.is_empty() {
+        lines.push(
+            assets
+                .messages
+                .text("map.crew_in_ship", &[("crew", &crew_in_ship.join(", "))]),
+        );
+    }
+    lines
+}
+
+/// The map position of a coordinate in the area, with the area centered on its overview position.
+fn map_pos(area: &Area, coord: Coord) -> (i32, i32) {
+    (
+        area.pos_in_overview.0 - area.size as i32 / 2 + coord as i32,
+        area.pos_in_overview.1,
+    )
+}
+
+fn is_explored_location_area(area: Entity, world: &World) -> bool {
+    world.satisfies::<&Explored>(area) && !area::is_ship(area, world)
+}
+
+pub(crate) fn prepare_map_view(state: &GameState, assets: &GameAssets) -> MapView {
+    let world = &state.world;
+    let controlled_area = world.get::<&Pos>(state.controlled).unwrap().get_area();
+
+    let mut crew_in_ship = Vec::new();
+    let mut crew_positions = Vec::new();
+    for (crew_member, pos) in world.query::<(Entity, &Pos)>().with::<&CrewMember>().iter() {
+        let name = text::capitalize(NameData::find(world, crew_member, assets).definite());
+        if area::is_ship(pos.get_area(), world) {
+            crew_in_ship.push(name);
+        } else {
+            crew_positions.push((pos.get_area(), name));
+        }
+    }
+
+    let mut connections = Vec::new();
+    let mut connected_pairs = HashSet::new();
+    let mut neighbours = Vec::new();
+    let mut ship_door_areas = HashSet::new();
+    for (pos, door) in world.query::<(&Pos, &Door)>().iter() {
+        let area = pos.get_area();
+        let destination_area = door.destination.get_area();
+        if !is_explored_location_area(area, world) {
+            continue;
+        }
+        if area::is_ship(destination_area, world) {
+            ship_door_areas.insert(area);
+        } else if is_explored_location_area(destination_area, world) {
+            neighbours.push((area, destination_area));
+            if connected_pairs.insert(door.door_pair) {
+                let (Ok(area_data), Ok(destination_data)) = (
+                    world.get::<&Area>(area),
+                    world.get::<&Area>(destination_area),
+                ) else {
+                    continue;
+                };
+                connections.push((
+                    map_pos(&area_data, pos.get_coord()),
+                    map_pos(&destination_data, door.destination.get_coord()),
+                ));
+            }
+        }
+    }
+
+    let mut areas = world
+        .query::<(Entity, &Area)>()
+        .with::<&Explored>()
+        .iter()
+        .filter(|&(area, _)| !area::is_ship(area, world))
+        .map(|(area, area_data)| {
+            let mut area_neighbours = neighbours
+                .iter()
+                .filter(|&&(from, _)| from == area)
+                .filter_map(|&(_, to)| world.get::<&Area>(to).ok().map(|to| to.label.clone()))
+                .collect::<Vec<_>>();
+            area_neighbours.sort();
+            area_neighbours.dedup();
+            MapArea {
+                label: area_data.label.clone(),
+                start: map_pos(area_data, 0),
+                size: area_data.size,
+                crew: crew_positions
+                    .iter()
+                    .filter(|(crew_area, _)| *crew_area == area)
+                    .map(|(_, name)| name.clone())
+                    .collect(),
+                has_ship: ship_door_areas.contains(&area),
+                has_controlled: area == controlled_area,
+                neighbours: area_neighbours,
+            }
+        })
+        .collect::<Vec<_>>();
+    areas.sort_by_key(|area| (-area.start.1, area.start.0));
+
+    let text = build_text(&areas, &crew_in_ship, assets);
+    MapView {
+        areas,
+        connections,
+        crew_in_ship,
+        text,
+    }
+}
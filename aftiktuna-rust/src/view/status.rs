@@ -1,6 +1,8 @@
 use super::text::{self, Messages};
 use crate::asset::GameAssets;
 use crate::core::area::{self, FuelAmount, ShipState, ShipStatus};
+use crate::core::combat;
+use crate::core::effect::{StatusEffectKind, StatusEffects};
 use crate::core::item::ItemTypeId;
 use crate::core::name::{self, Name, NameData, NameIdData, NounId};
 use crate::core::position::Pos;
@@ -22,6 +24,10 @@ struct CharacterCache {
     character_id: Entity,
     health: f32,
     wielded: Option<Entity>,
+    #[serde(default)]
+    worn: Option<Entity>,
+    #[serde(default)]
+    effects: Vec<StatusEffectKind>,
     inventory: Vec<Entity>,
 }
 
@@ -71,6 +77,26 @@ pub fn get_full_status(state: &GameState, assets: &GameAssets) -> FullStatus {
         })
         .count();
     ship_messages.add(format!("Food rations at ship: {ration_count}"));
+    if let Ok(ship_state) = state.world.get::<&ShipState>(state.ship_core)
+        && !ship_state.upgrades.is_empty()
+    {
+        let upgrade_names = ship_state
+            .upgrades
+            .iter()
+            .map(|upgrade| upgrade.name().to_owned())
+            .collect::<Vec<_>>();
+        ship_messages.add(assets.messages.text(
+            "ship.upgrade.status",
+            &[("upgrades", &upgrade_names.join(", "))],
+        ));
+    }
+    ship_messages.add(assets.messages.text(
+        "ship.cargo.status",
+        &[
+            ("count", &area::stored_items(&state.world).len().to_string()),
+            ("capacity", &area::SHIP_CARGO_CAPACITY.to_string()),
+        ],
+    ));
 
     let crew = state
         .world
@@ -117,6 +143,13 @@ pub fn get_full_status(state: &GameState, assets: &GameAssets) -> FullStatus {
                 .copied()
                 .unwrap_or_default();
             character_messages.add(format!("Morale: {}", morale.state().label()));
+            print_effects(
+                &state.world,
+                character,
+                &mut character_messages,
+                None,
+                assets,
+            );
 
             print_wielded(
                 &state.world,
@@ -125,6 +158,13 @@ pub fn get_full_status(state: &GameState, assets: &GameAssets) -> FullStatus {
                 None,
                 assets,
             );
+            print_worn(
+                &state.world,
+                character,
+                &mut character_messages,
+                None,
+                assets,
+            );
             print_inventory(
                 &state.world,
                 character,
@@ -197,6 +237,8 @@ fn print_character_with_cache(
     if cache.character_id == character {
         cache.health = print_health(world, character, messages, Some(cache.health));
         cache.wielded = print_wielded(world, character, messages, Some(cache.wielded), assets);
+        cache.worn = print_worn(world, character, messages, Some(cache.worn), assets);
+        cache.effects = print_effects(world, character, messages, Some(&cache.effects), assets);
         cache.inventory =
             print_inventory(world, character, messages, Some(&cache.inventory), assets);
     } else {
@@ -212,11 +254,15 @@ fn print_character_without_cache(
 ) -> CharacterCache {
     let health = print_health(world, character, messages, None);
     let wielded = print_wielded(world, character, messages, None, assets);
+    let worn = print_worn(world, character, messages, None, assets);
+    let effects = print_effects(world, character, messages, None, assets);
     let inventory = print_inventory(world, character, messages, None, assets);
     CharacterCache {
         character_id: character,
         health,
         wielded,
+        worn,
+        effects,
         inventory,
     }
 }
@@ -290,6 +336,69 @@ fn print_wielded(
     wielded
 }
 
+fn print_worn(
+    world: &World,
+    character: Entity,
+    messages: &mut Messages,
+    prev_worn: Option<Option<Entity>>,
+    assets: &GameAssets,
+) -> Option<Entity> {
+    let worn = inventory::get_worn(world, character);
+
+    if Some(worn) == prev_worn {
+        return worn;
+    }
+
+    let armor_text = worn.map_or_else(
+        || "Nothing".to_string(),
+        |item| {
+            let armor = combat::get_worn_armor_properties(world, character, assets);
+            format!(
+                "{} (blocks {} damage and {}% of the rest)",
+                text::capitalize(NameData::find(world, item, assets).base()),
+                armor.flat_reduction,
+                (armor.percent_reduction * 100.).round(),
+            )
+        },
+    );
+    messages.add(format!("Wearing {armor_text}"));
+    worn
+}
+
+fn print_effects(
+    world: &World,
+    character: Entity,
+    messages: &mut Messages,
+    prev_effects: Option<&Vec<StatusEffectKind>>,
+    assets: &GameAssets,
+) -> Vec<StatusEffectKind> {
+    let Ok(effects) = world.get::<&StatusEffects>(character) else {
+        if prev_effects.is_some_and(|prev_effects| !prev_effects.is_empty()) {
+            messages.add("Status effects: None");
+        }
+        return Vec::new();
+    };
+    let kinds = effects
+        .0
+        .iter()
+        .map(|effect| effect.kind)
+        .collect::<Vec<_>>();
+
+    if prev_effects == Some(&kinds) || (prev_effects.is_none() && kinds.is_empty()) {
+        return kinds;
+    }
+
+    if kinds.is_empty() {
+        messages.add("Status effects: None");
+    } else {
+        messages.add(format!(
+            "Status effects: {}",
+            effects.descriptions(assets).join(", ")
+        ));
+    }
+    kinds
+}
+
 fn print_inventory(
     world: &World,
     character: Entity,
@@ -68,102 +68,100 @@ impl CombinableMsgType {
 
     fn into_text(self, entities: Vec<NameIdData>, assets: &GameAssets) -> String {
         use CombinableMsgType::*;
-        match self {
-            Noise => format!(
-                "Something is making noise in the direction of {the_paths}.",
-                the_paths = join_elements(unique(
-                    entities
-                        .into_iter()
-                        .map(|name| name.lookup(assets).definite())
-                ))
+        let definite_names = |entities: Vec<NameIdData>| {
+            join_elements(
+                entities
+                    .into_iter()
+                    .map(|name| name.lookup(assets).definite())
+                    .collect(),
+            )
+        };
+        let counted_names = |entities: Vec<NameIdData>, article: name::ArticleKind| {
+            join_elements(name::names_with_counts(
+                entities,
+                article,
+                name::CountFormat::Text,
+                assets,
+            ))
+        };
+        let text = match self {
+            Noise => assets.messages.text(
+                "combinable.noise",
+                &[(
+                    "paths",
+                    &join_elements(unique(
+                        entities
+                            .into_iter()
+                            .map(|name| name.lookup(assets).definite()),
+                    )),
+                )],
             ),
-            EnterDoor(_, door_name) => format!(
-                "{the_characters} entered {the_door} into a new area.",
-                the_characters = capitalize(join_elements(
-                    entities
-                        .into_iter()
-                        .map(|name| name.lookup(assets).definite())
-                        .collect()
-                )),
-                the_door = door_name.lookup(assets).definite(),
+            EnterDoor(_, door_name) => assets.messages.text(
+                "combinable.enter_door",
+                &[
+                    ("characters", &definite_names(entities)),
+                    ("door", &door_name.lookup(assets).definite()),
+                ],
             ),
-            EnterPath(_, path_name) => format!(
-                "{the_characters} followed {the_path} to a new area.",
-                the_characters = capitalize(join_elements(
-                    entities
-                        .into_iter()
-                        .map(|name| name.lookup(assets).definite())
-                        .collect()
-                )),
-                the_path = path_name.lookup(assets).definite(),
+            EnterPath(_, path_name) => assets.messages.text(
+                "combinable.enter_path",
+                &[
+                    ("characters", &definite_names(entities)),
+                    ("path", &path_name.lookup(assets).definite()),
+                ],
             ),
-            Arrive(_) => format!(
-                "{the_characters} arrived from a nearby area.",
-                the_characters = capitalize(join_elements(name::names_with_counts(
-                    entities,
-                    name::ArticleKind::A,
-                    name::CountFormat::Text,
-                    assets,
-                )))
+            Arrive(_) => assets.messages.text(
+                "combinable.arrive",
+                &[("characters", &counted_names(entities, name::ArticleKind::A))],
             ),
-            PickUp(performer_name) => format!(
-                "{the_performer} picked up {the_items}.",
-                the_performer = capitalize(performer_name.lookup(assets).definite()),
-                the_items = join_elements(name::names_with_counts(
-                    entities,
-                    name::ArticleKind::The,
-                    name::CountFormat::Text,
-                    assets,
-                ))
+            PickUp(performer_name) => assets.messages.text(
+                "combinable.pick_up",
+                &[
+                    ("performer", &performer_name.lookup(assets).definite()),
+                    ("items", &counted_names(entities, name::ArticleKind::The)),
+                ],
             ),
             Threatening => {
                 if let [entity] = &entities[..] {
-                    format!(
-                        "{the_creature} makes a threatening pose.",
-                        the_creature = capitalize(entity.clone().lookup(assets).definite()),
+                    assets.messages.text(
+                        "combinable.threatening.one",
+                        &[("creature", &entity.clone().lookup(assets).definite())],
                     )
                 } else {
-                    format!(
-                        "{the_creatures} make threatening poses.",
-                        the_creatures = capitalize(join_elements(name::names_with_counts(
-                            entities,
-                            name::ArticleKind::The,
-                            name::CountFormat::Text,
-                            assets,
-                        ))),
+                    assets.messages.text(
+                        "combinable.threatening.many",
+                        &[(
+                            "creatures",
+                            &counted_names(entities, name::ArticleKind::The),
+                        )],
                     )
                 }
             }
             Attacking => {
                 if let [entity] = &entities[..] {
-                    format!(
-                        "{the_creature} moves in to attack.",
-                        the_creature = capitalize(entity.clone().lookup(assets).definite()),
+                    assets.messages.text(
+                        "combinable.attacking.one",
+                        &[("creature", &entity.clone().lookup(assets).definite())],
                     )
                 } else {
-                    format!(
-                        "{the_creatures} move in to attack.",
-                        the_creatures = capitalize(join_elements(name::names_with_counts(
-                            entities,
-                            name::ArticleKind::The,
-                            name::CountFormat::Text,
-                            assets,
-                        ))),
+                    assets.messages.text(
+                        "combinable.attacking.many",
+                        &[(
+                            "creatures",
+                            &counted_names(entities, name::ArticleKind::The),
+                        )],
                     )
                 }
             }
-            Refuel => {
-                format!(
-                    "{the_characters} refueled the ship.",
-                    the_characters = capitalize(join_elements(name::names_with_counts(
-                        entities,
-                        name::ArticleKind::The,
-                        name::CountFormat::Text,
-                        assets,
-                    )))
-                )
-            }
-        }
+            Refuel => assets.messages.text(
+                "combinable.refuel",
+                &[(
+                    "characters",
+                    &counted_names(entities, name::ArticleKind::The),
+                )],
+            ),
+        };
+        capitalize(text)
     }
 }
 